@@ -0,0 +1,89 @@
+use newsapi_rs::client::NewsApiClient;
+use newsapi_rs::error::ApiClientError;
+use newsapi_rs::model::{GetEverythingRequest, Language};
+use newsapi_rs::translate::{translate_articles, Translator};
+use serde_json::{json, Value};
+use std::future::Future;
+use std::pin::Pin;
+
+/// Translator for a LibreTranslate-compatible endpoint, which accepts
+/// `{"q": [...], "source": "auto", "target": "en"}` and answers with
+/// `{"translatedText": [...]}`.
+#[derive(Debug)]
+struct HttpTranslator {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl Translator for HttpTranslator {
+    fn translate<'a>(
+        &'a self,
+        texts: &'a [String],
+        target: &'a Language,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<String>, ApiClientError>> + Send + 'a>> {
+        Box::pin(async move {
+            let response: Value = self
+                .client
+                .post(&self.url)
+                .json(&json!({
+                    "q": texts,
+                    "source": "auto",
+                    "target": target.to_string(),
+                    "format": "text",
+                }))
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            response["translatedText"]
+                .as_array()
+                .map(|texts| {
+                    texts
+                        .iter()
+                        .map(|text| text.as_str().unwrap_or_default().to_string())
+                        .collect()
+                })
+                .ok_or_else(|| {
+                    ApiClientError::Translation(format!("unexpected response: {response}"))
+                })
+        })
+    }
+}
+
+/// Run with: TRANSLATE_URL=http://localhost:5000/translate \
+///     cargo run --example http_translator --features translate
+#[tokio::main]
+async fn main() {
+    dotenvy::dotenv().ok();
+
+    let translator = HttpTranslator {
+        client: reqwest::Client::new(),
+        url: std::env::var("TRANSLATE_URL").expect("TRANSLATE_URL must be set"),
+    };
+    let client = NewsApiClient::builder()
+        .build()
+        .expect("Failed to build NewsApiClient");
+
+    let request = GetEverythingRequest::builder()
+        .search_term(String::from("énergie"))
+        .language(Language::FR)
+        .page_size(5)
+        .build();
+
+    let mut articles = match client.get_everything(&request).await {
+        Ok(response) => response.into_articles(),
+        Err(e) => {
+            eprintln!("Error: {e}");
+            return;
+        }
+    };
+
+    if let Err(e) = translate_articles(&translator, &mut articles, &Language::EN).await {
+        eprintln!("Translation failed, showing originals: {e}");
+    }
+    for article in &articles {
+        println!("- {}", article.get_title());
+    }
+}
@@ -0,0 +1,223 @@
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use newsapi_rs::client::NewsApiClient;
+use newsapi_rs::error::ApiClientError;
+use newsapi_rs::model::{Article, Country, GetEverythingRequest, GetTopHeadlinesRequest};
+use newsapi_rs::{CachePolicy, Endpoint, QuotaBudget, QuotaStatus, RetryStrategy};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+type ProviderFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Vec<Article>, ApiClientError>> + Send + 'a>>;
+
+/// Source of the articles the proxy serves, so that the handlers don't depend on NewsAPI and
+/// can be tested against a fake.
+trait NewsProvider: Send + Sync {
+    fn top_headlines<'a>(&'a self, country: Country) -> ProviderFuture<'a>;
+
+    fn search<'a>(&'a self, query: &'a str, limit: usize) -> ProviderFuture<'a>;
+
+    fn quota(&self) -> QuotaStatus;
+}
+
+impl NewsProvider for NewsApiClient<reqwest::Client> {
+    fn top_headlines<'a>(&'a self, country: Country) -> ProviderFuture<'a> {
+        Box::pin(async move {
+            let request = GetTopHeadlinesRequest::builder()
+                .country(country)
+                .build()
+                .map_err(|e| ApiClientError::InvalidRequest(e.to_string()))?;
+            Ok(self.get_top_headlines(&request).await?.into_articles())
+        })
+    }
+
+    fn search<'a>(&'a self, query: &'a str, limit: usize) -> ProviderFuture<'a> {
+        Box::pin(async move {
+            let request = GetEverythingRequest::builder()
+                .search_term(query.to_string())
+                .build();
+            self.collect_everything(&request, limit).await
+        })
+    }
+
+    fn quota(&self) -> QuotaStatus {
+        self.quota_status()
+    }
+}
+
+/// Request counters per endpoint, fed by the client's `on_request` callback.
+#[derive(Default)]
+struct Metrics {
+    endpoints: Mutex<HashMap<Endpoint, EndpointMetrics>>,
+}
+
+#[derive(Default)]
+struct EndpointMetrics {
+    requests: u64,
+    errors: u64,
+    latency: Duration,
+}
+
+impl Metrics {
+    fn record(&self, endpoint: Endpoint, latency: Duration, error: Option<&ApiClientError>) {
+        let mut endpoints = self.endpoints.lock().unwrap();
+        let metrics = endpoints.entry(endpoint).or_default();
+        metrics.requests += 1;
+        metrics.errors += u64::from(error.is_some());
+        metrics.latency += latency;
+    }
+
+    /// Renders the counters in the Prometheus text format.
+    fn render(&self, quota: &QuotaStatus) -> String {
+        let mut out = String::new();
+        for (endpoint, metrics) in self.endpoints.lock().unwrap().iter() {
+            let _ = writeln!(
+                out,
+                "newsapi_requests_total{{endpoint=\"{endpoint}\"}} {}",
+                metrics.requests
+            );
+            let _ = writeln!(
+                out,
+                "newsapi_errors_total{{endpoint=\"{endpoint}\"}} {}",
+                metrics.errors
+            );
+            let _ = writeln!(
+                out,
+                "newsapi_latency_seconds_sum{{endpoint=\"{endpoint}\"}} {}",
+                metrics.latency.as_secs_f64()
+            );
+        }
+        if let Some(remaining) = quota.get_remaining() {
+            let _ = writeln!(out, "newsapi_quota_remaining {remaining}");
+        }
+        out
+    }
+}
+
+#[derive(Clone)]
+struct AppState {
+    provider: Arc<dyn NewsProvider>,
+    metrics: Arc<Metrics>,
+}
+
+#[derive(Deserialize)]
+struct HeadlinesParams {
+    country: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SearchParams {
+    q: String,
+    limit: Option<usize>,
+}
+
+/// An upstream failure, answered with a status telling clients whether to back off.
+struct ProxyError(ApiClientError);
+
+impl IntoResponse for ProxyError {
+    fn into_response(self) -> Response {
+        let status = match &self.0 {
+            ApiClientError::InvalidRequest(_) => StatusCode::BAD_REQUEST,
+            ApiClientError::Paused(_)
+            | ApiClientError::QuotaExceeded { .. }
+            | ApiClientError::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+            ApiClientError::CircuitOpen(_) | ApiClientError::Offline(_) => {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+            _ => StatusCode::BAD_GATEWAY,
+        };
+        (status, Json(json!({ "error": self.0.to_string() }))).into_response()
+    }
+}
+
+async fn headlines(
+    State(state): State<AppState>,
+    Query(params): Query<HeadlinesParams>,
+) -> Result<Json<Vec<Article>>, ProxyError> {
+    let country = params.country.as_deref().unwrap_or("us");
+    let country = Country::from_str(country).map_err(|_| {
+        ProxyError(ApiClientError::InvalidRequest(format!(
+            "unknown country {country:?}"
+        )))
+    })?;
+    let articles = state
+        .provider
+        .top_headlines(country)
+        .await
+        .map_err(ProxyError)?;
+    Ok(Json(articles))
+}
+
+async fn search(
+    State(state): State<AppState>,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<Vec<Article>>, ProxyError> {
+    let limit = params.limit.unwrap_or(20).min(500);
+    let articles = state
+        .provider
+        .search(&params.q, limit)
+        .await
+        .map_err(ProxyError)?;
+    Ok(Json(articles))
+}
+
+async fn render_metrics(State(state): State<AppState>) -> String {
+    state.metrics.render(&state.provider.quota())
+}
+
+/// Serves cached, quota-aware NewsAPI results over HTTP:
+///
+/// - `GET /headlines?country=us` top headlines, revalidated with the API on each request
+/// - `GET /search?q=rust&limit=50` everything search results, paged within the daily budget
+/// - `GET /metrics` request counters and remaining quota in the Prometheus text format
+///
+/// Run with: cargo run --example proxy_service
+#[tokio::main]
+async fn main() {
+    dotenvy::dotenv().ok();
+
+    let metrics = Arc::new(Metrics::default());
+    let recorder = metrics.clone();
+    let client = NewsApiClient::builder()
+        .retry(
+            RetryStrategy::ExponentialJitter {
+                base: Duration::from_millis(200),
+                max: Duration::from_secs(5),
+            },
+            2,
+        )
+        .rate_limit(5, Duration::from_secs(1))
+        .quota_budget(QuotaBudget::daily(100))
+        .cache_policy(Endpoint::TopHeadlines, CachePolicy::Revalidate)
+        .cache_policy(Endpoint::Everything, CachePolicy::StaleIfError)
+        .circuit_breaker(5, Duration::from_secs(30))
+        .on_request(move |endpoint, latency, error| recorder.record(endpoint, latency, error))
+        .build()
+        .expect("Failed to build NewsApiClient");
+
+    let state = AppState {
+        provider: Arc::new(client),
+        metrics,
+    };
+    let app = Router::new()
+        .route("/headlines", get(headlines))
+        .route("/search", get(search))
+        .route("/metrics", get(render_metrics))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .expect("Failed to bind 127.0.0.1:3000");
+    println!("Listening on http://127.0.0.1:3000");
+    axum::serve(listener, app).await.expect("Server failed");
+}
@@ -0,0 +1,107 @@
+use crate::error::ApiClientError;
+use crate::redact::API_KEY_PARAM;
+use crate::secret::SecretString;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use url::Url;
+use zeroize::Zeroizing;
+
+/// Header carrying the API key in [`AuthMode::ApiKeyHeader`].
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// How the client sends its API key to NewsAPI.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AuthMode {
+    /// `Authorization: Bearer <key>`
+    #[default]
+    Bearer,
+    /// `X-Api-Key: <key>`, for proxies that strip or rewrite the `Authorization` header.
+    ApiKeyHeader,
+    /// An `apiKey=<key>` query parameter, for environments that can't set request headers,
+    /// such as some WASM fetch setups. The key is then part of the URL and may be recorded by
+    /// proxies and servers along the way; the client redacts it from its own logs and errors.
+    QueryParameter,
+}
+
+impl AuthMode {
+    /// Adds the header carrying `api_key` in this mode, marked sensitive.
+    pub(crate) fn apply(
+        &self,
+        headers: &mut HeaderMap,
+        api_key: &SecretString,
+    ) -> Result<(), ApiClientError> {
+        let (name, value) = match self {
+            AuthMode::Bearer => (
+                AUTHORIZATION,
+                Zeroizing::new(format!("Bearer {}", api_key.expose_secret())),
+            ),
+            AuthMode::ApiKeyHeader => (
+                HeaderName::from_static(API_KEY_HEADER),
+                Zeroizing::new(api_key.expose_secret().to_string()),
+            ),
+            AuthMode::QueryParameter => return Ok(()),
+        };
+        let mut value = HeaderValue::from_str(&value)?;
+        value.set_sensitive(true);
+        headers.insert(name, value);
+        Ok(())
+    }
+
+    /// The URL to send a request for `url` to, carrying `api_key` in this mode.
+    pub(crate) fn request_url(&self, url: &Url, api_key: &SecretString) -> Zeroizing<String> {
+        match self {
+            AuthMode::QueryParameter => {
+                let mut url = url.clone();
+                url.query_pairs_mut()
+                    .append_pair(API_KEY_PARAM, api_key.expose_secret());
+                Zeroizing::new(url.into())
+            }
+            AuthMode::Bearer | AuthMode::ApiKeyHeader => Zeroizing::new(url.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_applies_key_header() {
+        let api_key = SecretString::from("test-api-key");
+
+        let mut headers = HeaderMap::new();
+        AuthMode::Bearer.apply(&mut headers, &api_key).unwrap();
+        assert_eq!(headers[AUTHORIZATION], "Bearer test-api-key");
+        assert!(headers[AUTHORIZATION].is_sensitive());
+
+        let mut headers = HeaderMap::new();
+        AuthMode::ApiKeyHeader
+            .apply(&mut headers, &api_key)
+            .unwrap();
+        assert_eq!(headers[API_KEY_HEADER], "test-api-key");
+        assert!(headers[API_KEY_HEADER].is_sensitive());
+        assert!(!headers.contains_key(AUTHORIZATION));
+
+        let mut headers = HeaderMap::new();
+        AuthMode::QueryParameter
+            .apply(&mut headers, &api_key)
+            .unwrap();
+        assert!(headers.is_empty());
+    }
+
+    #[test]
+    fn test_appends_key_query_parameter() {
+        let api_key = SecretString::from("test-api-key");
+        let url = Url::parse("https://newsapi.org/v2/everything?q=rust").unwrap();
+
+        assert_eq!(
+            AuthMode::QueryParameter
+                .request_url(&url, &api_key)
+                .as_str(),
+            "https://newsapi.org/v2/everything?q=rust&apiKey=test-api-key"
+        );
+        assert_eq!(
+            AuthMode::Bearer.request_url(&url, &api_key).as_str(),
+            url.as_str()
+        );
+    }
+}
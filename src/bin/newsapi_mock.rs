@@ -0,0 +1,89 @@
+//! Local NewsAPI imitation serving generated data, for end-to-end tests without a real key.
+//!
+//! ```text
+//! newsapi-mock [--addr 127.0.0.1:8080] [--seed N] [--total-results N]
+//!              [--fail-with CODE] [--failure-rate RATE] [--from DATE --to DATE]
+//! ```
+
+use chrono::{DateTime, Utc};
+use newsapi_rs::mock_server::{MockServer, MockServerConfig};
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: newsapi-mock [--addr ADDR] [--seed N] [--total-results N] \
+                     [--fail-with CODE] [--failure-rate RATE] [--from DATE --to DATE]";
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let mut addr = "127.0.0.1:8080".to_string();
+    let mut config = MockServerConfig::new();
+    let mut from: Option<DateTime<Utc>> = None;
+    let mut to: Option<DateTime<Utc>> = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        if flag == "--help" || flag == "-h" {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        let Some(value) = args.next() else {
+            eprintln!("Missing value for {flag}\n{USAGE}");
+            return ExitCode::FAILURE;
+        };
+        let parsed = match flag.as_str() {
+            "--addr" => {
+                addr = value;
+                Ok(())
+            }
+            "--seed" => value
+                .parse()
+                .map(|seed| config = config.clone().seed(seed))
+                .map_err(|e| e.to_string()),
+            "--total-results" => value
+                .parse()
+                .map(|total| config = config.clone().total_results(total))
+                .map_err(|e| e.to_string()),
+            "--fail-with" => {
+                config = config.clone().fail_with(value);
+                Ok(())
+            }
+            "--failure-rate" => value
+                .parse()
+                .map(|rate| config = config.clone().failure_rate(rate))
+                .map_err(|e| e.to_string()),
+            "--from" => value
+                .parse()
+                .map(|date| from = Some(date))
+                .map_err(|e: chrono::ParseError| e.to_string()),
+            "--to" => value
+                .parse()
+                .map(|date| to = Some(date))
+                .map_err(|e: chrono::ParseError| e.to_string()),
+            _ => Err("unknown flag".to_string()),
+        };
+        if let Err(e) = parsed {
+            eprintln!("Invalid {flag}: {e}\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    match (from, to) {
+        (Some(from), Some(to)) => config = config.published_between(from, to),
+        (None, None) => {}
+        _ => {
+            eprintln!("--from and --to must be given together\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    match MockServer::bind(&addr, config).await {
+        Ok(server) => {
+            println!("Serving the NewsAPI mock at {}", server.url());
+            server.wait().await;
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Failed to bind {addr}: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
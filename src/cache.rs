@@ -0,0 +1,214 @@
+use crate::error::{ApiClientError, ApiClientErrorCode};
+use chrono::{DateTime, Utc};
+use reqwest::header::{
+    HeaderMap, HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+const MAX_CACHE_ENTRIES: usize = 1024;
+
+/// Per-endpoint policy for caching successful responses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CachePolicy {
+    /// Responses are not cached.
+    #[default]
+    None,
+    /// The most recent successful response for a request is kept and served, flagged as
+    /// stale, when the API is rate limited or unreachable.
+    StaleIfError,
+    /// The most recent successful response is kept with its `ETag` and `Last-Modified`
+    /// headers, which are sent with the next identical request. When the API answers
+    /// `304 Not Modified` the cached body is returned, making polling cheaper.
+    Revalidate,
+}
+
+/// Whether a client may reach the API, see
+/// [`NewsApiClient::set_offline_mode`](crate::client::NewsApiClient::set_offline_mode).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OfflineMode {
+    #[default]
+    Online,
+    /// Every response is served from the client's cache, whatever the endpoint's
+    /// [`CachePolicy`], and misses fail with
+    /// [`ApiClientError::Offline`](crate::error::ApiClientError::Offline). No request is
+    /// sent, so no network or valid API key is needed.
+    CacheOnly,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct CacheEntry {
+    pub(crate) body: String,
+    pub(crate) fetched_at: DateTime<Utc>,
+    pub(crate) validators: Validators,
+}
+
+/// `ETag` and `Last-Modified` of a response, for conditional requests.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Validators {
+    etag: Option<HeaderValue>,
+    last_modified: Option<HeaderValue>,
+}
+
+impl Validators {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Self {
+        Self {
+            etag: headers.get(ETAG).cloned(),
+            last_modified: headers.get(LAST_MODIFIED).cloned(),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+
+    /// Adds `If-None-Match` and `If-Modified-Since` for the stored validators.
+    pub(crate) fn apply(&self, headers: &mut HeaderMap) {
+        if let Some(etag) = &self.etag {
+            headers.insert(IF_NONE_MATCH, etag.clone());
+        }
+        if let Some(last_modified) = &self.last_modified {
+            headers.insert(IF_MODIFIED_SINCE, last_modified.clone());
+        }
+    }
+}
+
+/// Response bodies keyed by request URL, shared by all clones of a client.
+#[derive(Debug, Default)]
+pub(crate) struct ResponseCache {
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl ResponseCache {
+    pub(crate) fn get(&self, key: &str) -> Option<CacheEntry> {
+        self.entries.lock().unwrap().get(key).cloned()
+    }
+
+    pub(crate) fn insert(
+        &self,
+        key: &str,
+        body: String,
+        fetched_at: DateTime<Utc>,
+        validators: Validators,
+    ) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= MAX_CACHE_ENTRIES && !entries.contains_key(key) {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.fetched_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(
+            key.to_string(),
+            CacheEntry {
+                body,
+                fetched_at,
+                validators,
+            },
+        );
+    }
+}
+
+/// Whether an error means the API is rate limited or down, as opposed to a bad request.
+pub(crate) fn is_unavailable(error: &ApiClientError) -> bool {
+    match error {
+        ApiClientError::Http(_)
+        | ApiClientError::Transport(_)
+        | ApiClientError::Paused(_)
+        | ApiClientError::QuotaExceeded { .. }
+        | ApiClientError::CircuitOpen(_)
+        | ApiClientError::RateLimited { .. } => true,
+        ApiClientError::InvalidResponse(response) => matches!(
+            response.code,
+            ApiClientErrorCode::RateLimited
+                | ApiClientErrorCode::ApiKeyExhausted
+                | ApiClientErrorCode::UnexpectedError
+                | ApiClientErrorCode::Unknown(_)
+        ),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ApiClientErrorResponse;
+    use crate::model::ResponseStatus;
+
+    #[test]
+    fn test_response_cache_insert_and_get() {
+        let cache = ResponseCache::default();
+        let fetched_at = Utc::now();
+        cache.insert(
+            "https://newsapi.org/v2/everything?q=a",
+            "{}".to_string(),
+            fetched_at,
+            Validators::default(),
+        );
+
+        let entry = cache.get("https://newsapi.org/v2/everything?q=a").unwrap();
+        assert_eq!(entry.body, "{}");
+        assert_eq!(entry.fetched_at, fetched_at);
+        assert!(cache.get("https://newsapi.org/v2/everything?q=b").is_none());
+    }
+
+    #[test]
+    fn test_response_cache_evicts_oldest() {
+        let cache = ResponseCache::default();
+        let start = Utc::now();
+        for i in 0..=MAX_CACHE_ENTRIES {
+            cache.insert(
+                &i.to_string(),
+                String::new(),
+                start + chrono::Duration::seconds(i as i64),
+                Validators::default(),
+            );
+        }
+
+        assert!(cache.get("0").is_none());
+        assert!(cache.get("1").is_some());
+        assert!(cache.get(&MAX_CACHE_ENTRIES.to_string()).is_some());
+    }
+
+    #[test]
+    fn test_is_unavailable() {
+        let response = |code| {
+            ApiClientError::InvalidResponse(ApiClientErrorResponse {
+                status: ResponseStatus::Error,
+                code,
+                message: String::new(),
+                request_id: None,
+                context: None,
+            })
+        };
+
+        assert!(is_unavailable(&response(ApiClientErrorCode::RateLimited)));
+        assert!(is_unavailable(&response(
+            ApiClientErrorCode::UnexpectedError
+        )));
+        assert!(is_unavailable(&ApiClientError::Paused(Utc::now())));
+        assert!(!is_unavailable(&response(
+            ApiClientErrorCode::ApiKeyInvalid
+        )));
+        assert!(!is_unavailable(&ApiClientError::InvalidRequest(
+            String::new()
+        )));
+    }
+
+    #[test]
+    fn test_validators_become_conditional_headers() {
+        let mut response_headers = HeaderMap::new();
+        response_headers.insert(ETAG, HeaderValue::from_static("\"abc\""));
+        let validators = Validators::from_headers(&response_headers);
+        assert!(!validators.is_empty());
+        assert!(Validators::from_headers(&HeaderMap::new()).is_empty());
+
+        let mut request_headers = HeaderMap::new();
+        validators.apply(&mut request_headers);
+        assert_eq!(request_headers.get(IF_NONE_MATCH).unwrap(), "\"abc\"");
+        assert!(request_headers.get(IF_MODIFIED_SINCE).is_none());
+    }
+}
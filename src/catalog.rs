@@ -0,0 +1,362 @@
+use crate::error::ApiClientError;
+use crate::model::{Country, GetSourcesResponse, NewsCategory, Source, Strictness};
+use chrono::{DateTime, Utc};
+use getset::Getters;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How long [`NewsApiClient::sources_catalog`](crate::client::NewsApiClient::sources_catalog)
+/// reuses a fetched catalog by default.
+pub const DEFAULT_SOURCES_CATALOG_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Response of the sources endpoint compiled into the crate. Regenerate it with
+/// `curl -H "Authorization: $NEWS_API_KEY" https://newsapi.org/v2/top-headlines/sources`.
+#[cfg(feature = "embedded-sources")]
+const EMBEDDED_SOURCES: &str = include_str!("../data/sources.json");
+
+#[cfg(feature = "probe")]
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Reachability of a source's homepage, see [`SourceCatalog::probe_feeds`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Liveness {
+    /// The homepage answered with the given status.
+    Reachable(u16),
+    /// The homepage is gone, failing or could not be reached.
+    Unreachable(String),
+}
+
+impl Liveness {
+    pub fn is_reachable(&self) -> bool {
+        matches!(self, Liveness::Reachable(_))
+    }
+}
+
+/// A source and what is known about its homepage
+#[derive(Debug, Clone, Getters)]
+pub struct CatalogEntry {
+    #[getset(get = "pub with_prefix")]
+    source: Source,
+
+    /// `None` until probed, and for sources without a homepage URL
+    #[getset(get = "pub with_prefix")]
+    liveness: Option<Liveness>,
+}
+
+/// Sources returned by the sources endpoint, annotated with homepage liveness once probed.
+#[derive(Debug, Clone, Default)]
+pub struct SourceCatalog {
+    entries: Vec<CatalogEntry>,
+    embedded: bool,
+}
+
+impl SourceCatalog {
+    /// The sources snapshot compiled into the crate, for looking up and validating source ids
+    /// while the API is unreachable. It may lag behind the live sources list.
+    #[cfg(feature = "embedded-sources")]
+    pub fn embedded() -> Self {
+        let response: GetSourcesResponse =
+            serde_json::from_str(EMBEDDED_SOURCES).expect("embedded sources snapshot is valid");
+        Self {
+            embedded: true,
+            ..response.into()
+        }
+    }
+
+    /// Whether this is the [`embedded`](Self::embedded) snapshot rather than a live response.
+    pub fn is_embedded(&self) -> bool {
+        self.embedded
+    }
+
+    pub fn get_entries(&self) -> &[CatalogEntry] {
+        &self.entries
+    }
+
+    /// Looks up a source by its NewsAPI id.
+    pub fn get(&self, id: &str) -> Option<&CatalogEntry> {
+        self.entries.iter().find(|entry| {
+            entry
+                .source
+                .get_id()
+                .is_some_and(|source_id| source_id == id)
+        })
+    }
+
+    /// Sources publishing in the given category.
+    pub fn by_category(&self, category: &NewsCategory) -> Vec<&CatalogEntry> {
+        let category = category.to_string();
+        self.entries
+            .iter()
+            .filter(|entry| entry.source.get_category() == Some(&category))
+            .collect()
+    }
+
+    /// Sources based in the given country.
+    pub fn by_country(&self, country: &Country) -> Vec<&CatalogEntry> {
+        let country = country.to_string();
+        self.entries
+            .iter()
+            .filter(|entry| entry.source.get_country() == Some(&country))
+            .collect()
+    }
+
+    /// Sends a HEAD request to each source's homepage, at most `concurrency` at a time, and
+    /// records whether it is reachable.
+    ///
+    /// Homepages answering with 404, 410 or a server error count as unreachable. Sources
+    /// without a URL are left unprobed. Fails if the HTTP client for the probes cannot be
+    /// created, or with [`ApiClientError::Cancelled`] if the runtime shuts down mid-probe.
+    #[cfg(feature = "probe")]
+    pub async fn probe_feeds(&mut self, concurrency: usize) -> Result<(), ApiClientError> {
+        let client = reqwest::Client::builder()
+            .timeout(PROBE_TIMEOUT)
+            .user_agent(crate::constant::NEWS_API_CLIENT_USER_AGENT)
+            .build()?;
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(concurrency.max(1)));
+
+        let mut probes = tokio::task::JoinSet::new();
+        for (index, entry) in self.entries.iter().enumerate() {
+            let Some(url) = entry.source.get_url().cloned() else {
+                continue;
+            };
+            let client = client.clone();
+            let semaphore = semaphore.clone();
+            probes.spawn(async move {
+                let _permit = semaphore
+                    .acquire()
+                    .await
+                    .expect("semaphore is never closed");
+                (index, probe(&client, &url).await)
+            });
+        }
+
+        while let Some(joined) = probes.join_next().await {
+            let (index, liveness) = crate::error::join_output(joined)?;
+            self.entries[index].liveness = Some(liveness);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "probe")]
+async fn probe(client: &reqwest::Client, url: &str) -> Liveness {
+    log::debug!("Probing source homepage {url}");
+    match client.head(url).send().await {
+        Ok(response) => {
+            let status = response.status();
+            if status == reqwest::StatusCode::NOT_FOUND
+                || status == reqwest::StatusCode::GONE
+                || status.is_server_error()
+            {
+                Liveness::Unreachable(format!("HTTP {}", status.as_u16()))
+            } else {
+                Liveness::Reachable(status.as_u16())
+            }
+        }
+        Err(e) => Liveness::Unreachable(e.to_string()),
+    }
+}
+
+/// The last fetched [`SourceCatalog`] of a client, shared by its clones.
+#[derive(Debug)]
+pub(crate) struct CatalogCache {
+    ttl: Duration,
+    slot: Mutex<Option<(DateTime<Utc>, Arc<SourceCatalog>)>>,
+}
+
+impl CatalogCache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            slot: Mutex::new(None),
+        }
+    }
+
+    /// The cached catalog, unless it was fetched more than the TTL before `now`.
+    pub(crate) fn get(&self, now: DateTime<Utc>) -> Option<Arc<SourceCatalog>> {
+        let slot = self.slot.lock().unwrap();
+        let (fetched_at, catalog) = slot.as_ref()?;
+        let age = (now - *fetched_at).to_std().unwrap_or_default();
+        (age < self.ttl).then(|| catalog.clone())
+    }
+
+    pub(crate) fn store(&self, fetched_at: DateTime<Utc>, catalog: Arc<SourceCatalog>) {
+        *self.slot.lock().unwrap() = Some((fetched_at, catalog));
+    }
+}
+
+impl From<Vec<Source>> for SourceCatalog {
+    fn from(sources: Vec<Source>) -> Self {
+        Self {
+            entries: sources
+                .into_iter()
+                .map(|source| CatalogEntry {
+                    source,
+                    liveness: None,
+                })
+                .collect(),
+            embedded: false,
+        }
+    }
+}
+
+/// Falls back to the embedded snapshot when the sources catalog could not be fetched, unless
+/// the client is [`Strictness::Strict`].
+pub(crate) fn fetch_failed(
+    error: ApiClientError,
+    strictness: Strictness,
+) -> Result<Arc<SourceCatalog>, ApiClientError> {
+    #[cfg(feature = "embedded-sources")]
+    if strictness == Strictness::Lenient {
+        log::warn!("Using the embedded sources catalog: {error}");
+        return Ok(Arc::new(SourceCatalog::embedded()));
+    }
+    #[cfg(not(feature = "embedded-sources"))]
+    let _ = strictness;
+    Err(error)
+}
+
+impl From<GetSourcesResponse> for SourceCatalog {
+    fn from(response: GetSourcesResponse) -> Self {
+        response.into_sources().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog_with(sources: &[(&str, &str, &str)]) -> SourceCatalog {
+        let sources: Vec<String> = sources
+            .iter()
+            .map(|(id, category, country)| {
+                format!(
+                    r#"{{"id": "{id}", "name": "{id}", "category": "{category}", "country": "{country}"}}"#
+                )
+            })
+            .collect();
+        let response: GetSourcesResponse = serde_json::from_str(&format!(
+            r#"{{"status": "ok", "sources": [{}]}}"#,
+            sources.join(",")
+        ))
+        .unwrap();
+        response.into()
+    }
+
+    fn catalog(sources: &[(&str, Option<String>)]) -> SourceCatalog {
+        let sources: Vec<String> = sources
+            .iter()
+            .map(|(id, url)| {
+                let url = url
+                    .as_ref()
+                    .map_or("null".to_string(), |url| format!("\"{url}\""));
+                format!(r#"{{"id": "{id}", "name": "{id}", "url": {url}}}"#)
+            })
+            .collect();
+        let response: GetSourcesResponse = serde_json::from_str(&format!(
+            r#"{{"status": "ok", "sources": [{}]}}"#,
+            sources.join(",")
+        ))
+        .unwrap();
+        response.into()
+    }
+
+    #[test]
+    fn test_get_by_id() {
+        let catalog = catalog(&[("bbc-news", None), ("cnn", None)]);
+
+        assert_eq!(catalog.get_entries().len(), 2);
+        assert_eq!(catalog.get("cnn").unwrap().get_source().get_name(), "cnn");
+        assert!(catalog.get("reuters").is_none());
+    }
+
+    #[test]
+    fn test_by_category_and_country() {
+        let catalog = catalog_with(&[
+            ("bbc-news", "general", "gb"),
+            ("bbc-sport", "sports", "gb"),
+            ("espn", "sports", "us"),
+        ]);
+
+        let ids = |entries: Vec<&CatalogEntry>| -> Vec<String> {
+            entries
+                .iter()
+                .filter_map(|entry| entry.get_source().get_id().cloned())
+                .collect()
+        };
+        assert_eq!(
+            ids(catalog.by_category(&NewsCategory::Sports)),
+            ["bbc-sport", "espn"]
+        );
+        assert_eq!(
+            ids(catalog.by_country(&Country::GB)),
+            ["bbc-news", "bbc-sport"]
+        );
+        assert!(catalog.by_category(&NewsCategory::Health).is_empty());
+    }
+
+    #[cfg(feature = "embedded-sources")]
+    #[test]
+    fn test_embedded_catalog() {
+        let catalog = SourceCatalog::embedded();
+
+        assert!(catalog.is_embedded());
+        assert_eq!(
+            catalog.get("bbc-news").unwrap().get_source().get_name(),
+            "BBC News"
+        );
+        assert!(!catalog.by_country(&Country::DE).is_empty());
+        assert!(catalog
+            .get_entries()
+            .iter()
+            .all(|entry| entry.get_source().get_id().is_some()));
+    }
+
+    #[test]
+    fn test_catalog_cache_expires_after_ttl() {
+        let cache = CatalogCache::new(Duration::from_secs(60));
+        let fetched_at = Utc::now();
+        assert!(cache.get(fetched_at).is_none());
+
+        cache.store(fetched_at, Arc::new(SourceCatalog::default()));
+        assert!(cache
+            .get(fetched_at + chrono::Duration::seconds(59))
+            .is_some());
+        assert!(cache
+            .get(fetched_at + chrono::Duration::seconds(60))
+            .is_none());
+    }
+
+    #[cfg(feature = "probe")]
+    #[tokio::test]
+    async fn test_probe_feeds() {
+        let mut server = mockito::Server::new_async().await;
+        let _ok = server
+            .mock("HEAD", "/live")
+            .with_status(200)
+            .create_async()
+            .await;
+        let _gone = server
+            .mock("HEAD", "/gone")
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let mut catalog = catalog(&[
+            ("live", Some(format!("{}/live", server.url()))),
+            ("gone", Some(format!("{}/gone", server.url()))),
+            ("down", Some("http://127.0.0.1:1".to_string())),
+            ("none", None),
+        ]);
+        catalog.probe_feeds(2).await.unwrap();
+
+        let liveness = |id| catalog.get(id).unwrap().get_liveness().clone();
+        assert_eq!(liveness("live"), Some(Liveness::Reachable(200)));
+        assert_eq!(
+            liveness("gone"),
+            Some(Liveness::Unreachable("HTTP 404".to_string()))
+        );
+        assert!(!liveness("down").unwrap().is_reachable());
+        assert_eq!(liveness("none"), None);
+    }
+}
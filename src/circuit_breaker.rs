@@ -0,0 +1,201 @@
+use crate::error::{ApiClientError, ApiClientErrorCode};
+use chrono::{DateTime, Utc};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// State of a client's circuit breaker, see
+/// [`NewsApiClientBuilder::circuit_breaker`](crate::client::NewsApiClientBuilder::circuit_breaker).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests are sent normally.
+    Closed,
+    /// Requests fail fast with [`ApiClientError::CircuitOpen`] until the cool-down ends.
+    Open,
+    /// The cool-down has ended and a single trial request decides whether to close the
+    /// circuit again.
+    HalfOpen,
+}
+
+/// Stops sending requests for a cool-down period after consecutive failures, shared by all
+/// clones of a client.
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    failure_threshold: u32,
+    cool_down: chrono::Duration,
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    state: CircuitState,
+    consecutive_failures: u32,
+    /// End of the cool-down when open, or of the trial request when half-open.
+    blocked_until: DateTime<Utc>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(failure_threshold: u32, cool_down: Duration) -> Self {
+        Self {
+            failure_threshold,
+            cool_down: chrono::Duration::from_std(cool_down).unwrap_or(chrono::Duration::MAX),
+            inner: Mutex::new(Inner {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                blocked_until: DateTime::<Utc>::MIN_UTC,
+            }),
+        }
+    }
+
+    pub(crate) fn state(&self) -> CircuitState {
+        self.inner.lock().unwrap().state
+    }
+
+    /// Fails fast while the circuit is open. Once the cool-down has passed, lets one trial
+    /// request through per cool-down period.
+    pub(crate) fn check(&self, now: DateTime<Utc>) -> Result<(), ApiClientError> {
+        let mut inner = self.inner.lock().unwrap();
+        match inner.state {
+            CircuitState::Closed => Ok(()),
+            _ if now < inner.blocked_until => Err(ApiClientError::CircuitOpen(inner.blocked_until)),
+            _ => {
+                log::debug!("Circuit half-open, sending a trial request");
+                inner.state = CircuitState::HalfOpen;
+                inner.blocked_until = self.cool_down_end(now);
+                Ok(())
+            }
+        }
+    }
+
+    fn cool_down_end(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now.checked_add_signed(self.cool_down)
+            .unwrap_or(DateTime::<Utc>::MAX_UTC)
+    }
+
+    /// Records the outcome of a request that passed [`check`](Self::check).
+    pub(crate) fn record<T>(&self, result: &Result<T, ApiClientError>, now: DateTime<Utc>) {
+        let mut inner = self.inner.lock().unwrap();
+        match result.as_ref().map_or_else(outcome, |_| Outcome::Success) {
+            Outcome::Success => {
+                if inner.state != CircuitState::Closed {
+                    log::debug!("Circuit closed");
+                }
+                inner.state = CircuitState::Closed;
+                inner.consecutive_failures = 0;
+            }
+            Outcome::Failure => {
+                inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+                if inner.state == CircuitState::HalfOpen
+                    || inner.consecutive_failures >= self.failure_threshold
+                {
+                    inner.state = CircuitState::Open;
+                    inner.blocked_until = self.cool_down_end(now);
+                    log::warn!(
+                        "Circuit opened after {} consecutive failures, failing fast until {}",
+                        inner.consecutive_failures,
+                        inner.blocked_until
+                    );
+                }
+            }
+            Outcome::NotSent => {
+                // The trial never reached the API, so let the next request try instead.
+                if inner.state == CircuitState::HalfOpen {
+                    inner.state = CircuitState::Open;
+                    inner.blocked_until = now;
+                }
+            }
+        }
+    }
+}
+
+enum Outcome {
+    Success,
+    Failure,
+    NotSent,
+}
+
+/// Classifies an error: the API being unreachable, rate limited or failing counts against the
+/// circuit, while any other API response shows it is healthy.
+fn outcome(error: &ApiClientError) -> Outcome {
+    match error {
+        ApiClientError::Http(_)
+        | ApiClientError::Transport(_)
+        | ApiClientError::RateLimited { .. } => Outcome::Failure,
+        ApiClientError::InvalidResponse(response) => match response.code {
+            ApiClientErrorCode::RateLimited
+            | ApiClientErrorCode::ApiKeyExhausted
+            | ApiClientErrorCode::UnexpectedError
+            | ApiClientErrorCode::Unknown(_) => Outcome::Failure,
+            _ => Outcome::Success,
+        },
+        _ => Outcome::NotSent,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ApiClientErrorResponse;
+    use crate::model::ResponseStatus;
+
+    fn rate_limited() -> Result<(), ApiClientError> {
+        Err(ApiClientError::InvalidResponse(ApiClientErrorResponse {
+            status: ResponseStatus::Error,
+            code: ApiClientErrorCode::RateLimited,
+            message: String::new(),
+            request_id: None,
+            context: None,
+        }))
+    }
+
+    #[test]
+    fn test_opens_after_consecutive_failures() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+        let now = Utc::now();
+
+        breaker.record(&rate_limited(), now);
+        breaker.record(&Ok(()), now);
+        breaker.record(&rate_limited(), now);
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        breaker.record(&rate_limited(), now);
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(matches!(
+            breaker.check(now + chrono::Duration::seconds(59)),
+            Err(ApiClientError::CircuitOpen(until)) if until == now + chrono::Duration::seconds(60)
+        ));
+    }
+
+    #[test]
+    fn test_half_open_trial() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(60));
+        let now = Utc::now();
+        breaker.record(&rate_limited(), now);
+
+        let later = now + chrono::Duration::seconds(60);
+        assert!(breaker.check(later).is_ok());
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(breaker.check(later).is_err());
+
+        breaker.record(&rate_limited(), later);
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        let later = later + chrono::Duration::seconds(60);
+        assert!(breaker.check(later).is_ok());
+        breaker.record(&Ok(()), later);
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_local_error_releases_trial() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(60));
+        let now = Utc::now();
+        breaker.record(&rate_limited(), now);
+
+        let later = now + chrono::Duration::seconds(60);
+        assert!(breaker.check(later).is_ok());
+        breaker.record::<()>(&Err(ApiClientError::Paused(later)), later);
+
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(breaker.check(later).is_ok());
+    }
+}
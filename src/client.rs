@@ -1,50 +1,317 @@
+use crate::auth::AuthMode;
+use crate::cache::{
+    is_unavailable, CacheEntry, CachePolicy, OfflineMode, ResponseCache, Validators,
+};
+use crate::catalog::{self, CatalogCache, SourceCatalog, DEFAULT_SOURCES_CATALOG_TTL};
+use crate::circuit_breaker::{CircuitBreaker, CircuitState};
+use crate::clock::{Clock, SleeperClock, SystemClock};
+use crate::compare::QueryComparison;
+use crate::config::{token_bucket, ConfigCell, LiveConfig, RuntimeConfig};
+#[cfg(feature = "config-file")]
+use crate::config_file::ConfigFile;
+#[cfg(feature = "config-file")]
+use crate::constant::NEWS_API_PROFILE_ENV;
 use crate::constant::{
-    EVERYTHING_ENDPOINT, NEWS_API_CLIENT_USER_AGENT, NEWS_API_KEY_ENV, NEWS_API_URI,
-    SOURCES_ENDPOINT, TOP_HEADLINES_ENDPOINT,
+    MAX_PAGE_SIZE, NEWS_API_CLIENT_USER_AGENT, NEWS_API_KEY_ENV, NEWS_API_REQUEST_ID_HEADER,
+    NEWS_API_URI, RATE_LIMIT_REMAINING_HEADER,
+};
+use crate::endpoint::{ApiVersion, Endpoint};
+use crate::envelope::{select_headers, RawResponse, ResponseEnvelope};
+use crate::error::{
+    join_output, ApiClientError, ApiClientErrorCode, ApiClientErrorResponse, ClientBuildError,
+    ResponseContext,
 };
-use crate::error::{ApiClientError, ApiClientErrorCode, ApiClientErrorResponse};
+use crate::health::Health;
+use crate::http_options::HttpClientOptions;
+use crate::keys::{self, KeyRing, DEFAULT_KEY_COOL_DOWN};
+use crate::log_summary::RequestLog;
 use crate::model::{
-    GetEverythingRequest, GetEverythingResponse, GetSourcesRequest, GetSourcesResponse,
-    GetTopHeadlinesRequest, TopHeadlinesResponse,
+    Article, ArticleSortBy, ArticleWarning, Country, GetEverythingRequest, GetEverythingResponse,
+    GetSourcesRequest, GetSourcesResponse, GetTopHeadlinesRequest, Normalization, PageSizePolicy,
+    ResponseMeta, ResponseStatus, Strictness, TopHeadlinesResponse, WithResponseMeta,
+};
+#[cfg(feature = "opentelemetry")]
+use crate::otel::RequestSpan;
+#[cfg(feature = "blocking")]
+use crate::paginate::BlockingEverythingPaginator;
+use crate::paginate::{EverythingPaginator, PagePlan};
+use crate::profile::Profile;
+use crate::query::Query;
+use crate::quota::{
+    parse_retry_after, QuotaAlarm, QuotaBudget, QuotaState, QuotaStatus, DEFAULT_QUOTA_THRESHOLDS,
 };
+use crate::rate_limit::ConcurrencyLimiter;
+#[cfg(feature = "governor")]
+use crate::rate_limit::Limiter;
+use crate::redact::{redact_error, redact_url, LogRedaction, Redacted, API_KEY_PARAM, REDACTED};
+use crate::region::Region;
 #[cfg(feature = "blocking")]
-use crate::retry::retry_blocking;
-use crate::retry::{retry, RetryStrategy};
-use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, USER_AGENT};
+use crate::retry::retry_blocking_within;
+use crate::retry::{retry_within, RetryAttempt, RetryHook, RetryOn, RetryStrategy};
+use crate::secret::SecretString;
+use crate::sleep::Sleeper;
+use crate::summary::DEFAULT_SUMMARY_LENGTH;
+#[cfg(feature = "blocking")]
+use crate::transport::BlockingHttpTransport;
+use crate::transport::{HttpRequest, HttpResponse, HttpTransport};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "governor")]
+use governor::{DefaultDirectRateLimiter, DefaultKeyedRateLimiter, Jitter};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, RETRY_AFTER, USER_AGENT};
+use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::env;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use url::Url;
 
 #[derive(Debug, Deserialize, Serialize)]
 struct NewsApiErrorResponse {
-    status: String,
+    status: ResponseStatus,
     code: Option<String>,
     message: Option<String>,
 }
 
-#[derive(Clone, Debug)]
+/// The status field of a response body, to tell error bodies apart whatever their HTTP status.
+#[derive(Deserialize)]
+struct StatusField {
+    status: ResponseStatus,
+}
+
+/// Whether a body reports `"status": "error"`, which the API occasionally sends with a
+/// success status.
+fn reports_error(body: &[u8]) -> bool {
+    serde_json::from_slice::<StatusField>(body)
+        .is_ok_and(|body| body.status == ResponseStatus::Error)
+}
+
+/// A successful response body, the number of body bytes transferred for it and the status
+/// and selected headers of the response it was read from.
+struct ResponseBody {
+    text: String,
+    transfer_size: usize,
+    status: StatusCode,
+    headers: HeaderMap,
+}
+
+impl ResponseBody {
+    /// A body received from the API, `content_length` bytes on the wire when known.
+    fn received(
+        text: String,
+        content_length: Option<u64>,
+        status: StatusCode,
+        headers: HeaderMap,
+    ) -> Self {
+        let transfer_size = content_length.map_or(text.len(), |length| length as usize);
+        Self {
+            text,
+            transfer_size,
+            status,
+            headers,
+        }
+    }
+
+    /// A cached body the API confirmed with `304 Not Modified`, without sending it again.
+    fn revalidated(text: String, headers: HeaderMap) -> Self {
+        Self {
+            text,
+            transfer_size: 0,
+            status: StatusCode::NOT_MODIFIED,
+            headers,
+        }
+    }
+}
+
+/// The endpoint and URL a call is sent to and the canonical key of its request, which
+/// equal requests share in the response cache.
+#[derive(Clone, Copy)]
+struct RequestTarget<'a> {
+    endpoint: Endpoint,
+    url: &'a Url,
+    cache_key: &'a str,
+}
+
+/// Appends a call's correlation ID to a log line, if it has one.
+struct RequestIdTag<'a>(Option<&'a str>);
+
+impl fmt::Display for RequestIdTag<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(request_id) => write!(f, " [request {request_id}]"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Callback invoked after each request sent to the API, see
+/// [`NewsApiClientBuilder::on_request`].
+type RequestHook = Arc<dyn Fn(Endpoint, Duration, Option<&ApiClientError>) + Send + Sync>;
+
+/// Decodes error responses before the client does, see
+/// [`NewsApiClientBuilder::error_mapper`].
+type ErrorMapper =
+    Arc<dyn Fn(StatusCode, &HeaderMap, &str) -> Option<ApiClientError> + Send + Sync>;
+
+#[derive(Clone)]
 pub struct NewsApiClient<T> {
     client: T,
-    api_key: String,
+    keys: Arc<KeyRing>,
     base_url: Url,
-    retry_strategy: RetryStrategy,
-    max_retries: usize,
+    api_version: ApiVersion,
+    config: Arc<ConfigCell>,
+    max_retry_duration: Option<Duration>,
+    timeout: Option<Duration>,
+    endpoint_timeouts: HashMap<Endpoint, Duration>,
+    on_retry: Option<RetryHook>,
+    on_request: Option<RequestHook>,
+    error_mapper: Option<ErrorMapper>,
+    clock: Arc<dyn Clock>,
+    quota: Arc<QuotaState>,
+    concurrency_limiter: Option<Arc<ConcurrencyLimiter>>,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    cache_policies: HashMap<Endpoint, CachePolicy>,
+    cache: Arc<ResponseCache>,
+    sources_catalog: Arc<CatalogCache>,
+    offline: Arc<AtomicBool>,
+    default_sort_by: Option<ArticleSortBy>,
+    page_size_policy: PageSizePolicy,
+    strictness: Strictness,
+    summary_length: usize,
+    skip_malformed_articles: bool,
+    auth_mode: AuthMode,
+    user_agent: HeaderValue,
+    default_headers: HeaderMap,
+    request_id_header: Option<HeaderName>,
+    request_id: Option<String>,
+    request_log: Option<Arc<RequestLog>>,
+    log_redaction: Arc<LogRedaction>,
+}
+
+impl<T: fmt::Debug> fmt::Debug for NewsApiClient<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("NewsApiClient");
+        debug
+            .field("client", &self.client)
+            .field("api_key", &Redacted)
+            .field("base_url", &self.base_url)
+            .field("api_version", &self.api_version)
+            .field("config", &self.config.load())
+            .field("max_retry_duration", &self.max_retry_duration)
+            .field("timeout", &self.timeout)
+            .field("endpoint_timeouts", &self.endpoint_timeouts)
+            .field("on_retry", &self.on_retry.is_some())
+            .field("on_request", &self.on_request.is_some())
+            .field("error_mapper", &self.error_mapper.is_some())
+            .field("clock", &self.clock)
+            .field("quota", &self.quota)
+            .field("concurrency_limiter", &self.concurrency_limiter)
+            .field("circuit_breaker", &self.circuit_breaker)
+            .field("cache_policies", &self.cache_policies)
+            .field("offline_mode", &self.get_offline_mode())
+            .field("default_sort_by", &self.default_sort_by)
+            .field("page_size_policy", &self.page_size_policy)
+            .field("strictness", &self.strictness)
+            .field("summary_length", &self.summary_length)
+            .field("skip_malformed_articles", &self.skip_malformed_articles)
+            .field("auth_mode", &self.auth_mode)
+            .field("user_agent", &self.user_agent)
+            .field("default_headers", &self.default_headers)
+            .field("request_id_header", &self.request_id_header)
+            .field("request_id", &self.request_id)
+            .field("log_summaries", &self.request_log.is_some())
+            .field("log_redaction", &self.log_redaction)
+            .finish_non_exhaustive()
+    }
 }
 
 pub struct NewsApiClientBuilder {
-    api_key: Option<String>,
-    base_url: Option<Url>,
-    retry_strategy: RetryStrategy,
-    max_retries: usize,
+    api_key: Option<SecretString>,
+    api_key_envs: Vec<String>,
+    #[cfg(feature = "keyring")]
+    keyring_account: Option<String>,
+    config_file_api_key: Option<SecretString>,
+    backup_api_keys: Vec<SecretString>,
+    key_cool_down: Duration,
+    base_url: Result<Url, url::ParseError>,
+    api_version: ApiVersion,
+    config: RuntimeConfig,
+    max_retry_duration: Option<Duration>,
+    timeout: Option<Duration>,
+    endpoint_timeouts: HashMap<Endpoint, Duration>,
+    on_retry: Option<RetryHook>,
+    on_request: Option<RequestHook>,
+    error_mapper: Option<ErrorMapper>,
+    clock: Arc<dyn Clock>,
+    #[cfg(feature = "governor")]
+    governor: Option<Limiter>,
+    max_concurrent_requests: Option<usize>,
+    circuit_breaker: Option<(u32, Duration)>,
+    quota_budget: Option<QuotaBudget>,
+    quota_alarm: Option<QuotaAlarm>,
+    cache_policies: HashMap<Endpoint, CachePolicy>,
+    sources_catalog_ttl: Duration,
+    offline_mode: OfflineMode,
+    default_sort_by: Option<ArticleSortBy>,
+    page_size_policy: PageSizePolicy,
+    strictness: Strictness,
+    summary_length: usize,
+    skip_malformed_articles: bool,
+    auth_mode: AuthMode,
+    user_agent: Option<String>,
+    default_headers: HeaderMap,
+    request_id_header: Option<HeaderName>,
+    http_client: Option<reqwest::Client>,
+    http_options: HttpClientOptions,
+    log_summary_interval: Option<Duration>,
+    log_redaction: LogRedaction,
 }
 
 impl Default for NewsApiClientBuilder {
     fn default() -> Self {
         Self {
             api_key: None,
-            base_url: Some(Url::parse(NEWS_API_URI).unwrap()),
-            retry_strategy: RetryStrategy::default(),
-            max_retries: 0,
+            api_key_envs: vec![NEWS_API_KEY_ENV.to_string()],
+            #[cfg(feature = "keyring")]
+            keyring_account: None,
+            config_file_api_key: None,
+            backup_api_keys: Vec::new(),
+            key_cool_down: DEFAULT_KEY_COOL_DOWN,
+            base_url: Ok(Url::parse(NEWS_API_URI).unwrap()),
+            api_version: ApiVersion::default(),
+            config: RuntimeConfig::default(),
+            max_retry_duration: None,
+            timeout: None,
+            endpoint_timeouts: HashMap::new(),
+            on_retry: None,
+            on_request: None,
+            error_mapper: None,
+            clock: Arc::new(SystemClock),
+            #[cfg(feature = "governor")]
+            governor: None,
+            max_concurrent_requests: None,
+            circuit_breaker: None,
+            quota_budget: None,
+            quota_alarm: None,
+            cache_policies: HashMap::new(),
+            sources_catalog_ttl: DEFAULT_SOURCES_CATALOG_TTL,
+            offline_mode: OfflineMode::default(),
+            default_sort_by: None,
+            page_size_policy: PageSizePolicy::default(),
+            strictness: Strictness::default(),
+            summary_length: DEFAULT_SUMMARY_LENGTH,
+            skip_malformed_articles: false,
+            auth_mode: AuthMode::default(),
+            user_agent: None,
+            default_headers: HeaderMap::new(),
+            request_id_header: None,
+            http_client: None,
+            http_options: HttpClientOptions::default(),
+            log_summary_interval: None,
+            log_redaction: LogRedaction::default(),
         }
     }
 }
@@ -54,735 +321,3136 @@ impl NewsApiClientBuilder {
         Self::default()
     }
 
-    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+    pub fn api_key(mut self, api_key: impl Into<SecretString>) -> Self {
         self.api_key = Some(api_key.into());
         self
     }
 
-    pub fn base_url(mut self, url: impl AsRef<str>) -> Result<Self, url::ParseError> {
-        self.base_url = Some(Url::parse(url.as_ref())?);
-        Ok(self)
+    /// Sets the environment variable the key is read from when none is given with
+    /// [`api_key`](Self::api_key), `NEWS_API_KEY` by default.
+    pub fn api_key_env(self, name: impl Into<String>) -> Self {
+        self.api_key_envs([name])
+    }
+
+    /// Sets the environment variables the key is read from when none is given with
+    /// [`api_key`](Self::api_key), in order of precedence. Variables set to an empty value
+    /// are skipped.
+    pub fn api_key_envs<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.api_key_envs = names.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Reads the key stored for `account` in the OS credential store when none is given with
+    /// [`api_key`](Self::api_key) or in the environment, see
+    /// [`credential_store`](crate::credential_store).
+    #[cfg(feature = "keyring")]
+    pub fn api_key_from_keyring(mut self, account: impl Into<String>) -> Self {
+        self.keyring_account = Some(account.into());
+        self
+    }
+
+    /// Adds a key the client fails over to, in the order added, when the API reports the
+    /// keys before it as exhausted or rate limited. The failed key is skipped until the
+    /// response's `Retry-After`, or for the [`key_cool_down`](Self::key_cool_down).
+    pub fn backup_api_key(mut self, api_key: impl Into<SecretString>) -> Self {
+        self.backup_api_keys.push(api_key.into());
+        self
+    }
+
+    /// How long a key is skipped after an exhausted or rate-limited response without
+    /// `Retry-After`, an hour by default. Only applies with a
+    /// [`backup_api_key`](Self::backup_api_key).
+    pub fn key_cool_down(mut self, cool_down: Duration) -> Self {
+        self.key_cool_down = cool_down;
+        self
+    }
+
+    /// Sends requests to `url` rather than `https://newsapi.org`, e.g. a proxy or a mock
+    /// server. An invalid URL makes [`build`](Self::build) fail with
+    /// [`ClientBuildError::InvalidBaseUrl`].
+    pub fn base_url(mut self, url: impl AsRef<str>) -> Self {
+        self.base_url = Url::parse(url.as_ref());
+        self
     }
 
     pub fn retry(mut self, strategy: RetryStrategy, max_retries: usize) -> Self {
-        self.retry_strategy = strategy;
-        self.max_retries = max_retries;
+        self.config = self.config.retry(strategy, max_retries);
         self
     }
 
-    pub fn from_env() -> Self {
-        match env::var(NEWS_API_KEY_ENV) {
-            Ok(api_key) => Self::new().api_key(api_key),
-            Err(_) => panic!("{NEWS_API_KEY_ENV} is not set"),
-        }
+    /// Retries with delays from a `backoff` crate policy until its `max_elapsed_time` has
+    /// passed, replacing any strategy set with [`retry`](Self::retry).
+    #[cfg(feature = "backoff")]
+    pub fn backoff(mut self, policy: backoff::ExponentialBackoff) -> Self {
+        self.config = self.config.backoff(policy);
+        self
     }
 
-    pub fn build(self) -> Result<NewsApiClient<reqwest::Client>, String> {
-        let api_key = match self.api_key {
-            Some(key) => key,
-            None => match env::var(NEWS_API_KEY_ENV) {
-                Ok(key) => key,
-                Err(_) => {
-                    return Err(format!(
-                        "API key must be provided either explicitly or via {NEWS_API_KEY_ENV} environment variable"
-                    ))
-                }
-            },
-        };
+    /// Restricts which errors are retried, e.g. to network errors only. All errors are
+    /// retried by default.
+    pub fn retry_on(mut self, retry_on: RetryOn) -> Self {
+        self.config = self.config.retry_on(retry_on);
+        self
+    }
 
-        let base_url = self
-            .base_url
-            .unwrap_or_else(|| Url::parse(NEWS_API_URI).unwrap());
+    /// Bounds the total time of a call including all retries, independent of `max_retries`.
+    /// Requests time out at the deadline and no retry is started that would sleep past it.
+    pub fn max_retry_duration(mut self, duration: Duration) -> Self {
+        self.max_retry_duration = Some(duration);
+        self
+    }
 
-        Ok(NewsApiClient {
-            client: reqwest::Client::new(),
-            api_key,
-            base_url,
-            retry_strategy: self.retry_strategy,
-            max_retries: self.max_retries,
-        })
+    /// Timeout of each request attempt, unless [`endpoint_timeout`](Self::endpoint_timeout)
+    /// sets one for its endpoint.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
     }
-}
 
-#[cfg(feature = "blocking")]
-pub struct BlockingNewsApiClientBuilder {
-    api_key: Option<String>,
-    base_url: Option<Url>,
-    retry_strategy: RetryStrategy,
-    max_retries: usize,
-}
+    /// Timeout of each request attempt to `endpoint`, e.g. a longer one for everything searches
+    /// with large pages than for the small sources list. Attempts still time out at the
+    /// [`max_retry_duration`](Self::max_retry_duration) deadline.
+    pub fn endpoint_timeout(mut self, endpoint: Endpoint, timeout: Duration) -> Self {
+        self.endpoint_timeouts.insert(endpoint, timeout);
+        self
+    }
 
-#[cfg(feature = "blocking")]
-impl Default for BlockingNewsApiClientBuilder {
-    fn default() -> Self {
-        Self {
-            api_key: None,
-            base_url: Some(Url::parse(NEWS_API_URI).unwrap()),
-            retry_strategy: RetryStrategy::default(),
-            max_retries: 0,
-        }
+    /// Registers a callback invoked before each retry with the retry number, counted from one,
+    /// the error that triggered it and the delay before the retry is sent.
+    pub fn on_retry<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(usize, &ApiClientError, Duration) + Send + Sync + 'static,
+    {
+        self.on_retry = Some(Arc::new(callback));
+        self
     }
-}
 
-#[cfg(feature = "blocking")]
-impl BlockingNewsApiClientBuilder {
-    pub fn new() -> Self {
-        Self::default()
+    /// Registers a callback invoked after each request sent to the API, retries and failovers
+    /// included, with its endpoint, its latency and the error when it failed. Responses
+    /// served from the cache without a request are not reported. Suited to feeding metrics.
+    pub fn on_request<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(Endpoint, Duration, Option<&ApiClientError>) + Send + Sync + 'static,
+    {
+        self.on_request = Some(Arc::new(callback));
+        self
     }
 
-    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
-        self.api_key = Some(api_key.into());
+    /// Registers a function decoding non-success responses from their status, headers and
+    /// body, e.g. for a gateway in front of NewsAPI that wraps its errors. When it returns an
+    /// error, that error is used in place of the client's own; `None` falls back to it.
+    /// Mapped errors are retried, and pause the client or fail over to backup keys, like the
+    /// client's own errors.
+    pub fn error_mapper<F>(mut self, mapper: F) -> Self
+    where
+        F: Fn(StatusCode, &HeaderMap, &str) -> Option<ApiClientError> + Send + Sync + 'static,
+    {
+        self.error_mapper = Some(Arc::new(mapper));
         self
     }
 
-    pub fn base_url(mut self, url: impl AsRef<str>) -> Result<Self, url::ParseError> {
-        self.base_url = Some(Url::parse(url.as_ref())?);
-        Ok(self)
+    /// Replaces how the client waits between retries, e.g. with an
+    /// [`InstantSleeper`](crate::sleep::InstantSleeper) to test a retry configuration without
+    /// waiting.
+    pub fn sleeper(mut self, sleeper: impl Sleeper + 'static) -> Self {
+        self.clock = Arc::new(SleeperClock(sleeper));
+        self
     }
 
-    pub fn retry(mut self, strategy: RetryStrategy, max_retries: usize) -> Self {
-        self.retry_strategy = strategy;
-        self.max_retries = max_retries;
+    /// Replaces the clock used for retry deadlines, rate limiting, cache TTLs, quota pauses,
+    /// key cool-downs, the circuit breaker and response timestamps, e.g. with a
+    /// [`ManualClock`](crate::clock::ManualClock) to test time-dependent behavior without
+    /// waiting. Also replaces any [`sleeper`](Self::sleeper).
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
         self
     }
 
-    pub fn from_env() -> Self {
-        match env::var(NEWS_API_KEY_ENV) {
-            Ok(api_key) => Self::new().api_key(api_key),
-            Err(_) => panic!("{NEWS_API_KEY_ENV} is not set"),
-        }
+    /// Limits the client to `requests_per_window` requests per `window` using a token bucket
+    /// shared by all endpoints and all clones of the client. Retry attempts count as requests.
+    pub fn rate_limit(mut self, requests_per_window: u32, window: Duration) -> Self {
+        self.config = self.config.rate_limit(requests_per_window, window);
+        self
     }
 
-    pub fn build(self) -> Result<NewsApiClient<reqwest::blocking::Client>, String> {
-        let api_key = match self.api_key {
-            Some(key) => key,
-            None => match env::var(NEWS_API_KEY_ENV) {
-                Ok(key) => key,
-                Err(_) => {
-                    return Err(format!(
-                        "API key must be provided either explicitly or via {NEWS_API_KEY_ENV} environment variable",
-                    ))
-                }
-            },
-        };
+    /// Uses a shared `governor` rate limiter for all requests, e.g. to make several clients
+    /// draw from one quota. Waits are spread by `jitter` when given. Takes precedence over
+    /// [`rate_limit`](Self::rate_limit).
+    #[cfg(feature = "governor")]
+    pub fn governor_rate_limiter(
+        mut self,
+        limiter: Arc<DefaultDirectRateLimiter>,
+        jitter: Option<Jitter>,
+    ) -> Self {
+        self.governor = Some(Limiter::Governor(limiter, jitter));
+        self
+    }
 
-        let base_url = self
-            .base_url
-            .unwrap_or_else(|| Url::parse(NEWS_API_URI).unwrap());
+    /// Uses a shared keyed `governor` rate limiter, keyed by a fingerprint of the API key, so
+    /// that clients using the same key share its quota. Takes precedence over [`rate_limit`](Self::rate_limit).
+    #[cfg(feature = "governor")]
+    pub fn governor_keyed_rate_limiter(
+        mut self,
+        limiter: Arc<DefaultKeyedRateLimiter<String>>,
+        jitter: Option<Jitter>,
+    ) -> Self {
+        self.governor = Some(Limiter::KeyedGovernor(limiter, jitter));
+        self
+    }
 
-        Ok(NewsApiClient {
-            client: reqwest::blocking::Client::new(),
-            api_key,
-            base_url,
-            retry_strategy: self.retry_strategy,
-            max_retries: self.max_retries,
-        })
+    /// Counts requests against a local budget, e.g. `QuotaBudget::daily(100)` for the
+    /// developer plan, and fails locally once it is spent.
+    pub fn quota_budget(mut self, budget: QuotaBudget) -> Self {
+        self.quota_budget = Some(budget);
+        self
     }
-}
 
-#[cfg(feature = "blocking")]
-mod blocking {
-    use super::*;
-    use reqwest::blocking::Client as BlockingClient;
+    /// Limits the number of requests in flight at once across all endpoints and clones of
+    /// the client. Further requests wait until one finishes.
+    pub fn max_concurrent_requests(mut self, n: usize) -> Self {
+        self.max_concurrent_requests = Some(n);
+        self
+    }
 
-    impl NewsApiClient<BlockingClient> {
-        pub fn new_blocking(api_key: &str) -> Self {
-            NewsApiClient {
-                client: BlockingClient::new(),
-                api_key: api_key.to_string(),
-                base_url: Url::parse(NEWS_API_URI).unwrap(),
-                retry_strategy: RetryStrategy::default(),
-                max_retries: 0,
-            }
-        }
+    /// Opens the circuit after `failure_threshold` consecutive network, server or rate-limit
+    /// errors. While open, requests fail fast with [`ApiClientError::CircuitOpen`]; after
+    /// `cool_down` a single trial request decides whether to close it again.
+    pub fn circuit_breaker(mut self, failure_threshold: u32, cool_down: Duration) -> Self {
+        self.circuit_breaker = Some((failure_threshold, cool_down));
+        self
+    }
 
-        pub fn builder_blocking() -> super::BlockingNewsApiClientBuilder {
-            super::BlockingNewsApiClientBuilder::new()
-        }
+    /// Calls `callback` with `(used, limit)` when usage of the [`QuotaBudget`] crosses 80% or
+    /// 95% of its limit, e.g. to alert operators before the key is exhausted.
+    pub fn on_quota_threshold<F>(self, callback: F) -> Self
+    where
+        F: Fn(u32, u32) + Send + Sync + 'static,
+    {
+        self.on_quota_thresholds(&DEFAULT_QUOTA_THRESHOLDS, callback)
+    }
 
-        fn parse_error_response(&self, response_text: String, status_code: u16) -> ApiClientError {
-            NewsApiClient::<BlockingClient>::parse_error_response_internal(
-                response_text,
-                status_code,
-            )
-        }
+    /// Like [`on_quota_threshold`](Self::on_quota_threshold) with custom usage ratios,
+    /// e.g. `&[0.5, 0.9]`.
+    pub fn on_quota_thresholds<F>(mut self, thresholds: &[f64], callback: F) -> Self
+    where
+        F: Fn(u32, u32) + Send + Sync + 'static,
+    {
+        self.quota_alarm = Some(QuotaAlarm::new(thresholds.to_vec(), Arc::new(callback)));
+        self
+    }
 
-        pub fn get_everything(
-            self,
-            request: &GetEverythingRequest,
-        ) -> Result<GetEverythingResponse, ApiClientError> {
-            retry_blocking(self.retry_strategy, self.max_retries, || {
-                log::debug!("Request: {request:?}");
+    /// Sets the caching policy for responses of `endpoint`.
+    pub fn cache_policy(mut self, endpoint: Endpoint, policy: CachePolicy) -> Self {
+        self.cache_policies.insert(endpoint, policy);
+        self
+    }
 
-                let mut url = self.base_url.clone();
-                NewsApiClient::<BlockingClient>::get_endpoint_with_query_params_for_everything(
-                    &mut url, request,
-                );
-                log::debug!("Request URL: {}", url.as_str());
-
-                let headers = self.get_request_headers()?;
-                let response = self.client.get(url.as_str()).headers(headers).send()?;
-                let status = response.status();
-                log::debug!("Response status: {status:?}");
-
-                if status.is_success() {
-                    let response_text = response.text()?;
-                    match serde_json::from_str::<GetEverythingResponse>(&response_text) {
-                        Ok(everything_response) => Ok(everything_response),
-                        Err(e) => Err(ApiClientError::InvalidRequest(format!("{e}"))),
-                    }
-                } else {
-                    let response_text = response.text()?;
-                    Err(self.parse_error_response(response_text, status.as_u16()))
-                }
-            })
-        }
+    /// How long [`NewsApiClient::sources_catalog`] reuses a fetched catalog, a day by default.
+    pub fn sources_catalog_ttl(mut self, ttl: Duration) -> Self {
+        self.sources_catalog_ttl = ttl;
+        self
+    }
 
-        pub fn get_top_headlines(
-            self,
-            request: &GetTopHeadlinesRequest,
-        ) -> Result<TopHeadlinesResponse, ApiClientError> {
-            retry_blocking(self.retry_strategy, self.max_retries, || {
-                log::debug!("Request: {request:?}");
-                NewsApiClient::<BlockingClient>::top_headlines_validate_request(request)?;
+    /// Starts the client in the given [`OfflineMode`]. Offline clients need no API key.
+    pub fn offline_mode(mut self, mode: OfflineMode) -> Self {
+        self.offline_mode = mode;
+        self
+    }
 
-                let mut url = self.base_url.clone();
-                NewsApiClient::<BlockingClient>::get_endpoint_with_query_params_for_top_headlines(
-                    &mut url, request,
-                );
-                log::debug!("Request URL: {}", url.as_str());
-
-                let headers = self.get_request_headers()?;
-                let response = self.client.get(url.as_str()).headers(headers).send()?;
-                let status = response.status();
-                log::debug!("Response status: {status:?}");
-
-                if status.is_success() {
-                    let response_text = response.text()?;
-                    match serde_json::from_str::<TopHeadlinesResponse>(&response_text) {
-                        Ok(headline_response) => Ok(headline_response),
-                        Err(e) => Err(ApiClientError::InvalidRequest(format!(
-                            "Failed to parse response: {e}"
-                        ))),
-                    }
-                } else {
-                    let response_text = response.text()?;
-                    Err(self.parse_error_response(response_text, status.as_u16()))
-                }
-            })
-        }
+    /// Sort order of everything searches that don't set one.
+    pub fn default_sort_by(mut self, sort_by: ArticleSortBy) -> Self {
+        self.default_sort_by = Some(sort_by);
+        self
+    }
 
-        pub fn get_sources(
-            self,
-            request: &GetSourcesRequest,
-        ) -> Result<GetSourcesResponse, ApiClientError> {
-            retry_blocking(self.retry_strategy, self.max_retries, || {
-                log::debug!("Request: {request:?}");
+    /// Page size of everything and top headlines requests that don't set one. Subject to the
+    /// [`PageSizePolicy`] like any other page size.
+    pub fn default_page_size(mut self, page_size: i32) -> Self {
+        self.config = self.config.default_page_size(page_size);
+        self
+    }
 
-                let mut url = self.base_url.clone();
-                NewsApiClient::<BlockingClient>::get_endpoint_with_query_params_for_sources(
-                    &mut url, request,
-                );
-                log::debug!("Request URL: {url}");
-
-                let headers = self.get_request_headers()?;
-                let response = self.client.get(url.as_str()).headers(headers).send()?;
-                let status = response.status();
-                log::debug!("Response status: {status:?}");
-
-                if status.is_success() {
-                    let response_text = response.text()?;
-                    match serde_json::from_str::<GetSourcesResponse>(&response_text) {
-                        Ok(sources_response) => Ok(sources_response),
-                        Err(e) => Err(ApiClientError::InvalidRequest(format!("{e}"))),
-                    }
-                } else {
-                    let response_text = response.text()?;
-                    Err(self.parse_error_response(response_text, status.as_u16()))
-                }
-            })
-        }
+    /// Sets how page sizes outside the API's range of 1 to 100 are handled, clamping them
+    /// by default.
+    pub fn page_size_policy(mut self, policy: PageSizePolicy) -> Self {
+        self.page_size_policy = policy;
+        self
+    }
 
-        pub fn with_retry(mut self, strategy: RetryStrategy, max_retries: usize) -> Self {
-            self.retry_strategy = strategy;
-            self.max_retries = max_retries;
-            self
-        }
+    /// Sets whether the client recovers from invalid page sizes, unavailable endpoints and
+    /// an unavailable sources catalog, or fails instead. Lenient by default, see [`Strictness`].
+    pub fn strictness(mut self, strictness: Strictness) -> Self {
+        self.strictness = strictness;
+        self
     }
-}
 
-impl NewsApiClient<reqwest::Client> {
-    pub fn new(api_key: &str) -> Self {
-        NewsApiClient {
-            client: reqwest::Client::new(),
-            api_key: api_key.to_string(),
-            base_url: Url::parse(NEWS_API_URI).unwrap(),
-            retry_strategy: RetryStrategy::default(),
-            max_retries: 0,
-        }
+    /// Sets the maximum length in characters of the summaries synthesized for articles,
+    /// [`DEFAULT_SUMMARY_LENGTH`] by default. See [`Article::get_summary`].
+    pub fn summary_length(mut self, max_chars: usize) -> Self {
+        self.summary_length = max_chars;
+        self
     }
 
-    pub fn builder() -> NewsApiClientBuilder {
-        NewsApiClientBuilder::new()
+    /// Parses the articles of everything and top headline responses one by one, leaving out
+    /// those that cannot be parsed instead of failing the whole response. Skipped articles are
+    /// logged and listed in the response's `get_warnings`. Off by default.
+    pub fn skip_malformed_articles(mut self, skip: bool) -> Self {
+        self.skip_malformed_articles = skip;
+        self
     }
 
-    pub fn from_env() -> Self {
-        match env::var(NEWS_API_KEY_ENV) {
-            Ok(api_key) => NewsApiClient::new(&api_key),
-            Err(_) => panic!("{NEWS_API_KEY_ENV} is not set"),
-        }
+    /// Sets how the API key is sent, in an `Authorization: Bearer` header by default. See
+    /// [`AuthMode`].
+    pub fn auth_mode(mut self, mode: AuthMode) -> Self {
+        self.auth_mode = mode;
+        self
     }
 
-    fn parse_error_response(&self, response_text: String, status_code: u16) -> ApiClientError {
-        NewsApiClient::<reqwest::Client>::parse_error_response_internal(response_text, status_code)
+    /// Identifies the application in the `User-Agent` header, e.g. `my-app/1.2`. The crate's
+    /// own `newsapi-rs/<version>` is appended, and sent alone by default.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
     }
 
-    pub async fn get_everything(
-        &self,
-        request: &GetEverythingRequest,
-    ) -> Result<GetEverythingResponse, ApiClientError> {
-        retry(self.retry_strategy, self.max_retries, || async {
-            log::debug!("Request: {request:?}");
-
-            let mut url = self.base_url.clone();
-            Self::get_endpoint_with_query_params_for_everything(&mut url, request);
-            log::debug!("Request URL: {url}");
-
-            let headers = self.get_request_headers()?;
-            let response = self
-                .client
-                .get(url.as_str())
-                .headers(headers)
-                .send()
-                .await?;
-            let status = response.status();
-            log::debug!("Response status: {status:?}");
-
-            if status.is_success() {
-                let response_text = response.text().await?;
-                match serde_json::from_str::<GetEverythingResponse>(&response_text) {
-                    Ok(everything_response) => Ok(everything_response),
-                    Err(e) => Err(ApiClientError::InvalidRequest(format!("{e}"))),
-                }
-            } else {
-                let response_text = response.text().await?;
-                Err(self.parse_error_response(response_text, status.as_u16()))
-            }
-        })
-        .await
+    /// Adds a header sent with every request, e.g. a tracing header or a token for a corporate
+    /// gateway. Setting the same header again replaces it. Authentication and `User-Agent`
+    /// headers set by the client take precedence. Values marked
+    /// [sensitive](HeaderValue::set_sensitive) are kept out of debug output.
+    pub fn default_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.default_headers.insert(name, value);
+        self
     }
 
-    pub async fn get_top_headlines(
-        &self,
-        request: &GetTopHeadlinesRequest,
-    ) -> Result<TopHeadlinesResponse, ApiClientError> {
-        retry(self.retry_strategy, self.max_retries, || async {
-            log::debug!("Request: {request:?}");
-            Self::top_headlines_validate_request(request)?;
-
-            let mut url = self.base_url.clone();
-            Self::get_endpoint_with_query_params_for_top_headlines(&mut url, request);
-            log::debug!("Request URL: {url}");
-
-            let headers = self.get_request_headers()?;
-            let response = self
-                .client
-                .get(url.as_str())
-                .headers(headers)
-                .send()
-                .await?;
-            let status = response.status();
-            log::debug!("Response status: {status:?}");
-
-            if status.is_success() {
-                let response_text = response.text().await?;
-                match serde_json::from_str::<TopHeadlinesResponse>(&response_text) {
-                    Ok(headline_response) => Ok(headline_response),
-                    Err(e) => Err(ApiClientError::InvalidRequest(format!(
-                        "Failed to parse response: {e}"
-                    ))),
-                }
-            } else {
-                let response_text = response.text().await?;
-                Err(self.parse_error_response(response_text, status.as_u16()))
-            }
-        })
-        .await
+    /// Adds each of `headers` like [`default_header`](Self::default_header).
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers.extend(headers);
+        self
     }
 
-    pub async fn get_sources(
-        &self,
-        request: &GetSourcesRequest,
-    ) -> Result<GetSourcesResponse, ApiClientError> {
-        retry(self.retry_strategy, self.max_retries, || async {
-            log::debug!("Request: {request:?}");
-
-            let mut url = self.base_url.clone();
-            Self::get_endpoint_with_query_params_for_sources(&mut url, request);
-            log::debug!("Request URL: {url}");
-
-            let headers = self.get_request_headers()?;
-            let response = self
-                .client
-                .get(url.as_str())
-                .headers(headers)
-                .send()
-                .await?;
-            let status = response.status();
-            log::debug!("Response status: {status:?}");
-
-            if status.is_success() {
-                let response_text = response.text().await?;
-                match serde_json::from_str::<GetSourcesResponse>(&response_text) {
-                    Ok(sources_response) => Ok(sources_response),
-                    Err(e) => Err(ApiClientError::InvalidRequest(format!("{e}"))),
-                }
-            } else {
-                let response_text = response.text().await?;
-                Err(self.parse_error_response(response_text, status.as_u16()))
-            }
-        })
-        .await
+    /// Sends a correlation ID generated for each call in the `name` header, e.g.
+    /// `x-request-id`. Retries of a call reuse its ID, which is also included in the call's
+    /// debug logs, in [`ResponseMeta::get_request_id`] and in API error responses, so that a
+    /// failed call can be tied back to the application's logs. See
+    /// [`NewsApiClient::with_request_id`] to supply the ID instead.
+    pub fn request_id_header(mut self, name: HeaderName) -> Self {
+        self.request_id_header = Some(name);
+        self
     }
 
-    pub fn with_retry(mut self, strategy: RetryStrategy, max_retries: usize) -> Self {
-        self.retry_strategy = strategy;
-        self.max_retries = max_retries;
+    /// Replaces the per-request debug logs with one info-level summary per endpoint and
+    /// `interval`: the number of requests, their p50 and p95 latency and the number that failed. For
+    /// high-volume pollers whose per-request logs would flood the log pipeline.
+    pub fn log_summary_interval(mut self, interval: Duration) -> Self {
+        self.log_summary_interval = Some(interval);
         self
     }
-}
 
-#[cfg(feature = "blocking")]
-impl NewsApiClient<reqwest::blocking::Client> {
-    pub fn from_env_blocking() -> Self {
-        match env::var(NEWS_API_KEY_ENV) {
-            Ok(api_key) => Self::new_blocking(&api_key),
-            Err(_) => panic!("{NEWS_API_KEY_ENV} is not set"),
-        }
+    /// Keeps more out of the client's logs than the API key, which is always redacted, e.g.
+    /// search terms or response content. See [`LogRedaction`].
+    pub fn log_redaction(mut self, redaction: LogRedaction) -> Self {
+        self.log_redaction = redaction;
+        self
     }
-}
 
-impl<T> NewsApiClient<T> {
-    fn parse_error_response_internal(response_text: String, status_code: u16) -> ApiClientError {
-        match serde_json::from_str::<NewsApiErrorResponse>(&response_text) {
-            Ok(error_response) => {
-                let error_code = match error_response.code.as_deref() {
-                    Some("apiKeyDisabled") => ApiClientErrorCode::ApiKeyDisabled,
-                    Some("apiKeyExhausted") => ApiClientErrorCode::ApiKeyExhausted,
-                    Some("apiKeyInvalid") => ApiClientErrorCode::ApiKeyInvalid,
-                    Some("apiKeyMissing") => ApiClientErrorCode::ApiKeyMissing,
-                    Some("parameterInvalid") => ApiClientErrorCode::ParameterInvalid,
-                    Some("parametersMissing") => ApiClientErrorCode::ParametersMissing,
-                    Some("rateLimited") => ApiClientErrorCode::RateLimited,
-                    Some("sourcesTooMany") => ApiClientErrorCode::SourcesTooMany,
-                    Some("sourceDoesNotExist") => ApiClientErrorCode::SourceDoesNotExist,
-                    _ => {
-                        // Check for rate limiting based on status code
-                        if status_code == 429 {
-                            ApiClientErrorCode::RateLimited
-                        } else {
-                            ApiClientErrorCode::UnexpectedError
-                        }
-                    }
-                };
+    /// Sets the version prefix endpoint paths are resolved under, `/v2` by default.
+    pub fn api_version(mut self, version: ApiVersion) -> Self {
+        self.api_version = version;
+        self
+    }
 
-                ApiClientError::InvalidResponse(ApiClientErrorResponse {
-                    status: error_response.status,
-                    code: error_code,
-                    message: error_response
-                        .message
-                        .unwrap_or_else(|| "Unknown error".to_string()),
+    /// Applies the settings of a deployment [`Profile`]. Later settings override it.
+    pub fn profile(self, profile: Profile) -> Self {
+        match profile {
+            Profile::Interactive => Endpoint::ALL
+                .into_iter()
+                .fold(self, |builder, endpoint| {
+                    builder.cache_policy(endpoint, CachePolicy::StaleIfError)
                 })
-            }
-            Err(_) => {
-                let error_code = if status_code == 429 {
-                    ApiClientErrorCode::RateLimited
-                } else {
-                    ApiClientErrorCode::UnexpectedError
-                };
-
-                ApiClientError::InvalidResponse(ApiClientErrorResponse {
-                    status: "error".to_string(),
-                    code: error_code,
-                    message: if response_text.contains("too many requests")
-                        || response_text.contains("rate limit")
-                    {
-                        "You have made too many requests. Rate limit exceeded.".to_string()
-                    } else {
-                        "Failed to parse error response".to_string()
+                .retry(
+                    RetryStrategy::Exponential {
+                        base: Duration::from_millis(250),
+                        max: Duration::from_secs(2),
+                    },
+                    2,
+                )
+                .retry_on(RetryOn::NetworkAndRateLimit)
+                .timeout(Profile::INTERACTIVE_TIMEOUT)
+                .max_retry_duration(Profile::INTERACTIVE_DEADLINE)
+                .circuit_breaker(3, Duration::from_secs(30)),
+            Profile::BatchBackfill => self
+                .timeout(Profile::BACKFILL_TIMEOUT)
+                .retry(
+                    RetryStrategy::Exponential {
+                        base: Duration::from_secs(1),
+                        max: Duration::from_secs(60),
                     },
+                    8,
+                )
+                .retry_on(RetryOn::NetworkAndRateLimit)
+                .rate_limit(
+                    Profile::BACKFILL_REQUESTS_PER_MINUTE,
+                    Duration::from_secs(60),
+                )
+                .max_concurrent_requests(2),
+            Profile::LowQuota => Endpoint::ALL
+                .into_iter()
+                .fold(self, |builder, endpoint| {
+                    builder.cache_policy(endpoint, CachePolicy::Revalidate)
                 })
-            }
+                .retry(RetryStrategy::Constant(Duration::from_secs(1)), 1)
+                .retry_on(RetryOn::NetworkOnly)
+                .timeout(Profile::LOW_QUOTA_TIMEOUT)
+                .quota_budget(QuotaBudget::daily(Profile::LOW_QUOTA_DAILY_LIMIT)),
         }
     }
 
-    fn get_request_headers(&self) -> Result<HeaderMap, ApiClientError> {
-        let mut headers = HeaderMap::new();
-        headers.insert(
-            AUTHORIZATION,
-            HeaderValue::from_str(&format!("Bearer {}", self.api_key))?,
-        );
-        headers.insert(
-            USER_AGENT,
-            HeaderValue::from_static(NEWS_API_CLIENT_USER_AGENT),
-        );
-        Ok(headers)
-    }
-
-    fn top_headlines_validate_request(
-        request: &GetTopHeadlinesRequest,
-    ) -> Result<(), ApiClientError> {
-        log::debug!("Validating request");
-        if request.get_sources().is_some()
-            && (request.get_country().is_some() || request.get_category().is_some())
-        {
-            return Err(ApiClientError::InvalidRequest(
-                "Cannot specify sources with country or category".to_string(),
-            ));
+    pub fn from_env() -> Self {
+        match env::var(NEWS_API_KEY_ENV) {
+            Ok(api_key) => Self::new().api_key(api_key),
+            Err(_) => panic!("{NEWS_API_KEY_ENV} is not set"),
         }
-        Ok(())
     }
 
-    fn get_endpoint_with_query_params_for_top_headlines(
-        url: &mut Url,
-        request: &GetTopHeadlinesRequest,
-    ) {
-        url.set_path(TOP_HEADLINES_ENDPOINT);
-        url.query_pairs_mut().clear();
+    /// A builder with the settings of a TOML config file. All settings are optional:
+    ///
+    /// ```toml
+    /// api_key = "your-api-key"
+    /// api_key_envs = ["MY_APP_NEWS_API_KEY"]
+    /// base_url = "https://newsapi.org"
+    /// timeout_ms = 5000
+    /// max_retry_duration_ms = 30000
+    /// default_sort_by = "publishedAt"
+    /// default_page_size = 50
+    ///
+    /// [retry]
+    /// # none, constant, linear, exponential, exponential_jitter or decorrelated_jitter
+    /// strategy = "exponential"
+    /// delay_ms = 100
+    /// max_delay_ms = 10000
+    /// max_retries = 3
+    /// ```
+    ///
+    /// The key in the file is used only when none is given with [`api_key`](Self::api_key) or
+    /// in the environment. Settings made on the returned builder override the file.
+    ///
+    /// Named profiles, e.g. `[profiles.dev]`, override the top-level settings. The profile
+    /// named by the `NEWS_API_PROFILE` environment variable is used when set, see
+    /// [`from_config_file_profile`](Self::from_config_file_profile) to select one explicitly.
+    #[cfg(feature = "config-file")]
+    pub fn from_config_file(path: impl AsRef<std::path::Path>) -> Result<Self, ClientBuildError> {
+        let path = path.as_ref();
+        ConfigFile::load(path)
+            .and_then(|config| match env::var(NEWS_API_PROFILE_ENV) {
+                Ok(profile) if !profile.is_empty() => config.profile(&profile),
+                _ => Ok(config),
+            })
+            .and_then(|config| config.apply(Self::new()))
+            .map_err(|source| ClientBuildError::ConfigFile {
+                path: path.to_path_buf(),
+                source,
+            })
+    }
 
-        for (key, value) in Self::get_top_headlines_query_params(request) {
-            url.query_pairs_mut().append_pair(&key, &value);
-        }
+    /// A builder with the settings of the named profile of a TOML config file, see
+    /// [`from_config_file`](Self::from_config_file).
+    #[cfg(feature = "config-file")]
+    pub fn from_config_file_profile(
+        path: impl AsRef<std::path::Path>,
+        profile: &str,
+    ) -> Result<Self, ClientBuildError> {
+        let path = path.as_ref();
+        ConfigFile::load(path)
+            .and_then(|config| config.profile(profile))
+            .and_then(|config| config.apply(Self::new()))
+            .map_err(|source| ClientBuildError::ConfigFile {
+                path: path.to_path_buf(),
+                source,
+            })
+    }
 
-        url.query_pairs_mut().finish();
+    #[cfg(feature = "config-file")]
+    pub(crate) fn config_file_api_key(mut self, api_key: SecretString) -> Self {
+        self.config_file_api_key = Some(api_key);
+        self
     }
 
-    fn get_top_headlines_query_params(request: &GetTopHeadlinesRequest) -> Vec<(String, String)> {
-        let mut query_params = Vec::new();
+    /// Sends requests with `client` rather than a default one, e.g. one configured with a
+    /// proxy, custom TLS or connection pool settings. The timeouts, headers and retries set on
+    /// this builder still apply to each request.
+    pub fn with_http_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
 
-        if let Some(country) = request.get_country() {
-            query_params.push(("country".to_string(), country.to_string()));
-        }
+    /// Trusts `certificate` in addition to the system's root certificates, e.g. the CA of a
+    /// corporate gateway or a test proxy.
+    #[cfg(feature = "__tls")]
+    pub fn add_root_certificate(mut self, certificate: reqwest::Certificate) -> Self {
+        self.http_options.root_certificates.push(certificate);
+        self
+    }
 
-        if let Some(category) = request.get_category() {
-            query_params.push(("category".to_string(), category.to_string()));
-        }
+    /// Accepts any TLS certificate, including expired and self-signed ones. Only meant for
+    /// test proxies: any server can then impersonate the API and read the API key.
+    #[cfg(feature = "__tls")]
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.http_options.accept_invalid_certs = accept;
+        self
+    }
 
-        if let Some(sources) = request.get_sources() {
-            query_params.push(("sources".to_string(), sources.to_string()));
-        }
+    /// Maximum number of idle connections kept open to the API for reuse, unlimited by
+    /// default. Pollers sending bursts of concurrent requests can raise reuse by keeping
+    /// more, memory-constrained clients can keep fewer.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.http_options.pool_max_idle_per_host = Some(max);
+        self
+    }
 
-        if !request.get_search_term().is_empty() {
-            query_params.push(("q".to_string(), request.get_search_term().to_string()));
-        }
+    /// How long an idle connection is kept open for reuse, 90 seconds by default. Clients
+    /// polling less often than that reconnect on every poll unless it is raised.
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.http_options.pool_idle_timeout = Some(timeout);
+        self
+    }
 
-        if *request.get_page_size() > 1 {
-            query_params.push(("pageSize".to_string(), request.get_page_size().to_string()));
-        }
+    /// Sends HTTP/2 pings at `interval` to keep connections alive through proxies and load
+    /// balancers that drop quiet ones. Off by default.
+    pub fn http2_keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.http_options.http2_keep_alive_interval = Some(interval);
+        self
+    }
 
-        if *request.get_page() > 1 {
-            query_params.push(("page".to_string(), request.get_page().to_string()));
-        }
+    /// How long to wait for a keep-alive ping to be answered before closing the connection,
+    /// 20 seconds by default.
+    pub fn http2_keep_alive_timeout(mut self, timeout: Duration) -> Self {
+        self.http_options.http2_keep_alive_timeout = Some(timeout);
+        self
+    }
 
-        query_params
+    /// Whether keep-alive pings are also sent while no request is in flight. Off by default,
+    /// so idle connections may still be dropped.
+    pub fn http2_keep_alive_while_idle(mut self, enabled: bool) -> Self {
+        self.http_options.http2_keep_alive_while_idle = enabled;
+        self
     }
 
-    fn get_endpoint_with_query_params_for_everything(
-        url: &mut Url,
-        request: &GetEverythingRequest,
-    ) {
-        url.set_path(EVERYTHING_ENDPOINT);
-        url.query_pairs_mut().clear();
+    pub fn build(mut self) -> Result<NewsApiClient<reqwest::Client>, ClientBuildError> {
+        let options = std::mem::take(&mut self.http_options);
+        let client = match self.http_client.take() {
+            Some(_) if options.is_set() => return Err(options_with_http_client()),
+            Some(client) => client,
+            None => {
+                let build_error = options.build_error();
+                options
+                    .apply(reqwest::Client::builder())
+                    .build()
+                    .map_err(build_error)?
+            }
+        };
+        self.build_with(client)
+    }
 
-        let query_params = Self::get_everything_query_params(request);
-        for (key, value) in query_params {
-            url.query_pairs_mut().append_pair(&key, &value);
+    /// Builds a client sending its requests with `transport` rather than reqwest, e.g. a
+    /// backend with a smaller dependency footprint or one recording requests in tests.
+    ///
+    /// Fails if [`with_http_client`](Self::with_http_client) or any connection setting was
+    /// given, since those only configure reqwest.
+    pub fn build_with_transport<T: HttpTransport>(
+        mut self,
+        transport: T,
+    ) -> Result<NewsApiClient<T>, ClientBuildError> {
+        if self.http_client.take().is_some() || self.http_options.is_set() {
+            return Err(ClientBuildError::InvalidSetting {
+                setting: "HTTP client settings",
+                reason: "cannot be combined with a custom transport, configure them on the \
+                         transport instead",
+            });
         }
-
-        url.query_pairs_mut().finish();
+        self.build_with(transport)
     }
 
-    fn get_everything_query_params(request: &GetEverythingRequest) -> Vec<(String, String)> {
-        let mut query_params = Vec::new();
+    /// The key to use, in order of precedence the one given with [`api_key`](Self::api_key),
+    /// the first of the [`api_key_envs`](Self::api_key_envs) that is set, the one in the OS
+    /// credential store, or the one from the config file.
+    fn resolve_api_key(&self) -> Result<SecretString, ClientBuildError> {
+        if let Some(key) = &self.api_key {
+            return Ok(key.clone());
+        }
+        let resolved = self
+            .api_key_envs
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|key| !key.is_empty())
+            .map(SecretString::from);
+        #[cfg(feature = "keyring")]
+        let resolved = match (resolved, &self.keyring_account) {
+            (None, Some(account)) => crate::credential_store::load_api_key(account)
+                .map_err(ClientBuildError::CredentialStore)?,
+            (resolved, _) => resolved,
+        };
+        match resolved.or_else(|| self.config_file_api_key.clone()) {
+            Some(key) => Ok(key),
+            None if self.offline_mode == OfflineMode::CacheOnly => Ok(SecretString::default()),
+            None => Err(ClientBuildError::MissingApiKey {
+                env_vars: self.api_key_envs.clone(),
+            }),
+        }
+    }
 
-        query_params.push(("q".to_string(), request.get_search_term().to_string()));
+    fn build_with<T>(self, client: T) -> Result<NewsApiClient<T>, ClientBuildError> {
+        let api_key = self.resolve_api_key()?;
 
-        if let Some(language) = request.get_language() {
-            query_params.push(("language".to_string(), language.to_string().to_lowercase()));
+        if let Err(e) = &self.base_url {
+            return Err(ClientBuildError::InvalidBaseUrl(*e));
         }
 
-        if let Some(start_date) = request.get_start_date() {
-            query_params.push(("from".to_string(), start_date.to_rfc3339()));
+        self.config.validate()?;
+
+        if self
+            .timeout
+            .iter()
+            .chain(self.endpoint_timeouts.values())
+            .any(Duration::is_zero)
+        {
+            return Err(ClientBuildError::InvalidSetting {
+                setting: "timeout",
+                reason: "must be non-zero",
+            });
         }
 
-        if let Some(end_date) = request.get_end_date() {
-            query_params.push(("to".to_string(), end_date.to_rfc3339()));
+        if self.max_concurrent_requests == Some(0) {
+            return Err(ClientBuildError::InvalidSetting {
+                setting: "concurrency limit",
+                reason: "must allow at least one request",
+            });
         }
 
-        if *request.get_page_size() > 0 {
-            query_params.push(("pageSize".to_string(), request.get_page_size().to_string()));
+        if matches!(self.circuit_breaker, Some((0, _))) {
+            return Err(ClientBuildError::InvalidSetting {
+                setting: "circuit breaker threshold",
+                reason: "must be at least one failure",
+            });
         }
 
-        if *request.get_page() > 1 {
-            query_params.push(("page".to_string(), request.get_page().to_string()));
+        if self.log_summary_interval == Some(Duration::ZERO) {
+            return Err(ClientBuildError::InvalidSetting {
+                setting: "log summary interval",
+                reason: "must be non-zero",
+            });
         }
 
-        query_params
-    }
+        self.user_agent_header()?;
 
-    fn get_endpoint_with_query_params_for_sources(url: &mut Url, request: &GetSourcesRequest) {
-        url.set_path(SOURCES_ENDPOINT);
-        url.query_pairs_mut().clear();
+        Ok(self.build_client(client, api_key))
+    }
 
-        let query_params = Self::get_sources_query_params(request);
-        for (key, value) in query_params {
-            url.query_pairs_mut().append_pair(&key, &value);
+    /// The `User-Agent` header, the application's followed by the crate's.
+    fn user_agent_header(&self) -> Result<HeaderValue, ClientBuildError> {
+        match &self.user_agent {
+            Some(user_agent) => HeaderValue::try_from(format!(
+                "{user_agent} {NEWS_API_CLIENT_USER_AGENT}"
+            ))
+            .map_err(|_| ClientBuildError::InvalidSetting {
+                setting: "user agent",
+                reason: "must be a valid header value",
+            }),
+            None => Ok(HeaderValue::from_static(NEWS_API_CLIENT_USER_AGENT)),
         }
-
-        url.query_pairs_mut().finish();
     }
 
-    fn get_sources_query_params(request: &GetSourcesRequest) -> Vec<(String, String)> {
-        let mut query_params = Vec::new();
-
-        if let Some(category) = request.get_category() {
-            query_params.push(("category".to_string(), category.to_string()));
-        }
+    fn build_client<T>(self, client: T, api_key: SecretString) -> NewsApiClient<T> {
+        let user_agent = self
+            .user_agent_header()
+            .unwrap_or_else(|_| HeaderValue::from_static(NEWS_API_CLIENT_USER_AGENT));
+        let base_url = self
+            .base_url
+            .unwrap_or_else(|_| Url::parse(NEWS_API_URI).unwrap());
 
-        if let Some(language) = request.get_language() {
-            query_params.push(("language".to_string(), language.to_string().to_lowercase()));
-        }
+        let rate_limiter = token_bucket(self.config.get_rate_limit(), &self.clock);
+        #[cfg(feature = "governor")]
+        let rate_limiter = self.governor.or(rate_limiter);
+        let request_log = self
+            .log_summary_interval
+            .map(|interval| Arc::new(RequestLog::new(interval)));
 
-        if let Some(country) = request.get_country() {
-            query_params.push(("country".to_string(), country.to_string()));
+        NewsApiClient {
+            client,
+            keys: Arc::new(KeyRing::new(
+                api_key,
+                self.backup_api_keys,
+                self.key_cool_down,
+            )),
+            base_url,
+            api_version: self.api_version,
+            config: Arc::new(ConfigCell::new(LiveConfig {
+                config: self.config,
+                rate_limiter,
+            })),
+            max_retry_duration: self.max_retry_duration,
+            timeout: self.timeout,
+            endpoint_timeouts: self.endpoint_timeouts,
+            on_retry: self.on_retry,
+            on_request: self.on_request,
+            error_mapper: self.error_mapper,
+            clock: self.clock,
+            quota: Arc::new(QuotaState::new(self.quota_budget, self.quota_alarm)),
+            concurrency_limiter: self
+                .max_concurrent_requests
+                .map(|n| Arc::new(ConcurrencyLimiter::new(n))),
+            circuit_breaker: self
+                .circuit_breaker
+                .map(|(threshold, cool_down)| Arc::new(CircuitBreaker::new(threshold, cool_down))),
+            cache_policies: self.cache_policies,
+            cache: Arc::default(),
+            sources_catalog: Arc::new(CatalogCache::new(self.sources_catalog_ttl)),
+            offline: Arc::new(AtomicBool::new(self.offline_mode == OfflineMode::CacheOnly)),
+            default_sort_by: self.default_sort_by,
+            page_size_policy: self.page_size_policy,
+            strictness: self.strictness,
+            summary_length: self.summary_length,
+            skip_malformed_articles: self.skip_malformed_articles,
+            auth_mode: self.auth_mode,
+            user_agent,
+            default_headers: self.default_headers,
+            request_id_header: self.request_id_header,
+            request_id: None,
+            request_log,
+            log_redaction: Arc::new(self.log_redaction),
         }
+    }
+}
 
-        query_params
+/// TLS, pool and HTTP/2 settings only apply to the HTTP client the builder creates.
+fn options_with_http_client() -> ClientBuildError {
+    ClientBuildError::InvalidSetting {
+        setting: "HTTP client settings",
+        reason: "cannot be combined with with_http_client, configure them on the supplied \
+                 client instead",
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::model::{Country, Language, NewsCategory};
-    use chrono::{DateTime, Utc};
-    use mockito;
-    use serial_test::serial;
-    use std::collections::HashMap;
-    use std::str::FromStr;
-    use std::time::Duration;
+#[cfg(feature = "blocking")]
+#[derive(Default)]
+pub struct BlockingNewsApiClientBuilder {
+    inner: NewsApiClientBuilder,
+    http_client: Option<reqwest::blocking::Client>,
+}
 
-    fn create_test_client() -> NewsApiClient<reqwest::Client> {
-        let api_key = "test-api-key";
-        let mut client = NewsApiClient::new(api_key);
-        let server = mockito::Server::new();
-        let mock_url = server.url();
-        client.base_url = Url::parse(&format!("http://{}", mock_url)).unwrap();
-        client
+#[cfg(feature = "blocking")]
+impl BlockingNewsApiClientBuilder {
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    #[test]
-    fn test_parse_error_response() {
-        let error_json =
-            r#"{"status":"error","code":"apiKeyInvalid","message":"Your API key is invalid"}"#;
-        let error = NewsApiClient::<reqwest::Client>::parse_error_response_internal(
-            error_json.to_string(),
-            400,
-        );
+    pub fn api_key(mut self, api_key: impl Into<SecretString>) -> Self {
+        self.inner = self.inner.api_key(api_key);
+        self
+    }
 
-        match error {
-            ApiClientError::InvalidResponse(response) => {
-                assert_eq!(response.status, "error");
-                assert_eq!(response.code, ApiClientErrorCode::ApiKeyInvalid);
-                assert_eq!(response.message, "Your API key is invalid");
-            }
-            _ => panic!("Expected InvalidResponse error"),
-        }
+    /// See [`NewsApiClientBuilder::api_key_env`].
+    pub fn api_key_env(mut self, name: impl Into<String>) -> Self {
+        self.inner = self.inner.api_key_env(name);
+        self
+    }
 
-        let error_json =
-            r#"{"status":"error","code":"parameterInvalid","message":"Invalid parameter"}"#;
-        let error = NewsApiClient::<reqwest::Client>::parse_error_response_internal(
-            error_json.to_string(),
-            400,
-        );
+    /// See [`NewsApiClientBuilder::api_key_envs`].
+    pub fn api_key_envs<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.inner = self.inner.api_key_envs(names);
+        self
+    }
 
-        match error {
-            ApiClientError::InvalidResponse(response) => {
-                assert_eq!(response.code, ApiClientErrorCode::ParameterInvalid);
-            }
-            _ => panic!("Expected InvalidResponse error"),
-        }
+    /// See [`NewsApiClientBuilder::api_key_from_keyring`].
+    #[cfg(feature = "keyring")]
+    pub fn api_key_from_keyring(mut self, account: impl Into<String>) -> Self {
+        self.inner = self.inner.api_key_from_keyring(account);
+        self
+    }
 
-        let error_json = r#"invalid json"#;
-        let error = NewsApiClient::<reqwest::Client>::parse_error_response_internal(
-            error_json.to_string(),
-            400,
-        );
+    /// See [`NewsApiClientBuilder::backup_api_key`].
+    pub fn backup_api_key(mut self, api_key: impl Into<SecretString>) -> Self {
+        self.inner = self.inner.backup_api_key(api_key);
+        self
+    }
 
-        match error {
-            ApiClientError::InvalidResponse(response) => {
-                assert_eq!(response.code, ApiClientErrorCode::UnexpectedError);
-            }
-            _ => panic!("Expected InvalidResponse error"),
-        }
+    /// See [`NewsApiClientBuilder::key_cool_down`].
+    pub fn key_cool_down(mut self, cool_down: Duration) -> Self {
+        self.inner = self.inner.key_cool_down(cool_down);
+        self
     }
 
-    #[test]
-    fn test_get_request_headers() {
-        let client = create_test_client();
-        let headers = client.get_request_headers().unwrap();
+    /// See [`NewsApiClientBuilder::base_url`].
+    pub fn base_url(mut self, url: impl AsRef<str>) -> Self {
+        self.inner = self.inner.base_url(url);
+        self
+    }
 
-        assert_eq!(
-            headers.get(AUTHORIZATION).unwrap().to_str().unwrap(),
-            "Bearer test-api-key"
-        );
-        assert_eq!(
-            headers.get(USER_AGENT).unwrap().to_str().unwrap(),
-            NEWS_API_CLIENT_USER_AGENT
-        );
+    pub fn retry(mut self, strategy: RetryStrategy, max_retries: usize) -> Self {
+        self.inner = self.inner.retry(strategy, max_retries);
+        self
     }
 
-    #[test]
-    fn test_top_headlines_validate_request_country_and_category() {
-        let request = GetTopHeadlinesRequest::builder()
-            .country(Country::US)
-            .category(NewsCategory::Business)
-            .search_term(String::new())
-            .page_size(20)
-            .page(1)
-            .build()
-            .unwrap();
-        assert!(NewsApiClient::<reqwest::Client>::top_headlines_validate_request(&request).is_ok());
+    /// See [`NewsApiClientBuilder::backoff`].
+    #[cfg(feature = "backoff")]
+    pub fn backoff(mut self, policy: backoff::ExponentialBackoff) -> Self {
+        self.inner = self.inner.backoff(policy);
+        self
     }
 
-    #[test]
-    fn test_top_headlines_validate_request_sources_only() {
-        let request = GetTopHeadlinesRequest::builder()
-            .sources("bbc-news,cnn".to_string())
-            .search_term(String::new())
-            .page_size(20)
-            .page(1)
-            .build()
-            .unwrap();
-        assert!(NewsApiClient::<reqwest::Client>::top_headlines_validate_request(&request).is_ok());
+    /// See [`NewsApiClientBuilder::retry_on`].
+    pub fn retry_on(mut self, retry_on: RetryOn) -> Self {
+        self.inner = self.inner.retry_on(retry_on);
+        self
     }
 
-    #[test]
-    fn test_top_headlines_validate_request_sources_with_country() {
-        let request = GetTopHeadlinesRequest::builder()
-            .sources("bbc-news".to_string())
-            .country(Country::US)
-            .search_term(String::new())
-            .page_size(20)
-            .page(1)
-            .build();
+    /// See [`NewsApiClientBuilder::max_retry_duration`].
+    pub fn max_retry_duration(mut self, duration: Duration) -> Self {
+        self.inner = self.inner.max_retry_duration(duration);
+        self
+    }
 
-        assert!(request.is_err());
+    /// See [`NewsApiClientBuilder::timeout`].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.timeout(timeout);
+        self
     }
 
-    #[test]
-    fn test_top_headlines_validate_request_sources_with_category() {
-        let request = GetTopHeadlinesRequest::builder()
+    /// See [`NewsApiClientBuilder::endpoint_timeout`].
+    pub fn endpoint_timeout(mut self, endpoint: Endpoint, timeout: Duration) -> Self {
+        self.inner = self.inner.endpoint_timeout(endpoint, timeout);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::on_retry`].
+    pub fn on_retry<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(usize, &ApiClientError, Duration) + Send + Sync + 'static,
+    {
+        self.inner = self.inner.on_retry(callback);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::on_request`].
+    pub fn on_request<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(Endpoint, Duration, Option<&ApiClientError>) + Send + Sync + 'static,
+    {
+        self.inner = self.inner.on_request(callback);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::error_mapper`].
+    pub fn error_mapper<F>(mut self, mapper: F) -> Self
+    where
+        F: Fn(StatusCode, &HeaderMap, &str) -> Option<ApiClientError> + Send + Sync + 'static,
+    {
+        self.inner = self.inner.error_mapper(mapper);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::sleeper`].
+    pub fn sleeper(mut self, sleeper: impl Sleeper + 'static) -> Self {
+        self.inner = self.inner.sleeper(sleeper);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::clock`].
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.inner = self.inner.clock(clock);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::rate_limit`].
+    pub fn rate_limit(mut self, requests_per_window: u32, window: Duration) -> Self {
+        self.inner = self.inner.rate_limit(requests_per_window, window);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::governor_rate_limiter`].
+    #[cfg(feature = "governor")]
+    pub fn governor_rate_limiter(
+        mut self,
+        limiter: Arc<DefaultDirectRateLimiter>,
+        jitter: Option<Jitter>,
+    ) -> Self {
+        self.inner = self.inner.governor_rate_limiter(limiter, jitter);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::governor_keyed_rate_limiter`].
+    #[cfg(feature = "governor")]
+    pub fn governor_keyed_rate_limiter(
+        mut self,
+        limiter: Arc<DefaultKeyedRateLimiter<String>>,
+        jitter: Option<Jitter>,
+    ) -> Self {
+        self.inner = self.inner.governor_keyed_rate_limiter(limiter, jitter);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::max_concurrent_requests`].
+    pub fn max_concurrent_requests(mut self, n: usize) -> Self {
+        self.inner = self.inner.max_concurrent_requests(n);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::circuit_breaker`].
+    pub fn circuit_breaker(mut self, failure_threshold: u32, cool_down: Duration) -> Self {
+        self.inner = self.inner.circuit_breaker(failure_threshold, cool_down);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::quota_budget`].
+    pub fn quota_budget(mut self, budget: QuotaBudget) -> Self {
+        self.inner = self.inner.quota_budget(budget);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::on_quota_threshold`].
+    pub fn on_quota_threshold<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(u32, u32) + Send + Sync + 'static,
+    {
+        self.inner = self.inner.on_quota_threshold(callback);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::on_quota_thresholds`].
+    pub fn on_quota_thresholds<F>(mut self, thresholds: &[f64], callback: F) -> Self
+    where
+        F: Fn(u32, u32) + Send + Sync + 'static,
+    {
+        self.inner = self.inner.on_quota_thresholds(thresholds, callback);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::cache_policy`].
+    pub fn cache_policy(mut self, endpoint: Endpoint, policy: CachePolicy) -> Self {
+        self.inner = self.inner.cache_policy(endpoint, policy);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::sources_catalog_ttl`].
+    pub fn sources_catalog_ttl(mut self, ttl: Duration) -> Self {
+        self.inner = self.inner.sources_catalog_ttl(ttl);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::offline_mode`].
+    pub fn offline_mode(mut self, mode: OfflineMode) -> Self {
+        self.inner = self.inner.offline_mode(mode);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::default_sort_by`].
+    pub fn default_sort_by(mut self, sort_by: ArticleSortBy) -> Self {
+        self.inner = self.inner.default_sort_by(sort_by);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::default_page_size`].
+    pub fn default_page_size(mut self, page_size: i32) -> Self {
+        self.inner = self.inner.default_page_size(page_size);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::page_size_policy`].
+    pub fn page_size_policy(mut self, policy: PageSizePolicy) -> Self {
+        self.inner = self.inner.page_size_policy(policy);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::strictness`].
+    pub fn strictness(mut self, strictness: Strictness) -> Self {
+        self.inner = self.inner.strictness(strictness);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::summary_length`].
+    pub fn summary_length(mut self, max_chars: usize) -> Self {
+        self.inner = self.inner.summary_length(max_chars);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::skip_malformed_articles`].
+    pub fn skip_malformed_articles(mut self, skip: bool) -> Self {
+        self.inner = self.inner.skip_malformed_articles(skip);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::auth_mode`].
+    pub fn auth_mode(mut self, mode: AuthMode) -> Self {
+        self.inner = self.inner.auth_mode(mode);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::user_agent`].
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.inner = self.inner.user_agent(user_agent);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::default_header`].
+    pub fn default_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.inner = self.inner.default_header(name, value);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::default_headers`].
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.inner = self.inner.default_headers(headers);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::request_id_header`].
+    pub fn request_id_header(mut self, name: HeaderName) -> Self {
+        self.inner = self.inner.request_id_header(name);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::log_summary_interval`].
+    pub fn log_summary_interval(mut self, interval: Duration) -> Self {
+        self.inner = self.inner.log_summary_interval(interval);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::log_redaction`].
+    pub fn log_redaction(mut self, redaction: LogRedaction) -> Self {
+        self.inner = self.inner.log_redaction(redaction);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::api_version`].
+    pub fn api_version(mut self, version: ApiVersion) -> Self {
+        self.inner = self.inner.api_version(version);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::profile`].
+    pub fn profile(mut self, profile: Profile) -> Self {
+        self.inner = self.inner.profile(profile);
+        self
+    }
+
+    pub fn from_env() -> Self {
+        Self {
+            inner: NewsApiClientBuilder::from_env(),
+            http_client: None,
+        }
+    }
+
+    /// See [`NewsApiClientBuilder::from_config_file`].
+    #[cfg(feature = "config-file")]
+    pub fn from_config_file(path: impl AsRef<std::path::Path>) -> Result<Self, ClientBuildError> {
+        Ok(Self {
+            inner: NewsApiClientBuilder::from_config_file(path)?,
+            http_client: None,
+        })
+    }
+
+    /// See [`NewsApiClientBuilder::from_config_file_profile`].
+    #[cfg(feature = "config-file")]
+    pub fn from_config_file_profile(
+        path: impl AsRef<std::path::Path>,
+        profile: &str,
+    ) -> Result<Self, ClientBuildError> {
+        Ok(Self {
+            inner: NewsApiClientBuilder::from_config_file_profile(path, profile)?,
+            http_client: None,
+        })
+    }
+
+    /// See [`NewsApiClientBuilder::with_http_client`].
+    pub fn with_http_client(mut self, client: reqwest::blocking::Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::add_root_certificate`].
+    #[cfg(feature = "__tls")]
+    pub fn add_root_certificate(mut self, certificate: reqwest::Certificate) -> Self {
+        self.inner = self.inner.add_root_certificate(certificate);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::danger_accept_invalid_certs`].
+    #[cfg(feature = "__tls")]
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.inner = self.inner.danger_accept_invalid_certs(accept);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::pool_max_idle_per_host`].
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.inner = self.inner.pool_max_idle_per_host(max);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::pool_idle_timeout`].
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.pool_idle_timeout(timeout);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::http2_keep_alive_interval`].
+    pub fn http2_keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.inner = self.inner.http2_keep_alive_interval(interval);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::http2_keep_alive_timeout`].
+    pub fn http2_keep_alive_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.http2_keep_alive_timeout(timeout);
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::http2_keep_alive_while_idle`].
+    pub fn http2_keep_alive_while_idle(mut self, enabled: bool) -> Self {
+        self.inner = self.inner.http2_keep_alive_while_idle(enabled);
+        self
+    }
+
+    pub fn build(mut self) -> Result<NewsApiClient<reqwest::blocking::Client>, ClientBuildError> {
+        let options = std::mem::take(&mut self.inner.http_options);
+        let client = match self.http_client.take() {
+            Some(_) if options.is_set() => return Err(options_with_http_client()),
+            Some(client) => client,
+            None => {
+                let build_error = options.build_error();
+                options
+                    .apply_blocking(reqwest::blocking::Client::builder())
+                    .build()
+                    .map_err(build_error)?
+            }
+        };
+        self.inner.build_with(client)
+    }
+}
+
+#[cfg(feature = "blocking")]
+mod blocking {
+    use super::*;
+    use reqwest::blocking::Client as BlockingClient;
+
+    impl NewsApiClient<BlockingClient> {
+        pub fn new_blocking(api_key: &str) -> Self {
+            NewsApiClientBuilder::new().build_client(BlockingClient::new(), api_key.into())
+        }
+
+        pub fn builder_blocking() -> super::BlockingNewsApiClientBuilder {
+            super::BlockingNewsApiClientBuilder::new()
+        }
+
+        fn send(
+            &self,
+            target: RequestTarget<'_>,
+            deadline: Option<Instant>,
+            request_id: Option<&str>,
+        ) -> Result<ResponseBody, ApiClientError> {
+            if let Some(breaker) = &self.circuit_breaker {
+                breaker.check(self.clock.now_utc())?;
+            }
+            let mut result = self
+                .send_request(target, deadline, request_id)
+                .map_err(redact_error);
+            for _ in 1..self.keys.len() {
+                if !self.should_fail_over(&result) {
+                    break;
+                }
+                result = self
+                    .send_request(target, deadline, request_id)
+                    .map_err(redact_error);
+            }
+            if let Some(breaker) = &self.circuit_breaker {
+                breaker.record(&result, self.clock.now_utc());
+            }
+            result
+        }
+
+        fn send_request(
+            &self,
+            target: RequestTarget<'_>,
+            deadline: Option<Instant>,
+            request_id: Option<&str>,
+        ) -> Result<ResponseBody, ApiClientError> {
+            let RequestTarget { endpoint, url, .. } = target;
+            self.quota.check(self.clock.now_utc())?;
+            let (key_index, api_key) = self.keys.active(self.clock.now_utc())?;
+            self.quota.acquire(self.clock.now_utc())?;
+            if let Some(rate_limiter) = &self.config.load().rate_limiter {
+                rate_limiter.acquire_blocking(&api_key.fingerprint());
+            }
+            let _permit = self
+                .concurrency_limiter
+                .as_ref()
+                .map(|limiter| limiter.acquire_blocking());
+            self.log_request(format_args!(
+                "Request URL: {}{}",
+                self.log_redaction.url(url),
+                RequestIdTag(request_id)
+            ));
+
+            let started = self.clock.now();
+            let result = self.fetch(target, deadline, request_id, key_index, api_key);
+            self.record_request(endpoint, started, &result);
+            result
+        }
+
+        fn fetch(
+            &self,
+            target: RequestTarget<'_>,
+            deadline: Option<Instant>,
+            request_id: Option<&str>,
+            key_index: usize,
+            api_key: &SecretString,
+        ) -> Result<ResponseBody, ApiClientError> {
+            let RequestTarget {
+                endpoint,
+                url,
+                cache_key,
+            } = target;
+            let mut headers = self.get_request_headers(api_key)?;
+            let cached = self.revalidation_entry(endpoint, cache_key);
+            if let Some(entry) = &cached {
+                entry.validators.apply(&mut headers);
+            }
+            if let Some(request_id) = request_id {
+                headers.insert(self.request_id_header(), HeaderValue::from_str(request_id)?);
+            }
+            #[cfg(feature = "opentelemetry")]
+            let span = RequestSpan::start(endpoint, &Method::GET, url, &mut headers);
+            let request = HttpRequest::new(
+                Method::GET,
+                self.auth_mode.request_url(url, api_key).as_str(),
+            )
+            .with_headers(headers)
+            .with_timeout(self.request_timeout(endpoint, deadline));
+            let response = BlockingHttpTransport::send(&self.client, request);
+            #[cfg(feature = "opentelemetry")]
+            span.record(&response);
+            let response = response?;
+            self.handle_response(target, response, cached, request_id, key_index)
+        }
+
+        fn execute<R: DeserializeOwned + WithResponseMeta>(
+            &self,
+            endpoint: Endpoint,
+            url: Url,
+            cache_key: String,
+            normalizations: Vec<Normalization>,
+        ) -> Result<ResponseEnvelope<R>, ApiClientError> {
+            let started = self.clock.now();
+            let target = RequestTarget {
+                endpoint,
+                url: &url,
+                cache_key: &cache_key,
+            };
+            if self.get_offline_mode() == OfflineMode::CacheOnly {
+                return self
+                    .offline_response(target, normalizations)
+                    .map(|envelope| {
+                        envelope.with_latency(self.clock.now().saturating_duration_since(started))
+                    });
+            }
+            let config = self.config.load();
+            let deadline = self.retry_deadline();
+            let retries = Mutex::new(Vec::new());
+            let request_id = self.next_request_id();
+            let result = retry_blocking_within(
+                config.config.delays(),
+                config.config.get_max_retries(),
+                deadline,
+                |e| config.config.get_retry_on().should_retry(e),
+                |attempt, e, delay| {
+                    self.notify_retry(endpoint, request_id.as_deref(), &retries, attempt, e, delay)
+                },
+                self.clock.as_ref(),
+                || self.send(target, deadline, request_id.as_deref()),
+            );
+            self.complete_response(
+                target,
+                result,
+                retries.into_inner().unwrap(),
+                request_id,
+                normalizations,
+            )
+            .map(|envelope| {
+                envelope.with_latency(self.clock.now().saturating_duration_since(started))
+            })
+        }
+
+        pub fn get_everything(
+            self,
+            request: &GetEverythingRequest,
+        ) -> Result<GetEverythingResponse, ApiClientError> {
+            self.get_everything_with_meta(request)
+                .map(ResponseEnvelope::into_body)
+        }
+
+        /// See [`NewsApiClient::get_everything_raw`].
+        pub fn get_everything_raw(
+            self,
+            request: &GetEverythingRequest,
+        ) -> Result<RawResponse<GetEverythingResponse>, ApiClientError> {
+            self.get_everything_with_meta(request)
+                .map(ResponseEnvelope::into_raw)
+        }
+
+        /// See [`NewsApiClient::get_everything_with_meta`].
+        pub fn get_everything_with_meta(
+            self,
+            request: &GetEverythingRequest,
+        ) -> Result<ResponseEnvelope<GetEverythingResponse>, ApiClientError> {
+            self.log_request_details(request);
+            let mut normalizations = Vec::new();
+            let (request, url) = self.everything_url(request, &mut normalizations)?;
+            let envelope = self.execute(
+                Endpoint::Everything,
+                url,
+                request.cache_key(),
+                normalizations,
+            )?;
+            Ok(envelope.map(|mut response: GetEverythingResponse| {
+                response.highlight(&Query::new(request.get_search_term()));
+                response.summarize(self.summary_length);
+                response
+            }))
+        }
+
+        pub fn get_top_headlines(
+            self,
+            request: &GetTopHeadlinesRequest,
+        ) -> Result<TopHeadlinesResponse, ApiClientError> {
+            self.get_top_headlines_with_meta(request)
+                .map(ResponseEnvelope::into_body)
+        }
+
+        /// See [`NewsApiClient::get_top_headlines_raw`].
+        pub fn get_top_headlines_raw(
+            self,
+            request: &GetTopHeadlinesRequest,
+        ) -> Result<RawResponse<TopHeadlinesResponse>, ApiClientError> {
+            self.get_top_headlines_with_meta(request)
+                .map(ResponseEnvelope::into_raw)
+        }
+
+        /// See [`NewsApiClient::get_top_headlines_with_meta`].
+        pub fn get_top_headlines_with_meta(
+            self,
+            request: &GetTopHeadlinesRequest,
+        ) -> Result<ResponseEnvelope<TopHeadlinesResponse>, ApiClientError> {
+            self.log_request_details(request);
+            let mut normalizations = Vec::new();
+            let (request, url) = self.top_headlines_url(request, &mut normalizations)?;
+            let envelope = self.execute(
+                Endpoint::TopHeadlines,
+                url,
+                request.cache_key(),
+                normalizations,
+            )?;
+            Ok(envelope.map(|mut response: TopHeadlinesResponse| {
+                response.highlight(&Query::new(request.get_search_term()));
+                response.summarize(self.summary_length);
+                response
+            }))
+        }
+
+        pub fn get_sources(
+            self,
+            request: &GetSourcesRequest,
+        ) -> Result<GetSourcesResponse, ApiClientError> {
+            self.get_sources_with_meta(request)
+                .map(ResponseEnvelope::into_body)
+        }
+
+        /// See [`NewsApiClient::get_sources_raw`].
+        pub fn get_sources_raw(
+            self,
+            request: &GetSourcesRequest,
+        ) -> Result<RawResponse<GetSourcesResponse>, ApiClientError> {
+            self.get_sources_with_meta(request)
+                .map(ResponseEnvelope::into_raw)
+        }
+
+        /// See [`NewsApiClient::get_sources_with_meta`].
+        pub fn get_sources_with_meta(
+            self,
+            request: &GetSourcesRequest,
+        ) -> Result<ResponseEnvelope<GetSourcesResponse>, ApiClientError> {
+            self.log_request_details(request);
+
+            let url = self.sources_url(request);
+            self.execute(Endpoint::Sources, url, request.cache_key(), Vec::new())
+        }
+
+        /// See [`NewsApiClient::sources_catalog`].
+        pub fn sources_catalog(self) -> Result<Arc<SourceCatalog>, ApiClientError> {
+            if let Some(catalog) = self.sources_catalog.get(self.clock.now_utc()) {
+                return Ok(catalog);
+            }
+            let cache = self.sources_catalog.clone();
+            let clock = self.clock.clone();
+            let strictness = self.strictness;
+            let catalog = match self.get_sources(&GetSourcesRequest::builder().build()) {
+                Ok(response) => Arc::new(SourceCatalog::from(response)),
+                Err(e) => return catalog::fetch_failed(e, strictness),
+            };
+            cache.store(clock.now_utc(), catalog.clone());
+            Ok(catalog)
+        }
+
+        /// See [`NewsApiClient::warm_up`].
+        pub fn warm_up(self, prime_sources_catalog: bool) -> Result<(), ApiClientError> {
+            if self.get_offline_mode() == OfflineMode::CacheOnly {
+                return Ok(());
+            }
+            BlockingHttpTransport::send(&self.client, self.warm_up_request())?;
+            if prime_sources_catalog {
+                self.sources_catalog()?;
+            }
+            Ok(())
+        }
+
+        /// See [`NewsApiClient::ping`].
+        pub fn ping(self) -> Health {
+            if self.get_offline_mode() == OfflineMode::CacheOnly {
+                return Health::NetworkDown;
+            }
+            let request = GetSourcesRequest::builder().build();
+            let url = self.sources_url(&request);
+            let target = RequestTarget {
+                endpoint: Endpoint::Sources,
+                url: &url,
+                cache_key: &request.cache_key(),
+            };
+            Health::from_result(self.send(target, None, None))
+        }
+
+        pub fn with_retry(mut self, strategy: RetryStrategy, max_retries: usize) -> Self {
+            self.detach_config(|config| config.retry(strategy, max_retries));
+            self
+        }
+    }
+}
+
+impl NewsApiClient<reqwest::Client> {
+    pub fn new(api_key: &str) -> Self {
+        NewsApiClientBuilder::new().build_client(reqwest::Client::new(), api_key.into())
+    }
+
+    pub fn builder() -> NewsApiClientBuilder {
+        NewsApiClientBuilder::new()
+    }
+
+    pub fn from_env() -> Self {
+        match env::var(NEWS_API_KEY_ENV) {
+            Ok(api_key) => {
+                NewsApiClientBuilder::new().build_client(reqwest::Client::new(), api_key.into())
+            }
+            Err(_) => panic!("{NEWS_API_KEY_ENV} is not set"),
+        }
+    }
+}
+
+impl<T: HttpTransport> NewsApiClient<T> {
+    async fn send(
+        &self,
+        target: RequestTarget<'_>,
+        deadline: Option<Instant>,
+        request_id: Option<&str>,
+    ) -> Result<ResponseBody, ApiClientError> {
+        if let Some(breaker) = &self.circuit_breaker {
+            breaker.check(self.clock.now_utc())?;
+        }
+        let mut result = self
+            .send_request(target, deadline, request_id)
+            .await
+            .map_err(redact_error);
+        for _ in 1..self.keys.len() {
+            if !self.should_fail_over(&result) {
+                break;
+            }
+            result = self
+                .send_request(target, deadline, request_id)
+                .await
+                .map_err(redact_error);
+        }
+        if let Some(breaker) = &self.circuit_breaker {
+            breaker.record(&result, self.clock.now_utc());
+        }
+        result
+    }
+
+    async fn send_request(
+        &self,
+        target: RequestTarget<'_>,
+        deadline: Option<Instant>,
+        request_id: Option<&str>,
+    ) -> Result<ResponseBody, ApiClientError> {
+        let RequestTarget { endpoint, url, .. } = target;
+        self.quota.check(self.clock.now_utc())?;
+        let (key_index, api_key) = self.keys.active(self.clock.now_utc())?;
+        self.quota.acquire(self.clock.now_utc())?;
+        let rate_limiter = self.config.load().rate_limiter.clone();
+        if let Some(rate_limiter) = rate_limiter {
+            rate_limiter.acquire(&api_key.fingerprint()).await;
+        }
+        let _permit = match &self.concurrency_limiter {
+            Some(limiter) => Some(limiter.acquire().await),
+            None => None,
+        };
+        self.log_request(format_args!(
+            "Request URL: {}{}",
+            self.log_redaction.url(url),
+            RequestIdTag(request_id)
+        ));
+
+        let started = self.clock.now();
+        let result = self
+            .fetch(target, deadline, request_id, key_index, api_key)
+            .await;
+        self.record_request(endpoint, started, &result);
+        result
+    }
+
+    async fn fetch(
+        &self,
+        target: RequestTarget<'_>,
+        deadline: Option<Instant>,
+        request_id: Option<&str>,
+        key_index: usize,
+        api_key: &SecretString,
+    ) -> Result<ResponseBody, ApiClientError> {
+        let RequestTarget {
+            endpoint,
+            url,
+            cache_key,
+        } = target;
+        let mut headers = self.get_request_headers(api_key)?;
+        let cached = self.revalidation_entry(endpoint, cache_key);
+        if let Some(entry) = &cached {
+            entry.validators.apply(&mut headers);
+        }
+        if let Some(request_id) = request_id {
+            headers.insert(self.request_id_header(), HeaderValue::from_str(request_id)?);
+        }
+        #[cfg(feature = "opentelemetry")]
+        let span = RequestSpan::start(endpoint, &Method::GET, url, &mut headers);
+        let request = HttpRequest::new(
+            Method::GET,
+            self.auth_mode.request_url(url, api_key).as_str(),
+        )
+        .with_headers(headers)
+        .with_timeout(self.request_timeout(endpoint, deadline));
+        let response = self.client.send(request).await;
+        #[cfg(feature = "opentelemetry")]
+        span.record(&response);
+        let response = response?;
+        self.handle_response(target, response, cached, request_id, key_index)
+    }
+
+    async fn execute<R: DeserializeOwned + WithResponseMeta>(
+        &self,
+        endpoint: Endpoint,
+        url: Url,
+        cache_key: String,
+        normalizations: Vec<Normalization>,
+    ) -> Result<ResponseEnvelope<R>, ApiClientError> {
+        let started = self.clock.now();
+        let target = RequestTarget {
+            endpoint,
+            url: &url,
+            cache_key: &cache_key,
+        };
+        if self.get_offline_mode() == OfflineMode::CacheOnly {
+            return self
+                .offline_response(target, normalizations)
+                .map(|envelope| {
+                    envelope.with_latency(self.clock.now().saturating_duration_since(started))
+                });
+        }
+        let config = self.config.load();
+        let deadline = self.retry_deadline();
+        let retries = Mutex::new(Vec::new());
+        let request_id = self.next_request_id();
+        let result = retry_within(
+            config.config.delays(),
+            config.config.get_max_retries(),
+            deadline,
+            |e| config.config.get_retry_on().should_retry(e),
+            |attempt, e, delay| {
+                self.notify_retry(endpoint, request_id.as_deref(), &retries, attempt, e, delay)
+            },
+            self.clock.as_ref(),
+            || self.send(target, deadline, request_id.as_deref()),
+        )
+        .await;
+        self.complete_response(
+            target,
+            result,
+            retries.into_inner().unwrap(),
+            request_id,
+            normalizations,
+        )
+        .map(|envelope| envelope.with_latency(self.clock.now().saturating_duration_since(started)))
+    }
+
+    pub async fn get_everything(
+        &self,
+        request: &GetEverythingRequest,
+    ) -> Result<GetEverythingResponse, ApiClientError> {
+        self.get_everything_with_meta(request)
+            .await
+            .map(ResponseEnvelope::into_body)
+    }
+
+    /// Like [`get_everything`](Self::get_everything), also returning the response body as
+    /// received, e.g. to archive exact payloads or read fields the models don't cover.
+    pub async fn get_everything_raw(
+        &self,
+        request: &GetEverythingRequest,
+    ) -> Result<RawResponse<GetEverythingResponse>, ApiClientError> {
+        self.get_everything_with_meta(request)
+            .await
+            .map(ResponseEnvelope::into_raw)
+    }
+
+    /// Like [`get_everything`](Self::get_everything), returning the response in a
+    /// [`ResponseEnvelope`] with the call's latency, attempts, status and headers.
+    pub async fn get_everything_with_meta(
+        &self,
+        request: &GetEverythingRequest,
+    ) -> Result<ResponseEnvelope<GetEverythingResponse>, ApiClientError> {
+        self.log_request_details(request);
+        let mut normalizations = Vec::new();
+        let (request, url) = self.everything_url(request, &mut normalizations)?;
+        let envelope = self
+            .execute(
+                Endpoint::Everything,
+                url,
+                request.cache_key(),
+                normalizations,
+            )
+            .await?;
+        Ok(envelope.map(|mut response: GetEverythingResponse| {
+            response.highlight(&Query::new(request.get_search_term()));
+            response.summarize(self.summary_length);
+            response
+        }))
+    }
+
+    pub async fn get_top_headlines(
+        &self,
+        request: &GetTopHeadlinesRequest,
+    ) -> Result<TopHeadlinesResponse, ApiClientError> {
+        self.get_top_headlines_with_meta(request)
+            .await
+            .map(ResponseEnvelope::into_body)
+    }
+
+    /// Like [`get_top_headlines`](Self::get_top_headlines), also returning the response body as received.
+    pub async fn get_top_headlines_raw(
+        &self,
+        request: &GetTopHeadlinesRequest,
+    ) -> Result<RawResponse<TopHeadlinesResponse>, ApiClientError> {
+        self.get_top_headlines_with_meta(request)
+            .await
+            .map(ResponseEnvelope::into_raw)
+    }
+
+    /// Like [`get_top_headlines`](Self::get_top_headlines), returning the response in a
+    /// [`ResponseEnvelope`].
+    pub async fn get_top_headlines_with_meta(
+        &self,
+        request: &GetTopHeadlinesRequest,
+    ) -> Result<ResponseEnvelope<TopHeadlinesResponse>, ApiClientError> {
+        self.log_request_details(request);
+        let mut normalizations = Vec::new();
+        let (request, url) = self.top_headlines_url(request, &mut normalizations)?;
+        let envelope = self
+            .execute(
+                Endpoint::TopHeadlines,
+                url,
+                request.cache_key(),
+                normalizations,
+            )
+            .await?;
+        Ok(envelope.map(|mut response: TopHeadlinesResponse| {
+            response.highlight(&Query::new(request.get_search_term()));
+            response.summarize(self.summary_length);
+            response
+        }))
+    }
+
+    pub async fn get_sources(
+        &self,
+        request: &GetSourcesRequest,
+    ) -> Result<GetSourcesResponse, ApiClientError> {
+        self.get_sources_with_meta(request)
+            .await
+            .map(ResponseEnvelope::into_body)
+    }
+
+    /// Like [`get_sources`](Self::get_sources), also returning the response body as received.
+    pub async fn get_sources_raw(
+        &self,
+        request: &GetSourcesRequest,
+    ) -> Result<RawResponse<GetSourcesResponse>, ApiClientError> {
+        self.get_sources_with_meta(request)
+            .await
+            .map(ResponseEnvelope::into_raw)
+    }
+
+    /// Like [`get_sources`](Self::get_sources), returning the response in a
+    /// [`ResponseEnvelope`].
+    pub async fn get_sources_with_meta(
+        &self,
+        request: &GetSourcesRequest,
+    ) -> Result<ResponseEnvelope<GetSourcesResponse>, ApiClientError> {
+        self.log_request_details(request);
+
+        let url = self.sources_url(request);
+        self.execute(Endpoint::Sources, url, request.cache_key(), Vec::new())
+            .await
+    }
+
+    /// All sources, fetched once and reused for the
+    /// [`sources_catalog_ttl`](NewsApiClientBuilder::sources_catalog_ttl) by this client and
+    /// its clones. With the `embedded-sources` feature, a failed fetch falls back to
+    /// [`SourceCatalog::embedded`], which is not cached.
+    pub async fn sources_catalog(&self) -> Result<Arc<SourceCatalog>, ApiClientError> {
+        if let Some(catalog) = self.sources_catalog.get(self.clock.now_utc()) {
+            return Ok(catalog);
+        }
+        let catalog = match self
+            .get_sources(&GetSourcesRequest::builder().build())
+            .await
+        {
+            Ok(response) => Arc::new(SourceCatalog::from(response)),
+            Err(e) => return catalog::fetch_failed(e, self.strictness),
+        };
+        self.sources_catalog
+            .store(self.clock.now_utc(), catalog.clone());
+        Ok(catalog)
+    }
+
+    /// Opens a connection to the API ahead of the first request, resolving its host and
+    /// completing the TLS handshake, so that the first real request reuses the pooled
+    /// connection. With `prime_sources_catalog`, also fills the
+    /// [`sources_catalog`](Self::sources_catalog) cache.
+    ///
+    /// The connection is opened with an unauthenticated `HEAD` request to the API root, which
+    /// does not count against the quota. Clients in [`OfflineMode::CacheOnly`] do nothing.
+    pub async fn warm_up(&self, prime_sources_catalog: bool) -> Result<(), ApiClientError> {
+        if self.get_offline_mode() == OfflineMode::CacheOnly {
+            return Ok(());
+        }
+        self.client.send(self.warm_up_request()).await?;
+        if prime_sources_catalog {
+            self.sources_catalog().await?;
+        }
+        Ok(())
+    }
+
+    /// Checks that the API is reachable and accepts the key, for startup checks and readiness
+    /// probes. Sends a single sources request, which takes no paging parameters, without
+    /// retries and without falling back to the cache.
+    ///
+    /// The request counts against the quota. Clients in [`OfflineMode::CacheOnly`] report
+    /// [`Health::NetworkDown`] without sending it.
+    pub async fn ping(&self) -> Health {
+        if self.get_offline_mode() == OfflineMode::CacheOnly {
+            return Health::NetworkDown;
+        }
+        let request = GetSourcesRequest::builder().build();
+        let url = self.sources_url(&request);
+        let target = RequestTarget {
+            endpoint: Endpoint::Sources,
+            url: &url,
+            cache_key: &request.cache_key(),
+        };
+        Health::from_result(self.send(target, None, None).await)
+    }
+
+    /// Runs two everything searches and compares their results.
+    ///
+    /// Requests without an end date are pinned to the same end date so that articles
+    /// published between the two calls do not skew the comparison.
+    pub async fn compare(
+        &self,
+        request_a: &GetEverythingRequest,
+        request_b: &GetEverythingRequest,
+    ) -> Result<QueryComparison, ApiClientError> {
+        let pinned = self.clock.now_utc();
+        let pin = |request: &GetEverythingRequest| match request.get_end_date() {
+            Some(_) => request.clone(),
+            None => request.with_end_date(pinned),
+        };
+        let (request_a, request_b) = (pin(request_a), pin(request_b));
+
+        let (response_a, response_b) = tokio::try_join!(
+            self.get_everything(&request_a),
+            self.get_everything(&request_b)
+        )?;
+        Ok(QueryComparison::from_responses(&response_a, &response_b))
+    }
+
+    /// Fetches the top headlines of every country in `region` concurrently, sending `request`
+    /// with its country replaced. Fails with the first error.
+    pub async fn get_top_headlines_for_region(
+        &self,
+        region: &Region,
+        request: &GetTopHeadlinesRequest,
+    ) -> Result<Vec<(Country, TopHeadlinesResponse)>, ApiClientError>
+    where
+        T: Clone + 'static,
+    {
+        let countries = region.get_countries();
+        log::debug!("Fetching top headlines for {}", region.get_name());
+
+        let mut requests = tokio::task::JoinSet::new();
+        for (index, country) in countries.iter().enumerate() {
+            let client = self.clone();
+            let request = request.with_country(country.clone());
+            requests.spawn(async move { (index, client.get_top_headlines(&request).await) });
+        }
+
+        let mut responses: Vec<Option<TopHeadlinesResponse>> =
+            countries.iter().map(|_| None).collect();
+        while let Some(joined) = requests.join_next().await {
+            let (index, response) = join_output(joined)?;
+            responses[index] = Some(response?);
+        }
+
+        Ok(countries
+            .into_iter()
+            .zip(responses.into_iter().flatten())
+            .collect())
+    }
+
+    /// Returns a paginator over the pages of an everything search.
+    pub fn everything_pages(&self, request: &GetEverythingRequest) -> EverythingPaginator<'_, T> {
+        EverythingPaginator::new(self, request)
+    }
+
+    /// Collects up to `target` articles from the start of an everything search's result set,
+    /// in the fewest requests the remaining [`QuotaBudget`] allows, see [`PagePlan`]. The
+    /// request's page and page size are replaced by the plan's.
+    pub async fn collect_everything(
+        &self,
+        request: &GetEverythingRequest,
+        target: usize,
+    ) -> Result<Vec<Article>, ApiClientError> {
+        let plan = PagePlan::for_target(target, self.remaining_quota());
+        log::debug!("Collecting {target} articles with {plan:?}");
+        let mut paginator = self.everything_pages(&plan.apply(request));
+        let mut articles = Vec::with_capacity(plan.capacity());
+        for _ in 0..plan.get_pages() {
+            match paginator.next_page().await {
+                Some(page) => articles.extend(page?),
+                None => break,
+            }
+        }
+        articles.truncate(target);
+        Ok(articles)
+    }
+
+    pub fn with_retry(mut self, strategy: RetryStrategy, max_retries: usize) -> Self {
+        self.detach_config(|config| config.retry(strategy, max_retries));
+        self
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl NewsApiClient<reqwest::blocking::Client> {
+    pub fn from_env_blocking() -> Self {
+        match env::var(NEWS_API_KEY_ENV) {
+            Ok(api_key) => NewsApiClientBuilder::new()
+                .build_client(reqwest::blocking::Client::new(), api_key.into()),
+            Err(_) => panic!("{NEWS_API_KEY_ENV} is not set"),
+        }
+    }
+
+    /// Iterates over the pages of an everything search, starting at the request's page.
+    pub fn everything_pages_iter(
+        &self,
+        request: &GetEverythingRequest,
+    ) -> BlockingEverythingPaginator {
+        BlockingEverythingPaginator::new(self, request)
+    }
+
+    /// See [`NewsApiClient::collect_everything`].
+    pub fn collect_everything(
+        &self,
+        request: &GetEverythingRequest,
+        target: usize,
+    ) -> Result<Vec<Article>, ApiClientError> {
+        let plan = PagePlan::for_target(target, self.remaining_quota());
+        log::debug!("Collecting {target} articles with {plan:?}");
+        let mut articles = Vec::with_capacity(plan.capacity());
+        for page in self
+            .everything_pages_iter(&plan.apply(request))
+            .take(plan.get_pages())
+        {
+            articles.extend(page?);
+        }
+        articles.truncate(target);
+        Ok(articles)
+    }
+}
+
+impl<T> NewsApiClient<T> {
+    /// Sends `request_id` as the correlation ID of every call made through this client, e.g.
+    /// the ID of the incoming request being served, in the
+    /// [`request_id_header`](NewsApiClientBuilder::request_id_header) or `x-request-id`.
+    /// Clone the client first to keep the original's IDs.
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// The correlation ID for a new call: the one supplied with
+    /// [`with_request_id`](Self::with_request_id), or a generated one if IDs are enabled.
+    fn next_request_id(&self) -> Option<String> {
+        match (&self.request_id, &self.request_id_header) {
+            (Some(request_id), _) => Some(request_id.clone()),
+            (None, Some(_)) => Some(format!("{:016x}", rand::random::<u64>())),
+            (None, None) => None,
+        }
+    }
+
+    fn request_id_header(&self) -> HeaderName {
+        self.request_id_header
+            .clone()
+            .unwrap_or(HeaderName::from_static(NEWS_API_REQUEST_ID_HEADER))
+    }
+
+    /// Returns the quota state shared by all clones of this client.
+    pub fn quota_status(&self) -> QuotaStatus {
+        self.quota.status(self.clock.now_utc())
+    }
+
+    /// Requests left in the [`QuotaBudget`]. A spent budget still allows one, so that it
+    /// fails with [`ApiClientError::QuotaExceeded`] rather than returning nothing.
+    fn remaining_quota(&self) -> Option<u32> {
+        self.quota_status()
+            .get_remaining()
+            .map(|remaining| remaining.max(1))
+    }
+
+    /// The client's current [`RuntimeConfig`].
+    pub fn get_config(&self) -> RuntimeConfig {
+        self.config.load().config.clone()
+    }
+
+    /// Changes retry, rate limit and page size settings of this client and all its clones
+    /// while they are in use, e.g. when a service's configuration is updated. Calls already
+    /// in progress finish with the previous settings. The rate limiter is only replaced, and
+    /// its state reset, when the rate limit changes. Setting a rate limit, or removing it,
+    /// also replaces a `governor` limiter given to the builder.
+    ///
+    /// ```no_run
+    /// # use newsapi_rs::{NewsApiClient, RetryStrategy};
+    /// # use std::time::Duration;
+    /// # let client = NewsApiClient::new("key");
+    /// client
+    ///     .reconfigure(|config| {
+    ///         config
+    ///             .retry(RetryStrategy::Constant(Duration::from_secs(1)), 5)
+    ///             .rate_limit(30, Duration::from_secs(60))
+    ///     })
+    ///     .expect("valid configuration");
+    /// ```
+    pub fn reconfigure(
+        &self,
+        update: impl FnOnce(RuntimeConfig) -> RuntimeConfig,
+    ) -> Result<(), ClientBuildError> {
+        self.config.update(update, &self.clock)
+    }
+
+    /// The URL the client would call for `request`, after applying its defaults and page
+    /// size policy, without sending anything. With [`AuthMode::QueryParameter`] it ends in
+    /// `apiKey=REDACTED` where the key would be; the other modes send the key in a header.
+    /// Either way the URL holds no API key and is safe to log.
+    ///
+    /// ```
+    /// # use newsapi_rs::model::Country;
+    /// # use newsapi_rs::{GetSourcesRequest, NewsApiClient};
+    /// let client = NewsApiClient::new("key");
+    /// let request = GetSourcesRequest::builder().country(Country::US).build();
+    /// let url = client.preview_url(&request).unwrap();
+    /// assert_eq!(url.as_str(), "https://newsapi.org/v2/top-headlines/sources?country=us");
+    /// ```
+    pub fn preview_url(&self, request: &impl PreviewRequest) -> Result<Url, ApiClientError> {
+        let mut url = request.preview(self)?;
+        if self.auth_mode == AuthMode::QueryParameter {
+            url.query_pairs_mut().append_pair(API_KEY_PARAM, REDACTED);
+        }
+        Ok(url)
+    }
+
+    /// Gives this client its own copy of the configuration with `update` applied, leaving
+    /// clones it was made from unchanged.
+    fn detach_config(&mut self, update: impl FnOnce(RuntimeConfig) -> RuntimeConfig) {
+        let current = self.config.load();
+        self.config = Arc::new(ConfigCell::new(LiveConfig {
+            config: update(current.config.clone()),
+            rate_limiter: current.rate_limiter.clone(),
+        }));
+    }
+
+    /// Records a retry in `retries` and reports it to the `on_retry` callback.
+    fn notify_retry(
+        &self,
+        endpoint: Endpoint,
+        request_id: Option<&str>,
+        retries: &Mutex<Vec<RetryAttempt>>,
+        attempt: usize,
+        error: &ApiClientError,
+        delay: Duration,
+    ) {
+        log::debug!(
+            "Retry {attempt} of {endpoint}{} in {delay:?} after error: {}",
+            RequestIdTag(request_id),
+            self.log_redaction.error(error)
+        );
+        retries
+            .lock()
+            .unwrap()
+            .push(RetryAttempt::new(error, delay));
+        #[cfg(feature = "metrics")]
+        crate::request_metrics::record_retry(endpoint);
+        if let Some(on_retry) = &self.on_retry {
+            on_retry(attempt, error, delay);
+        }
+    }
+
+    fn retry_deadline(&self) -> Option<Instant> {
+        self.max_retry_duration
+            .and_then(|duration| self.clock.now().checked_add(duration))
+    }
+
+    /// Returns the state of the circuit breaker, if one is configured.
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.circuit_breaker.as_ref().map(|breaker| breaker.state())
+    }
+
+    /// Parses a response body from `url`, received with `status` or served from the cache,
+    /// skipping malformed articles if the client is configured to.
+    fn parse_body<R: DeserializeOwned + WithResponseMeta>(
+        &self,
+        url: &Url,
+        status: Option<u16>,
+        response_text: &str,
+    ) -> Result<R, ApiClientError> {
+        if !self.skip_malformed_articles {
+            return Self::parse_response(url, status, response_text);
+        }
+        let mut body: serde_json::Value = Self::parse_response(url, status, response_text)?;
+        let mut warnings = Vec::new();
+        if let Some(articles) = body.get_mut("articles").and_then(|a| a.as_array_mut()) {
+            let mut index = 0;
+            articles.retain(|article| {
+                let result = serde_path_to_error::deserialize::<_, Article>(article);
+                if let Err(e) = &result {
+                    log::warn!("Skipping malformed article {index} at {}", e.path());
+                    warnings.push(ArticleWarning::new(index, e.to_string()));
+                }
+                index += 1;
+                result.is_ok()
+            });
+        }
+        let mut response: R = serde_path_to_error::deserialize(body).map_err(|error| {
+            ApiClientError::ParseResponse {
+                context: ResponseContext::new(url, status, response_text),
+                path: error.path().to_string(),
+                source: error.into_inner(),
+            }
+        })?;
+        response.set_warnings(warnings);
+        Ok(response)
+    }
+
+    /// Parses a response body from `url`, received with `status` or served from the cache.
+    fn parse_response<R: DeserializeOwned>(
+        url: &Url,
+        status: Option<u16>,
+        response_text: &str,
+    ) -> Result<R, ApiClientError> {
+        let mut deserializer = serde_json::Deserializer::from_str(response_text);
+        serde_path_to_error::deserialize(&mut deserializer)
+            .map_err(|error| (error.path().to_string(), error.into_inner()))
+            .and_then(|response| {
+                deserializer
+                    .end()
+                    .map(|()| response)
+                    .map_err(|error| (".".to_string(), error))
+            })
+            .map_err(|(path, source)| ApiClientError::ParseResponse {
+                context: ResponseContext::new(url, status, response_text),
+                path,
+                source,
+            })
+    }
+
+    /// Switches between serving from the API and from the cache only. Applies to all clones
+    /// of the client, e.g. to go offline when the network is known to be down.
+    pub fn set_offline_mode(&self, mode: OfflineMode) {
+        self.offline
+            .store(mode == OfflineMode::CacheOnly, Ordering::Relaxed);
+    }
+
+    pub fn get_offline_mode(&self) -> OfflineMode {
+        if self.offline.load(Ordering::Relaxed) {
+            OfflineMode::CacheOnly
+        } else {
+            OfflineMode::Online
+        }
+    }
+
+    /// Serves a response from the cache without sending a request.
+    fn offline_response<R: DeserializeOwned + WithResponseMeta>(
+        &self,
+        target: RequestTarget<'_>,
+        normalizations: Vec<Normalization>,
+    ) -> Result<ResponseEnvelope<R>, ApiClientError> {
+        let RequestTarget {
+            endpoint,
+            url,
+            cache_key,
+        } = target;
+        let request_key = redact_url(url);
+        let entry = self
+            .cache
+            .get(cache_key)
+            .ok_or_else(|| ApiClientError::Offline(request_key.to_string()))?;
+        log::debug!("Serving cached {endpoint} response while offline");
+        let mut response = self.parse_body::<R>(url, None, &entry.body)?;
+        response.set_meta(
+            ResponseMeta::stale(entry.fetched_at, request_key.as_str())
+                .with_sizes(entry.body.len(), None)
+                .with_normalizations(normalizations),
+        );
+        Ok(ResponseEnvelope::new(response, 0, None, HeaderMap::new()).with_raw(entry.body))
+    }
+
+    fn cache_policy(&self, endpoint: Endpoint) -> CachePolicy {
+        self.cache_policies
+            .get(&endpoint)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The cached response to revalidate with a conditional request, if `endpoint` uses
+    /// [`CachePolicy::Revalidate`].
+    fn revalidation_entry(&self, endpoint: Endpoint, cache_key: &str) -> Option<CacheEntry> {
+        if self.cache_policy(endpoint) != CachePolicy::Revalidate {
+            return None;
+        }
+        self.cache
+            .get(cache_key)
+            .filter(|entry| !entry.validators.is_empty())
+    }
+
+    /// Caches a response that can be revalidated later.
+    fn store_validated(
+        &self,
+        endpoint: Endpoint,
+        cache_key: &str,
+        body: &str,
+        validators: Validators,
+    ) {
+        if self.cache_policy(endpoint) == CachePolicy::Revalidate && !validators.is_empty() {
+            self.cache.insert(
+                cache_key,
+                body.to_string(),
+                self.clock.now_utc(),
+                validators,
+            );
+        }
+    }
+
+    /// Reads the body of a response the transport received, answering `304 Not Modified`
+    /// with the `cached` body and turning error statuses and error bodies into errors.
+    fn handle_response(
+        &self,
+        target: RequestTarget<'_>,
+        response: HttpResponse,
+        cached: Option<CacheEntry>,
+        request_id: Option<&str>,
+        key_index: usize,
+    ) -> Result<ResponseBody, ApiClientError> {
+        let RequestTarget {
+            endpoint,
+            url,
+            cache_key,
+        } = target;
+        let status = response.get_status();
+        self.log_request(format_args!(
+            "Response status: {status:?}{}",
+            RequestIdTag(request_id)
+        ));
+
+        let headers = select_headers(response.get_headers(), &self.request_id_header());
+        if let (StatusCode::NOT_MODIFIED, Some(entry)) = (status, cached) {
+            Ok(ResponseBody::revalidated(entry.body, headers))
+        } else if status.is_success() && !reports_error(response.get_body()) {
+            let validators = Validators::from_headers(response.get_headers());
+            let content_length = response.content_length();
+            let body = response.into_text();
+            self.store_validated(endpoint, cache_key, &body, validators);
+            Ok(ResponseBody::received(
+                body,
+                content_length,
+                status,
+                headers,
+            ))
+        } else {
+            Err(self.handle_error_response(url, response, request_id, key_index))
+        }
+    }
+
+    /// Parses a successful response body, or falls back to the cache when the endpoint's
+    /// policy allows serving stale data for this error.
+    fn complete_response<R: DeserializeOwned + WithResponseMeta>(
+        &self,
+        target: RequestTarget<'_>,
+        result: Result<ResponseBody, ApiClientError>,
+        retries: Vec<RetryAttempt>,
+        request_id: Option<String>,
+        normalizations: Vec<Normalization>,
+    ) -> Result<ResponseEnvelope<R>, ApiClientError> {
+        let RequestTarget {
+            endpoint,
+            url,
+            cache_key,
+        } = target;
+        let policy = self.cache_policy(endpoint);
+        let request_key = redact_url(url);
+        let attempts = retries.len() + 1;
+        match result {
+            Ok(body) => {
+                let fetched_at = self.clock.now_utc();
+                let mut response =
+                    self.parse_body::<R>(url, Some(body.status.as_u16()), &body.text)?;
+                response.set_meta(
+                    ResponseMeta::fresh(fetched_at, request_key.as_str())
+                        .with_sizes(body.text.len(), Some(body.transfer_size))
+                        .with_retries(retries)
+                        .with_request_id(request_id)
+                        .with_normalizations(normalizations),
+                );
+                if policy == CachePolicy::StaleIfError {
+                    self.cache.insert(
+                        cache_key,
+                        body.text.clone(),
+                        fetched_at,
+                        Validators::default(),
+                    );
+                }
+                Ok(
+                    ResponseEnvelope::new(response, attempts, Some(body.status), body.headers)
+                        .with_raw(body.text),
+                )
+            }
+            Err(e)
+                if policy == CachePolicy::StaleIfError
+                    && self.strictness == Strictness::Lenient
+                    && is_unavailable(&e) =>
+            {
+                match self.cache.get(cache_key) {
+                    Some(entry) => {
+                        log::warn!(
+                            "Serving stale {endpoint} response after error: {}",
+                            self.log_redaction.error(&e)
+                        );
+                        let mut response = self.parse_body::<R>(url, None, &entry.body)?;
+                        response.set_meta(
+                            ResponseMeta::stale(entry.fetched_at, request_key.as_str())
+                                .with_sizes(entry.body.len(), None)
+                                .with_retries(retries)
+                                .with_request_id(request_id)
+                                .with_normalizations(normalizations),
+                        );
+                        Ok(
+                            ResponseEnvelope::new(response, attempts, None, HeaderMap::new())
+                                .with_raw(entry.body),
+                        )
+                    }
+                    None => Err(e),
+                }
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn get_retry_after(&self, headers: &HeaderMap) -> Option<DateTime<Utc>> {
+        headers
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| parse_retry_after(value, self.clock.now_utc()))
+    }
+
+    /// Converts an error response with the [`error_mapper`](NewsApiClientBuilder::error_mapper)
+    /// or [`api_error`](Self::api_error). An exhausted or rate-limited key cools down when
+    /// there are backup keys, otherwise the client pauses when the quota reset time is known.
+    fn handle_error_response(
+        &self,
+        url: &Url,
+        response: HttpResponse,
+        request_id: Option<&str>,
+        key_index: usize,
+    ) -> ApiClientError {
+        let status = response.get_status();
+        let headers = response.get_headers().clone();
+        let response_text = response.into_text();
+        let mapped = self
+            .error_mapper
+            .as_ref()
+            .and_then(|mapper| mapper(status, &headers, &response_text));
+        let error = match mapped {
+            Some(error) => error,
+            None => self.api_error(url, status, &headers, response_text, request_id),
+        };
+        if keys::is_key_exhausted(&error) {
+            let retry_after = self.get_retry_after(&headers);
+            if self.keys.has_backups() {
+                self.keys
+                    .cool_down(key_index, retry_after, self.clock.now_utc());
+            } else if let Some(until) = retry_after {
+                self.quota.pause_until(until);
+            }
+        }
+        error
+    }
+
+    /// Decodes an error response from the API, `429 Too Many Requests` into
+    /// [`ApiClientError::RateLimited`].
+    fn api_error(
+        &self,
+        url: &Url,
+        status: StatusCode,
+        headers: &HeaderMap,
+        response_text: String,
+        request_id: Option<&str>,
+    ) -> ApiClientError {
+        let context = ResponseContext::new(url, Some(status.as_u16()), &response_text);
+        let mut error = Self::parse_error_response_internal(response_text, status.as_u16());
+        if let ApiClientError::InvalidResponse(response) = &mut error {
+            response.request_id = request_id.map(str::to_string);
+            response.context = Some(Box::new(context));
+        }
+        match (status, error) {
+            (StatusCode::TOO_MANY_REQUESTS, ApiClientError::InvalidResponse(response)) => {
+                ApiClientError::RateLimited {
+                    retry_after: self.get_retry_after(headers).map(|until| {
+                        (until - self.clock.now_utc())
+                            .to_std()
+                            .unwrap_or(Duration::ZERO)
+                    }),
+                    remaining: headers
+                        .get(RATE_LIMIT_REMAINING_HEADER)
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(|value| value.trim().parse().ok()),
+                    response: Box::new(response),
+                }
+            }
+            (_, error) => error,
+        }
+    }
+
+    /// Logs per-request details at debug level, unless they are aggregated into summaries.
+    fn log_request(&self, message: fmt::Arguments<'_>) {
+        if self.request_log.is_none() {
+            log::debug!("{message}");
+        }
+    }
+
+    /// Echoes a request in the logs, unless its parameters are redacted.
+    fn log_request_details(&self, request: &impl fmt::Debug) {
+        if self.log_redaction.logs_requests() {
+            self.log_request(format_args!("Request: {request:?}"));
+        }
+    }
+
+    fn record_request(
+        &self,
+        endpoint: Endpoint,
+        started: Instant,
+        result: &Result<ResponseBody, ApiClientError>,
+    ) {
+        let now = self.clock.now();
+        let latency = now.saturating_duration_since(started);
+        if let Some(request_log) = &self.request_log {
+            request_log.record(endpoint, latency, result.is_ok(), now);
+        }
+        #[cfg(feature = "metrics")]
+        crate::request_metrics::record_request(endpoint, latency, result.as_ref().err());
+        if let Some(on_request) = &self.on_request {
+            on_request(endpoint, latency, result.as_ref().err());
+        }
+    }
+
+    /// Whether `result` failed on an exhausted key and another key is available.
+    fn should_fail_over(&self, result: &Result<ResponseBody, ApiClientError>) -> bool {
+        match result {
+            Err(e) if keys::is_key_exhausted(e) => {
+                let available = self.keys.active(self.clock.now_utc()).is_ok();
+                if available {
+                    log::warn!(
+                        "Failing over to a backup API key after error: {}",
+                        self.log_redaction.error(e)
+                    );
+                }
+                available
+            }
+            _ => false,
+        }
+    }
+
+    fn parse_error_response_internal(response_text: String, status_code: u16) -> ApiClientError {
+        match serde_json::from_str::<NewsApiErrorResponse>(&response_text) {
+            Ok(error_response) => {
+                let error_code = match error_response.code.as_deref() {
+                    Some(code) => ApiClientErrorCode::from(code),
+                    // Check for rate limiting based on status code
+                    None if status_code == 429 => ApiClientErrorCode::RateLimited,
+                    None => ApiClientErrorCode::UnexpectedError,
+                };
+
+                ApiClientError::InvalidResponse(ApiClientErrorResponse {
+                    status: error_response.status,
+                    code: error_code,
+                    message: error_response
+                        .message
+                        .unwrap_or_else(|| "Unknown error".to_string()),
+                    request_id: None,
+                    context: None,
+                })
+            }
+            Err(_) => {
+                let error_code = if status_code == 429 {
+                    ApiClientErrorCode::RateLimited
+                } else {
+                    ApiClientErrorCode::UnexpectedError
+                };
+
+                ApiClientError::InvalidResponse(ApiClientErrorResponse {
+                    status: ResponseStatus::Error,
+                    code: error_code,
+                    message: if response_text.contains("too many requests")
+                        || response_text.contains("rate limit")
+                    {
+                        "You have made too many requests. Rate limit exceeded.".to_string()
+                    } else {
+                        "Failed to parse error response".to_string()
+                    },
+                    request_id: None,
+                    context: None,
+                })
+            }
+        }
+    }
+
+    fn get_request_headers(&self, api_key: &SecretString) -> Result<HeaderMap, ApiClientError> {
+        let mut headers = self.default_headers.clone();
+        self.auth_mode.apply(&mut headers, api_key)?;
+        headers.insert(USER_AGENT, self.user_agent.clone());
+        Ok(headers)
+    }
+
+    fn top_headlines_validate_request(
+        request: &GetTopHeadlinesRequest,
+    ) -> Result<(), ApiClientError> {
+        log::debug!("Validating request");
+        if request.get_sources().is_some()
+            && (request.get_country().is_some() || request.get_category().is_some())
+        {
+            return Err(ApiClientError::InvalidRequest(
+                "Cannot specify sources with country or category".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn everything_url(
+        &self,
+        request: &GetEverythingRequest,
+        normalizations: &mut Vec<Normalization>,
+    ) -> Result<(GetEverythingRequest, Url), ApiClientError> {
+        let request = self.prepare_everything_request(request, normalizations)?;
+        let mut url = self.endpoint_url(Endpoint::Everything);
+        Self::get_endpoint_with_query_params_for_everything(&mut url, &request);
+        Ok((request, url))
+    }
+
+    fn top_headlines_url(
+        &self,
+        request: &GetTopHeadlinesRequest,
+        normalizations: &mut Vec<Normalization>,
+    ) -> Result<(GetTopHeadlinesRequest, Url), ApiClientError> {
+        Self::top_headlines_validate_request(request)?;
+        let request = self.prepare_top_headlines_request(request, normalizations)?;
+        let mut url = self.endpoint_url(Endpoint::TopHeadlines);
+        Self::get_endpoint_with_query_params_for_top_headlines(&mut url, &request);
+        Ok((request, url))
+    }
+
+    fn sources_url(&self, request: &GetSourcesRequest) -> Url {
+        let mut url = self.endpoint_url(Endpoint::Sources);
+        Self::get_endpoint_with_query_params_for_sources(&mut url, request);
+        url
+    }
+
+    /// Applies the client's default sort order and page size policy.
+    fn prepare_everything_request(
+        &self,
+        request: &GetEverythingRequest,
+        normalizations: &mut Vec<Normalization>,
+    ) -> Result<GetEverythingRequest, ApiClientError> {
+        request.get_date_range().checked()?;
+        let page_size = self.checked_page_size(*request.get_page_size(), normalizations)?;
+        let request = request.with_page_size(page_size);
+        match (&self.default_sort_by, request.get_sort_by()) {
+            (Some(sort_by), None) => {
+                normalizations.push(Normalization::DefaultSortBy(sort_by.to_string()));
+                Ok(request.with_sort_by(sort_by))
+            }
+            _ => Ok(request),
+        }
+    }
+
+    /// Applies the client's page size policy.
+    fn prepare_top_headlines_request(
+        &self,
+        request: &GetTopHeadlinesRequest,
+        normalizations: &mut Vec<Normalization>,
+    ) -> Result<GetTopHeadlinesRequest, ApiClientError> {
+        let page_size = self.checked_page_size(*request.get_page_size(), normalizations)?;
+        Ok(request.with_page_size(page_size))
+    }
+
+    /// Applies the default page size to an unset one, then clamps or rejects a page size
+    /// outside the API's range. Zero without a default leaves it unset.
+    fn checked_page_size(
+        &self,
+        page_size: i32,
+        normalizations: &mut Vec<Normalization>,
+    ) -> Result<i32, ApiClientError> {
+        let page_size = match (page_size, self.config.load().config.get_default_page_size()) {
+            (0, Some(default)) => {
+                normalizations.push(Normalization::DefaultPageSize(default));
+                default
+            }
+            _ => page_size,
+        };
+        if page_size == 0 || (1..=MAX_PAGE_SIZE).contains(&page_size) {
+            return Ok(page_size);
+        }
+
+        match (self.strictness, self.page_size_policy) {
+            (Strictness::Lenient, PageSizePolicy::Clamp) => {
+                let clamped = page_size.clamp(1, MAX_PAGE_SIZE);
+                log::warn!("Clamping pageSize {page_size} to {clamped}");
+                normalizations.push(Normalization::PageSizeClamped {
+                    from: page_size,
+                    to: clamped,
+                });
+                Ok(clamped)
+            }
+            _ => Err(ApiClientError::InvalidRequest(format!(
+                "pageSize must be between 1 and {MAX_PAGE_SIZE}, got {page_size}"
+            ))),
+        }
+    }
+
+    /// Timeout of one attempt at `endpoint`, shortened to end at the retry deadline.
+    fn request_timeout(&self, endpoint: Endpoint, deadline: Option<Instant>) -> Option<Duration> {
+        let timeout = self
+            .endpoint_timeouts
+            .get(&endpoint)
+            .copied()
+            .or(self.timeout);
+        let remaining =
+            deadline.map(|deadline| deadline.saturating_duration_since(self.clock.now()));
+        match (timeout, remaining) {
+            (Some(timeout), Some(remaining)) => Some(timeout.min(remaining)),
+            (timeout, remaining) => timeout.or(remaining),
+        }
+    }
+
+    /// Unauthenticated `HEAD` request to the API root for [`warm_up`](NewsApiClient::warm_up).
+    fn warm_up_request(&self) -> HttpRequest {
+        let mut url = self.base_url.clone();
+        url.set_path("/");
+        let mut headers = self.default_headers.clone();
+        headers.insert(USER_AGENT, self.user_agent.clone());
+        HttpRequest::new(Method::HEAD, url).with_headers(headers)
+    }
+
+    fn endpoint_url(&self, endpoint: Endpoint) -> Url {
+        let mut url = self.base_url.clone();
+        url.set_path(&self.api_version.path(endpoint));
+        url
+    }
+
+    fn get_endpoint_with_query_params_for_top_headlines(
+        url: &mut Url,
+        request: &GetTopHeadlinesRequest,
+    ) {
+        url.query_pairs_mut().clear();
+
+        for (key, value) in Self::get_top_headlines_query_params(request) {
+            url.query_pairs_mut().append_pair(&key, &value);
+        }
+
+        url.query_pairs_mut().finish();
+    }
+
+    fn get_top_headlines_query_params(request: &GetTopHeadlinesRequest) -> Vec<(String, String)> {
+        let mut query_params = Vec::new();
+
+        if let Some(country) = request.get_country() {
+            query_params.push(("country".to_string(), country.to_string()));
+        }
+
+        if let Some(category) = request.get_category() {
+            query_params.push(("category".to_string(), category.to_string()));
+        }
+
+        if let Some(sources) = request.get_sources() {
+            query_params.push(("sources".to_string(), sources.to_string()));
+        }
+
+        if !request.get_search_term().is_empty() {
+            query_params.push(("q".to_string(), request.get_search_term().to_string()));
+        }
+
+        if *request.get_page_size() > 1 {
+            query_params.push(("pageSize".to_string(), request.get_page_size().to_string()));
+        }
+
+        if *request.get_page() > 1 {
+            query_params.push(("page".to_string(), request.get_page().to_string()));
+        }
+
+        query_params
+    }
+
+    fn get_endpoint_with_query_params_for_everything(
+        url: &mut Url,
+        request: &GetEverythingRequest,
+    ) {
+        url.query_pairs_mut().clear();
+
+        let query_params = Self::get_everything_query_params(request);
+        for (key, value) in query_params {
+            url.query_pairs_mut().append_pair(&key, &value);
+        }
+
+        url.query_pairs_mut().finish();
+    }
+
+    fn get_everything_query_params(request: &GetEverythingRequest) -> Vec<(String, String)> {
+        let mut query_params = Vec::new();
+
+        query_params.push(("q".to_string(), request.get_search_term().to_string()));
+
+        if let Some(language) = request.get_language() {
+            query_params.push(("language".to_string(), language.to_string().to_lowercase()));
+        }
+
+        if let Some(start_date) = request.get_start_date() {
+            query_params.push(("from".to_string(), start_date.to_rfc3339()));
+        }
+
+        if let Some(end_date) = request.get_end_date() {
+            query_params.push(("to".to_string(), end_date.to_rfc3339()));
+        }
+
+        if let Some(sort_by) = request.get_sort_by() {
+            query_params.push(("sortBy".to_string(), sort_by.to_string()));
+        }
+
+        if *request.get_page_size() > 0 {
+            query_params.push(("pageSize".to_string(), request.get_page_size().to_string()));
+        }
+
+        if *request.get_page() > 1 {
+            query_params.push(("page".to_string(), request.get_page().to_string()));
+        }
+
+        query_params
+    }
+
+    fn get_endpoint_with_query_params_for_sources(url: &mut Url, request: &GetSourcesRequest) {
+        url.query_pairs_mut().clear();
+
+        let query_params = Self::get_sources_query_params(request);
+        for (key, value) in query_params {
+            url.query_pairs_mut().append_pair(&key, &value);
+        }
+
+        url.query_pairs_mut().finish();
+    }
+
+    fn get_sources_query_params(request: &GetSourcesRequest) -> Vec<(String, String)> {
+        let mut query_params = Vec::new();
+
+        if let Some(category) = request.get_category() {
+            query_params.push(("category".to_string(), category.to_string()));
+        }
+
+        if let Some(language) = request.get_language() {
+            query_params.push(("language".to_string(), language.to_string().to_lowercase()));
+        }
+
+        if let Some(country) = request.get_country() {
+            query_params.push(("country".to_string(), country.to_string()));
+        }
+
+        query_params
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Request types accepted by [`NewsApiClient::preview_url`].
+pub trait PreviewRequest: sealed::Sealed {
+    #[doc(hidden)]
+    fn preview<T>(&self, client: &NewsApiClient<T>) -> Result<Url, ApiClientError>;
+}
+
+impl sealed::Sealed for GetEverythingRequest {}
+
+impl PreviewRequest for GetEverythingRequest {
+    fn preview<T>(&self, client: &NewsApiClient<T>) -> Result<Url, ApiClientError> {
+        Ok(client.everything_url(self, &mut Vec::new())?.1)
+    }
+}
+
+impl sealed::Sealed for GetTopHeadlinesRequest {}
+
+impl PreviewRequest for GetTopHeadlinesRequest {
+    fn preview<T>(&self, client: &NewsApiClient<T>) -> Result<Url, ApiClientError> {
+        Ok(client.top_headlines_url(self, &mut Vec::new())?.1)
+    }
+}
+
+impl sealed::Sealed for GetSourcesRequest {}
+
+impl PreviewRequest for GetSourcesRequest {
+    fn preview<T>(&self, client: &NewsApiClient<T>) -> Result<Url, ApiClientError> {
+        Ok(client.sources_url(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualClock;
+    use crate::model::{Country, Language, NewsCategory, SearchInOption};
+    use crate::sleep::InstantSleeper;
+    use chrono::{DateTime, TimeZone, Utc};
+    use mockito;
+    use reqwest::header::AUTHORIZATION;
+    use serial_test::serial;
+    use std::collections::HashMap;
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    fn create_test_client() -> NewsApiClient<reqwest::Client> {
+        let api_key = "test-api-key";
+        let mut client = NewsApiClient::new(api_key);
+        let server = mockito::Server::new();
+        let mock_url = server.url();
+        client.base_url = Url::parse(&format!("http://{}", mock_url)).unwrap();
+        client
+    }
+
+    #[test]
+    fn test_parse_error_response() {
+        let error_json =
+            r#"{"status":"error","code":"apiKeyInvalid","message":"Your API key is invalid"}"#;
+        let error = NewsApiClient::<reqwest::Client>::parse_error_response_internal(
+            error_json.to_string(),
+            400,
+        );
+
+        match error {
+            ApiClientError::InvalidResponse(response) => {
+                assert_eq!(response.status, ResponseStatus::Error);
+                assert_eq!(response.code, ApiClientErrorCode::ApiKeyInvalid);
+                assert_eq!(response.message, "Your API key is invalid");
+            }
+            _ => panic!("Expected InvalidResponse error"),
+        }
+
+        let error_json =
+            r#"{"status":"error","code":"parameterInvalid","message":"Invalid parameter"}"#;
+        let error = NewsApiClient::<reqwest::Client>::parse_error_response_internal(
+            error_json.to_string(),
+            400,
+        );
+
+        match error {
+            ApiClientError::InvalidResponse(response) => {
+                assert_eq!(response.code, ApiClientErrorCode::ParameterInvalid);
+            }
+            _ => panic!("Expected InvalidResponse error"),
+        }
+
+        let error_json = r#"invalid json"#;
+        let error = NewsApiClient::<reqwest::Client>::parse_error_response_internal(
+            error_json.to_string(),
+            400,
+        );
+
+        match error {
+            ApiClientError::InvalidResponse(response) => {
+                assert_eq!(response.code, ApiClientErrorCode::UnexpectedError);
+            }
+            _ => panic!("Expected InvalidResponse error"),
+        }
+    }
+
+    #[test]
+    fn test_get_request_headers() {
+        let client = create_test_client();
+        let headers = client
+            .get_request_headers(&SecretString::from(client.keys.primary()))
+            .unwrap();
+
+        assert_eq!(
+            headers.get(AUTHORIZATION).unwrap().to_str().unwrap(),
+            "Bearer test-api-key"
+        );
+        assert_eq!(
+            headers.get(USER_AGENT).unwrap().to_str().unwrap(),
+            NEWS_API_CLIENT_USER_AGENT
+        );
+    }
+
+    #[test]
+    fn test_user_agent_includes_crate_version() {
+        assert_eq!(
+            NEWS_API_CLIENT_USER_AGENT,
+            format!("newsapi-rs/{}", env!("CARGO_PKG_VERSION"))
+        );
+    }
+
+    #[test]
+    fn test_default_headers() {
+        let mut gateway = HeaderMap::new();
+        gateway.insert("x-gateway-token", HeaderValue::from_static("gateway-token"));
+        gateway.insert(AUTHORIZATION, HeaderValue::from_static("Bearer other-key"));
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .default_header(
+                HeaderName::from_static("traceparent"),
+                HeaderValue::from_static("00-trace-span-01"),
+            )
+            .default_headers(gateway)
+            .build()
+            .unwrap();
+        let headers = client
+            .get_request_headers(&SecretString::from(client.keys.primary()))
+            .unwrap();
+
+        assert_eq!(headers.get("traceparent").unwrap(), "00-trace-span-01");
+        assert_eq!(headers.get("x-gateway-token").unwrap(), "gateway-token");
+        assert_eq!(headers.get(AUTHORIZATION).unwrap(), "Bearer test-api-key");
+        assert_eq!(headers.get_all(AUTHORIZATION).iter().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_with_http_client_async() {
+        let mut server = mockito::Server::new_async().await;
+        let m = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .match_header("x-proxy-authorization", "proxy-token")
+            .match_header("authorization", "Bearer test-api-key")
+            .with_status(200)
+            .with_body(r#"{"status":"ok","sources":[]}"#)
+            .create_async()
+            .await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-proxy-authorization",
+            HeaderValue::from_static("proxy-token"),
+        );
+        let http_client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .unwrap();
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .with_http_client(http_client)
+            .build()
+            .unwrap();
+
+        let result = client
+            .get_sources(&GetSourcesRequest::builder().build())
+            .await;
+
+        assert!(result.is_ok());
+        m.assert_async().await;
+    }
+
+    /// Answers every request with a canned body, recording the requests.
+    #[derive(Debug, Default)]
+    struct FakeTransport {
+        requests: Mutex<Vec<HttpRequest>>,
+    }
+
+    impl HttpTransport for FakeTransport {
+        fn send(
+            &self,
+            request: HttpRequest,
+        ) -> std::pin::Pin<
+            Box<dyn std::future::Future<Output = Result<HttpResponse, ApiClientError>> + Send + '_>,
+        > {
+            self.requests.lock().unwrap().push(request);
+            let body = br#"{"status":"ok","sources":[]}"#.to_vec();
+            Box::pin(async move { Ok(HttpResponse::new(StatusCode::OK, HeaderMap::new(), body)) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_build_with_transport() {
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .default_header(
+                HeaderName::from_static("x-trace"),
+                HeaderValue::from_static("abc"),
+            )
+            .build_with_transport(FakeTransport::default())
+            .unwrap();
+
+        let result = client
+            .get_sources(&GetSourcesRequest::builder().build())
+            .await;
+
+        assert!(result.is_ok());
+        let requests = client.client.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].get_method(), Method::GET);
+        assert!(requests[0]
+            .get_url()
+            .starts_with("https://newsapi.org/v2/top-headlines/sources"));
+        assert_eq!(requests[0].get_headers()["x-trace"], "abc");
+        assert_eq!(
+            requests[0].get_headers()[AUTHORIZATION],
+            "Bearer test-api-key"
+        );
+    }
+
+    #[test]
+    fn test_build_with_transport_rejects_http_settings() {
+        let result = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .pool_max_idle_per_host(1)
+            .build_with_transport(FakeTransport::default());
+
+        assert!(matches!(
+            result,
+            Err(ClientBuildError::InvalidSetting { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_connection_pool_options_async() {
+        let mut server = mockito::Server::new_async().await;
+        let m = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"status":"ok","sources":[]}"#)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .pool_max_idle_per_host(4)
+            .pool_idle_timeout(Duration::from_secs(300))
+            .http2_keep_alive_interval(Duration::from_secs(30))
+            .http2_keep_alive_timeout(Duration::from_secs(10))
+            .http2_keep_alive_while_idle(true)
+            .build()
+            .unwrap();
+        let result = client
+            .get_sources(&GetSourcesRequest::builder().build())
+            .await;
+        assert!(result.is_ok());
+        m.assert_async().await;
+
+        assert!(matches!(
+            NewsApiClient::builder()
+                .api_key("test-api-key")
+                .pool_max_idle_per_host(4)
+                .with_http_client(reqwest::Client::new())
+                .build(),
+            Err(ClientBuildError::InvalidSetting { .. })
+        ));
+    }
+
+    #[cfg(feature = "__tls")]
+    #[test]
+    fn test_tls_options() {
+        assert!(NewsApiClient::builder()
+            .api_key("test-api-key")
+            .danger_accept_invalid_certs(true)
+            .build()
+            .is_ok());
+
+        assert!(matches!(
+            NewsApiClient::builder()
+                .api_key("test-api-key")
+                .danger_accept_invalid_certs(true)
+                .with_http_client(reqwest::Client::new())
+                .build(),
+            Err(ClientBuildError::InvalidSetting { .. })
+        ));
+    }
+
+    #[test]
+    fn test_custom_user_agent() {
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .user_agent("my-app/1.2")
+            .build()
+            .unwrap();
+        let headers = client
+            .get_request_headers(&SecretString::from(client.keys.primary()))
+            .unwrap();
+
+        assert_eq!(
+            headers.get(USER_AGENT).unwrap(),
+            &format!("my-app/1.2 {NEWS_API_CLIENT_USER_AGENT}")
+        );
+        assert!(matches!(
+            NewsApiClient::builder()
+                .api_key("test-api-key")
+                .user_agent("my-app\n")
+                .build(),
+            Err(ClientBuildError::InvalidSetting { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_api_key_header_auth_mode() {
+        let mut server = mockito::Server::new_async().await;
+        let _m = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .match_header("x-api-key", "test-api-key")
+            .match_header("authorization", mockito::Matcher::Missing)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"status": "ok", "sources": []}"#)
+            .create_async()
+            .await;
+
+        let mut client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .auth_mode(AuthMode::ApiKeyHeader)
+            .build()
+            .unwrap();
+        client.base_url = Url::parse(&server.url()).unwrap();
+
+        let response = client
+            .get_sources(&GetSourcesRequest::builder().build())
+            .await
+            .unwrap();
+        assert!(response.get_sources().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_query_parameter_auth_mode() {
+        let mut server = mockito::Server::new_async().await;
+        let _m = server
+            .mock("GET", "/v2/everything")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("q".into(), "bitcoin".into()),
+                mockito::Matcher::UrlEncoded("apiKey".into(), "test-api-key".into()),
+            ]))
+            .match_header("authorization", mockito::Matcher::Missing)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"status": "ok", "totalResults": 0, "articles": []}"#)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .auth_mode(AuthMode::QueryParameter)
+            .base_url(server.url())
+            .build()
+            .unwrap();
+
+        let request = GetEverythingRequest::builder()
+            .search_term("bitcoin".to_string())
+            .build();
+        let response = client.get_everything(&request).await.unwrap();
+        let request_key = response.get_meta().get_request_key().unwrap();
+        assert!(!request_key.contains("apiKey"));
+    }
+
+    #[test]
+    fn test_top_headlines_validate_request_country_and_category() {
+        let request = GetTopHeadlinesRequest::builder()
+            .country(Country::US)
+            .category(NewsCategory::Business)
+            .search_term(String::new())
+            .page_size(20)
+            .page(1)
+            .build()
+            .unwrap();
+        assert!(NewsApiClient::<reqwest::Client>::top_headlines_validate_request(&request).is_ok());
+    }
+
+    #[test]
+    fn test_top_headlines_validate_request_sources_only() {
+        let request = GetTopHeadlinesRequest::builder()
+            .sources("bbc-news,cnn".to_string())
+            .search_term(String::new())
+            .page_size(20)
+            .page(1)
+            .build()
+            .unwrap();
+        assert!(NewsApiClient::<reqwest::Client>::top_headlines_validate_request(&request).is_ok());
+    }
+
+    #[test]
+    fn test_top_headlines_validate_request_sources_with_country() {
+        let request = GetTopHeadlinesRequest::builder()
+            .sources("bbc-news".to_string())
+            .country(Country::US)
+            .search_term(String::new())
+            .page_size(20)
+            .page(1)
+            .build();
+
+        assert!(request.is_err());
+    }
+
+    #[test]
+    fn test_top_headlines_validate_request_sources_with_category() {
+        let request = GetTopHeadlinesRequest::builder()
             .sources("bbc-news".to_string())
             .category(NewsCategory::Business)
             .search_term(String::new())
@@ -790,191 +3458,1910 @@ mod tests {
             .page(1)
             .build();
 
-        assert!(request.is_err());
+        assert!(request.is_err());
+    }
+
+    #[test]
+    fn test_get_top_headlines_query_params() {
+        let request = GetTopHeadlinesRequest::builder()
+            .country(Country::US)
+            .category(NewsCategory::Technology)
+            .search_term("ai".to_string())
+            .page_size(15)
+            .page(2)
+            .build()
+            .unwrap();
+
+        let params = NewsApiClient::<reqwest::Client>::get_top_headlines_query_params(&request);
+        let params_map: HashMap<_, _> = params.into_iter().collect();
+
+        assert_eq!(params_map.get("country").unwrap(), "us");
+        assert_eq!(params_map.get("category").unwrap(), "technology");
+        assert_eq!(params_map.get("q").unwrap(), "ai");
+        assert_eq!(params_map.get("page").unwrap(), "2");
+        assert_eq!(params_map.get("pageSize").unwrap(), "15");
+    }
+
+    #[test]
+    fn test_get_everything_query_params() {
+        let start_date = DateTime::<Utc>::from_str("2023-01-01T00:00:00Z").unwrap();
+        let end_date = DateTime::<Utc>::from_str("2023-01-31T23:59:59Z").unwrap();
+
+        let request = GetEverythingRequest::builder()
+            .search_term("bitcoin".to_string())
+            .language(Language::AR)
+            .start_date(start_date)
+            .end_date(end_date)
+            .page(3)
+            .page_size(20)
+            .build();
+
+        let params = NewsApiClient::<reqwest::Client>::get_everything_query_params(&request);
+        let params_map: HashMap<_, _> = params.into_iter().collect();
+
+        assert_eq!(params_map.get("q").unwrap(), "bitcoin");
+        assert_eq!(params_map.get("language").unwrap(), "ar"); // Fix expectation to "ar" instead of "en"
+        assert_eq!(params_map.get("from").unwrap(), "2023-01-01T00:00:00+00:00");
+        assert_eq!(params_map.get("to").unwrap(), "2023-01-31T23:59:59+00:00");
+        assert_eq!(params_map.get("page").unwrap(), "3");
+        assert_eq!(params_map.get("pageSize").unwrap(), "20");
+    }
+
+    #[test]
+    fn test_everything_open_date_range() {
+        let client = NewsApiClient::new("test-api-key");
+        let since = DateTime::<Utc>::from_str("2023-01-01T00:00:00Z").unwrap();
+
+        let request = GetEverythingRequest::builder()
+            .search_term("bitcoin".to_string())
+            .since(since)
+            .build();
+        let params: HashMap<_, _> =
+            NewsApiClient::<reqwest::Client>::get_everything_query_params(&request)
+                .into_iter()
+                .collect();
+        assert_eq!(params.get("from").unwrap(), "2023-01-01T00:00:00+00:00");
+        assert!(!params.contains_key("to"));
+
+        let reversed = GetEverythingRequest::builder()
+            .search_term("bitcoin".to_string())
+            .start_date(since)
+            .end_date(since - chrono::Duration::days(1))
+            .build();
+        assert!(matches!(
+            client.prepare_everything_request(&reversed, &mut Vec::new()),
+            Err(ApiClientError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_profiles() {
+        let interactive = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .profile(Profile::Interactive)
+            .build()
+            .unwrap();
+        assert_eq!(interactive.get_config().get_max_retries(), 2);
+        assert_eq!(interactive.timeout, Some(Profile::INTERACTIVE_TIMEOUT));
+        assert_eq!(
+            interactive.max_retry_duration,
+            Some(Profile::INTERACTIVE_DEADLINE)
+        );
+        assert!(interactive.circuit_breaker.is_some());
+        assert_eq!(
+            interactive.cache_policy(Endpoint::Sources),
+            CachePolicy::StaleIfError
+        );
+
+        let low_quota = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .profile(Profile::LowQuota)
+            .retry(RetryStrategy::None, 0)
+            .build()
+            .unwrap();
+        assert_eq!(low_quota.get_config().get_max_retries(), 0);
+        assert_eq!(low_quota.timeout, Some(Profile::LOW_QUOTA_TIMEOUT));
+        assert_eq!(low_quota.get_config().get_retry_on(), RetryOn::NetworkOnly);
+        assert_eq!(
+            low_quota.quota_status().get_limit(),
+            Some(Profile::LOW_QUOTA_DAILY_LIMIT)
+        );
+        assert_eq!(
+            low_quota.cache_policy(Endpoint::Everything),
+            CachePolicy::Revalidate
+        );
+
+        let backfill = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .profile(Profile::BatchBackfill)
+            .build()
+            .unwrap();
+        assert!(backfill.config.load().rate_limiter.is_some());
+        assert!(backfill.concurrency_limiter.is_some());
+        assert_eq!(backfill.timeout, Some(Profile::BACKFILL_TIMEOUT));
+    }
+
+    #[test]
+    fn test_reconfigure_applies_to_clones() {
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .rate_limit(10, Duration::from_secs(60))
+            .build()
+            .unwrap();
+        let clone = client.clone();
+        let detached = client
+            .clone()
+            .with_retry(RetryStrategy::Constant(Duration::from_secs(1)), 1);
+
+        clone
+            .reconfigure(|config| {
+                config
+                    .retry(RetryStrategy::Linear(Duration::from_secs(2)), 4)
+                    .no_rate_limit()
+                    .default_page_size(20)
+            })
+            .unwrap();
+
+        assert_eq!(client.get_config().get_max_retries(), 4);
+        assert!(client.config.load().rate_limiter.is_none());
+        assert_eq!(detached.get_config().get_max_retries(), 1);
+        assert!(detached.config.load().rate_limiter.is_some());
+
+        let mut normalizations = Vec::new();
+        let request = client
+            .prepare_everything_request(
+                &GetEverythingRequest::builder()
+                    .search_term("rust".to_string())
+                    .build(),
+                &mut normalizations,
+            )
+            .unwrap();
+        assert_eq!(*request.get_page_size(), 20);
+        assert_eq!(normalizations, vec![Normalization::DefaultPageSize(20)]);
+
+        assert!(matches!(
+            client.reconfigure(|config| config.rate_limit(0, Duration::from_secs(1))),
+            Err(ClientBuildError::InvalidSetting { .. })
+        ));
+        assert_eq!(client.get_config().get_default_page_size(), Some(20));
+    }
+
+    #[test]
+    fn test_client_debug_redacts_api_key() {
+        let client = NewsApiClient::new("secret-api-key");
+        let debug = format!("{client:?}");
+
+        assert!(!debug.contains("secret-api-key"));
+        assert!(debug.contains("api_key: REDACTED"));
+    }
+
+    #[test]
+    fn test_complete_response_redacts_request_key() {
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .cache_policy(Endpoint::Sources, CachePolicy::StaleIfError)
+            .build()
+            .unwrap();
+        let url = Url::parse("https://newsapi.org/v2/top-headlines/sources?apiKey=secret-api-key")
+            .unwrap();
+
+        let response: GetSourcesResponse = client
+            .complete_response(
+                RequestTarget {
+                    endpoint: Endpoint::Sources,
+                    url: &url,
+                    cache_key: "sources?",
+                },
+                Ok(ResponseBody::received(
+                    r#"{"status": "ok", "sources": []}"#.to_string(),
+                    None,
+                    StatusCode::OK,
+                    HeaderMap::new(),
+                )),
+                Vec::new(),
+                None,
+                Vec::new(),
+            )
+            .unwrap()
+            .into_body();
+
+        let request_key = response.get_meta().get_request_key().unwrap();
+        assert!(!request_key.contains("secret-api-key"));
+        assert!(client.cache.get(url.as_str()).is_none());
+        assert!(client.cache.get("sources?").is_some());
+    }
+
+    #[test]
+    fn test_prepare_everything_request_defaults() {
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .default_sort_by(ArticleSortBy::PublishedAt)
+            .build()
+            .unwrap();
+
+        let request = GetEverythingRequest::builder()
+            .search_term("bitcoin".to_string())
+            .page_size(500)
+            .build();
+        let mut normalizations = Vec::new();
+        let prepared = client
+            .prepare_everything_request(&request, &mut normalizations)
+            .unwrap();
+        assert_eq!(prepared.get_sort_by().as_deref(), Some("publishedAt"));
+        assert_eq!(*prepared.get_page_size(), 100);
+        assert_eq!(
+            normalizations,
+            vec![
+                Normalization::PageSizeClamped { from: 500, to: 100 },
+                Normalization::DefaultSortBy("publishedAt".to_string()),
+            ]
+        );
+
+        let request = GetEverythingRequest::builder()
+            .search_term("bitcoin".to_string())
+            .sort_by(ArticleSortBy::Popularity)
+            .build();
+        let mut normalizations = Vec::new();
+        let prepared = client
+            .prepare_everything_request(&request, &mut normalizations)
+            .unwrap();
+        assert!(normalizations.is_empty());
+        assert_eq!(prepared.get_sort_by().as_deref(), Some("popularity"));
+        assert_eq!(*prepared.get_page_size(), 0);
+
+        let params = NewsApiClient::<reqwest::Client>::get_everything_query_params(&prepared);
+        assert!(params.contains(&("sortBy".to_string(), "popularity".to_string())));
+    }
+
+    #[test]
+    fn test_prepare_everything_request_without_default_sort_by() {
+        let client = NewsApiClient::new("test-api-key");
+
+        let request = GetEverythingRequest::builder()
+            .search_term("bitcoin".to_string())
+            .build();
+        let mut normalizations = Vec::new();
+        let prepared = client
+            .prepare_everything_request(&request, &mut normalizations)
+            .unwrap();
+        assert!(normalizations.is_empty());
+        assert_eq!(prepared.get_sort_by().as_deref(), None);
+
+        let params = NewsApiClient::<reqwest::Client>::get_everything_query_params(&prepared);
+        assert!(!params.iter().any(|(key, _)| key == "sortBy"));
+    }
+
+    #[test]
+    fn test_preview_url() {
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .default_sort_by(ArticleSortBy::PublishedAt)
+            .default_page_size(20)
+            .build()
+            .unwrap();
+
+        let request = GetEverythingRequest::builder()
+            .search_term("bitcoin".to_string())
+            .build();
+        let url = client.preview_url(&request).unwrap();
+        assert_eq!(url.path(), "/v2/everything");
+        let query: HashMap<_, _> = url.query_pairs().into_owned().collect();
+        assert_eq!(query["q"], "bitcoin");
+        assert_eq!(query["sortBy"], "publishedAt");
+        assert_eq!(query["pageSize"], "20");
+        assert!(!url.as_str().contains("test-api-key"));
+
+        let request = GetTopHeadlinesRequest::builder()
+            .country(Country::US)
+            .build()
+            .unwrap();
+        let url = client.preview_url(&request).unwrap();
+        assert_eq!(url.path(), "/v2/top-headlines");
+        assert!(url.query_pairs().any(|(k, v)| k == "country" && v == "us"));
+
+        let strict = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .page_size_policy(PageSizePolicy::Strict)
+            .build()
+            .unwrap();
+        let oversized = GetEverythingRequest::builder()
+            .search_term("bitcoin".to_string())
+            .page_size(500)
+            .build();
+        assert!(matches!(
+            strict.preview_url(&oversized),
+            Err(ApiClientError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_preview_url_query_parameter_auth_mode() {
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .auth_mode(AuthMode::QueryParameter)
+            .build()
+            .unwrap();
+
+        let request = GetSourcesRequest::builder().country(Country::US).build();
+        let url = client.preview_url(&request).unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://newsapi.org/v2/top-headlines/sources?country=us&apiKey=REDACTED"
+        );
+    }
+
+    #[test]
+    fn test_page_size_policy_strict() {
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .page_size_policy(PageSizePolicy::Strict)
+            .build()
+            .unwrap();
+        let request = GetTopHeadlinesRequest::builder()
+            .country(Country::US)
+            .page_size(101)
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            client.prepare_top_headlines_request(&request, &mut Vec::new()),
+            Err(ApiClientError::InvalidRequest(_))
+        ));
+        assert!(client
+            .prepare_top_headlines_request(&request.with_page_size(100), &mut Vec::new())
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_get_everything_async() {
+        let mock_response = r#"{
+            "status": "ok",
+            "totalResults": 2,
+            "articles": [
+                {
+                    "source": {"id": "test-source", "name": "Test Source"},
+                    "author": "Test Author",
+                    "title": "Test Title",
+                    "description": "Test Description",
+                    "url": "https://example.com/article1",
+                    "urlToImage": "https://example.com/image1.jpg",
+                    "publishedAt": "2023-05-01T12:00:00Z",
+                    "content": "Test content"
+                },
+                {
+                    "source": {"id": "test-source-2", "name": "Test Source 2"},
+                    "author": "Test Author 2",
+                    "title": "Test Title 2",
+                    "description": "Test Description 2",
+                    "url": "https://example.com/article2",
+                    "urlToImage": "https://example.com/image2.jpg",
+                    "publishedAt": "2023-05-02T12:00:00Z",
+                    "content": "Test content 2"
+                }
+            ]
+        }"#;
+
+        let mut server = mockito::Server::new_async().await;
+
+        let _m = server
+            .mock("GET", "/v2/everything")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(mock_response)
+            .create_async()
+            .await;
+
+        let mut client = NewsApiClient::new("test-api-key");
+        client.base_url = Url::parse(&server.url()).unwrap();
+
+        let request = GetEverythingRequest::builder()
+            .search_term("test".to_string())
+            .build();
+
+        let response = client.get_everything(&request).await.unwrap();
+
+        assert_eq!(response.get_status(), &ResponseStatus::Ok);
+        assert_eq!(*response.get_total_results(), 2);
+        assert_eq!(response.get_articles().len(), 2);
+        assert_eq!(response.get_articles()[0].get_title(), "Test Title");
+        assert_eq!(response.get_articles()[1].get_title(), "Test Title 2");
+    }
+
+    #[tokio::test]
+    async fn test_get_everything_highlights_matched_terms_async() {
+        let mut server = mockito::Server::new_async().await;
+        let _m = server
+            .mock("GET", "/v2/everything")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(
+                r#"{"status": "ok", "totalResults": 1, "articles": [
+                    {"source": {"id": null, "name": "Test Source"},
+                     "title": "Test Title", "description": "A test description",
+                     "url": "https://example.com/article1",
+                     "publishedAt": "2023-05-01T12:00:00Z"}]}"#,
+            )
+            .create_async()
+            .await;
+
+        let mut client = NewsApiClient::new("test-api-key");
+        client.base_url = Url::parse(&server.url()).unwrap();
+        let request = GetEverythingRequest::builder()
+            .search_term("test".to_string())
+            .build();
+
+        let response = client.get_everything(&request).await.unwrap();
+        let article = &response.get_articles()[0];
+        assert_eq!(article.matched_terms(), vec!["test"]);
+        assert_eq!(article.get_term_matches().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_synthesizes_article_summaries() {
+        let mock_response = r#"{
+            "status": "ok",
+            "totalResults": 2,
+            "articles": [
+                {
+                    "source": {"id": null, "name": "Test Source"},
+                    "title": "Test Title",
+                    "description": "Markets rallied on Monday.",
+                    "url": "https://example.com/article",
+                    "publishedAt": "2023-05-01T12:00:00Z",
+                    "content": "Analysts expect gains to continue. Traders remained cautious after… [+2817 chars]"
+                },
+                {
+                    "source": {"id": null, "name": "Test Source"},
+                    "title": "Test Title 2",
+                    "url": "https://example.com/article2",
+                    "publishedAt": "2023-05-02T12:00:00Z"
+                }
+            ]
+        }"#;
+
+        let mut server = mockito::Server::new_async().await;
+        let _m = server
+            .mock("GET", "/v2/everything")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(mock_response)
+            .create_async()
+            .await;
+
+        let mut client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .summary_length(40)
+            .build()
+            .unwrap();
+        client.base_url = Url::parse(&server.url()).unwrap();
+
+        let request = GetEverythingRequest::builder()
+            .search_term("markets".to_string())
+            .build();
+        let response = client.get_everything(&request).await.unwrap();
+
+        assert_eq!(
+            response.get_articles()[0].get_summary(),
+            Some("Markets rallied on Monday.")
+        );
+        assert_eq!(response.get_articles()[1].get_summary(), None);
+    }
+
+    #[tokio::test]
+    async fn test_get_top_headlines_async() {
+        let mock_response = r#"{
+            "status": "ok",
+            "totalResults": 1,
+            "articles": [
+                {
+                    "source": {"id": "test-source", "name": "Test Source"},
+                    "author": "Test Author",
+                    "title": "Breaking News",
+                    "description": "Test Description",
+                    "url": "https://example.com/article1",
+                    "urlToImage": "https://example.com/image1.jpg",
+                    "publishedAt": "2023-05-01T12:00:00Z",
+                    "content": "Test content"
+                }
+            ]
+        }"#;
+
+        let mut server = mockito::Server::new_async().await;
+        let _m = server
+            .mock("GET", "/v2/top-headlines")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(mock_response)
+            .create_async()
+            .await;
+        let mut client = NewsApiClient::new("test-api-key");
+        client.base_url = Url::parse(&server.url()).unwrap();
+
+        let request = GetTopHeadlinesRequest::builder()
+            .country(Country::US)
+            .search_term(String::new())
+            .page_size(20)
+            .page(1)
+            .build()
+            .unwrap();
+
+        let response = client.get_top_headlines(&request).await.unwrap();
+
+        assert_eq!(response.get_status(), &ResponseStatus::Ok);
+        assert_eq!(*response.get_total_results(), 1);
+        assert_eq!(response.get_articles().len(), 1);
+        assert_eq!(response.get_articles()[0].get_title(), "Breaking News");
+    }
+
+    #[tokio::test]
+    async fn test_error_responses_async() {
+        let error_response = r#"{
+            "status": "error",
+            "code": "apiKeyInvalid",
+            "message": "Your API key is invalid or incorrect"
+        }"#;
+
+        let mut server = mockito::Server::new_async().await;
+        let _m = server
+            .mock("GET", "/v2/everything")
+            .match_query(mockito::Matcher::Any)
+            .with_status(401)
+            .with_body(error_response)
+            .create_async()
+            .await;
+
+        let mut client = NewsApiClient::new("test-api-key");
+        client.base_url = Url::parse(&server.url()).unwrap();
+
+        let request = GetEverythingRequest::builder()
+            .search_term("test".to_string())
+            .build();
+
+        let result = client.get_everything(&request).await;
+        assert!(result.is_err());
+
+        match result.unwrap_err() {
+            ApiClientError::InvalidResponse(response) => {
+                assert_eq!(response.code, ApiClientErrorCode::ApiKeyInvalid);
+            }
+            _ => panic!("Expected InvalidResponse error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_pauses_client_async() {
+        let error_response = r#"{
+            "status": "error",
+            "code": "rateLimited",
+            "message": "You have been rate limited"
+        }"#;
+
+        let mut server = mockito::Server::new_async().await;
+        let m = server
+            .mock("GET", "/v2/everything")
+            .match_query(mockito::Matcher::Any)
+            .with_status(429)
+            .with_header("retry-after", "120")
+            .with_header("x-ratelimit-remaining", "0")
+            .with_body(error_response)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let mut client = NewsApiClient::new("test-api-key");
+        client.base_url = Url::parse(&server.url()).unwrap();
+        let clone = client.clone();
+
+        let request = GetEverythingRequest::builder()
+            .search_term("test".to_string())
+            .build();
+
+        match client.get_everything(&request).await.unwrap_err() {
+            ApiClientError::RateLimited {
+                retry_after,
+                remaining,
+                response,
+            } => {
+                assert!(retry_after.unwrap() > Duration::from_secs(115));
+                assert_eq!(remaining, Some(0));
+                assert_eq!(response.code, ApiClientErrorCode::RateLimited);
+            }
+            _ => panic!("Expected RateLimited error"),
+        }
+
+        assert!(clone.quota_status().is_paused());
+        assert!(matches!(
+            clone.get_everything(&request).await.unwrap_err(),
+            ApiClientError::Paused(_)
+        ));
+        m.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_stale_if_error_async() {
+        let mock_response = r#"{
+            "status": "ok",
+            "sources": [{"id": "bbc-news", "name": "BBC News"}]
+        }"#;
+
+        let mut server = mockito::Server::new_async().await;
+        let ok = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(mock_response)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .cache_policy(Endpoint::Sources, CachePolicy::StaleIfError)
+            .build()
+            .unwrap();
+        let request = GetSourcesRequest::builder().build();
+
+        let response = client.get_sources(&request).await.unwrap();
+        assert!(!response.get_meta().is_stale());
+        let fetched_at = *response.get_meta().get_fetched_at().unwrap();
+
+        ok.remove_async().await;
+        let _m = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(500)
+            .with_body(r#"{"status":"error","code":"unexpectedError","message":"Oops"}"#)
+            .create_async()
+            .await;
+
+        let response = client.get_sources(&request).await.unwrap();
+        assert!(response.get_meta().is_stale());
+        assert_eq!(response.get_meta().get_fetched_at(), Some(&fetched_at));
+        assert!(response
+            .get_meta()
+            .get_request_key()
+            .is_some_and(|key| key.contains("/v2/top-headlines/sources")));
+        assert_eq!(response.get_sources()[0].get_name(), "BBC News");
+
+        let other_request = GetSourcesRequest::builder().country(Country::US).build();
+        assert!(client.get_sources(&other_request).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_quota_budget_fails_locally_async() {
+        let mut server = mockito::Server::new_async().await;
+        let m = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"status": "ok", "sources": []}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .quota_budget(QuotaBudget::daily(1))
+            .build()
+            .unwrap();
+        let request = GetSourcesRequest::builder().build();
+
+        assert!(client.get_sources(&request).await.is_ok());
+        assert!(matches!(
+            client.get_sources(&request).await.unwrap_err(),
+            ApiClientError::QuotaExceeded { limit: 1, .. }
+        ));
+        assert_eq!(client.quota_status().get_remaining(), Some(0));
+        m.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_retry_on_network_only_async() {
+        let mut server = mockito::Server::new_async().await;
+        let m = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(429)
+            .with_body(r#"{"status":"error","code":"rateLimited","message":"Slow down"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .retry(RetryStrategy::Constant(Duration::from_millis(1)), 3)
+            .retry_on(RetryOn::NetworkOnly)
+            .build()
+            .unwrap();
+        let request = GetSourcesRequest::builder().build();
+
+        assert!(client.get_sources(&request).await.is_err());
+        m.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_on_retry_async() {
+        let mut server = mockito::Server::new_async().await;
+        let _m = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(429)
+            .with_body(r#"{"status":"error","code":"rateLimited","message":"Slow down"}"#)
+            .create_async()
+            .await;
+
+        let retries = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = retries.clone();
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .retry(RetryStrategy::Constant(Duration::from_millis(1)), 2)
+            .on_retry(move |attempt, error, delay| {
+                assert!(matches!(error, ApiClientError::RateLimited { .. }));
+                recorded.lock().unwrap().push((attempt, delay));
+            })
+            .build()
+            .unwrap();
+
+        let result = client
+            .get_sources(&GetSourcesRequest::builder().build())
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            *retries.lock().unwrap(),
+            vec![(1, Duration::from_millis(1)), (2, Duration::from_millis(1))]
+        );
+    }
+
+    #[test]
+    fn test_skip_malformed_articles() {
+        let url = Url::parse("https://newsapi.org/v2/everything?q=rust").unwrap();
+        let body = r#"{"status": "ok", "totalResults": 3, "articles": [
+            {"source": {"id": null, "name": "BBC"}, "title": "One",
+             "url": "https://example.com/1", "publishedAt": "2023-05-01T12:00:00Z"},
+            {"source": {"id": null, "name": "BBC"}, "title": null,
+             "url": "https://example.com/2", "publishedAt": "2023-05-01T12:00:00Z"},
+            {"source": {"id": null, "name": "BBC"}, "title": "Three",
+             "url": "https://example.com/3", "publishedAt": "2023-05-01T12:00:00Z"}]}"#;
+        let strict = NewsApiClient::new("test-api-key");
+        let lenient = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .skip_malformed_articles(true)
+            .build()
+            .unwrap();
+
+        assert!(strict
+            .parse_body::<GetEverythingResponse>(&url, Some(200), body)
+            .is_err());
+        let response = lenient
+            .parse_body::<GetEverythingResponse>(&url, Some(200), body)
+            .unwrap();
+
+        let titles: Vec<_> = response
+            .get_articles()
+            .iter()
+            .map(|article| article.get_title().as_str())
+            .collect();
+        assert_eq!(titles, vec!["One", "Three"]);
+        assert_eq!(response.get_warnings().len(), 1);
+        assert_eq!(*response.get_warnings()[0].get_index(), 1);
+        assert!(response.get_warnings()[0]
+            .get_message()
+            .starts_with("title: "));
+    }
+
+    #[test]
+    fn test_parse_error_path() {
+        let url = Url::parse("https://newsapi.org/v2/everything?q=rust").unwrap();
+        let body = r#"{"status": "ok", "totalResults": 2, "articles": [
+            {"source": {"id": null, "name": "BBC"}, "title": "One",
+             "url": "https://example.com/1", "publishedAt": "2023-05-01T12:00:00Z"},
+            {"source": {"id": null}, "title": "Two",
+             "url": "https://example.com/2", "publishedAt": "2023-05-01T12:00:00Z"}]}"#;
+
+        let error = NewsApiClient::<reqwest::Client>::parse_response::<GetEverythingResponse>(
+            &url,
+            Some(200),
+            body,
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            &error,
+            ApiClientError::ParseResponse { path, .. } if path == "articles[1].source"
+        ));
+        assert!(error.to_string().ends_with("at articles[1].source"));
+        assert!(std::error::Error::source(&error)
+            .unwrap()
+            .to_string()
+            .contains("missing field `name`"));
+    }
+
+    #[tokio::test]
+    async fn test_errors_carry_response_context() {
+        let mut server = mockito::Server::new_async().await;
+        let _m = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"status":"ok","sources":"none"}"#)
+            .create_async()
+            .await;
+        let _e = server
+            .mock("GET", "/v2/everything")
+            .match_query(mockito::Matcher::Any)
+            .with_status(400)
+            .with_body(r#"{"status":"error","code":"parameterInvalid","message":"Bad q"}"#)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .retry(RetryStrategy::None, 0)
+            .build()
+            .unwrap();
+
+        let error = client
+            .get_sources(&GetSourcesRequest::builder().build())
+            .await
+            .unwrap_err();
+        assert!(matches!(&error, ApiClientError::ParseResponse { path, .. } if path == "sources"));
+        assert!(std::error::Error::source(&error).is_some());
+        let context = error.get_context().unwrap();
+        assert!(context.get_url().contains("/v2/top-headlines/sources"));
+        assert_eq!(context.get_status(), Some(200));
+        assert_eq!(context.get_body(), r#"{"status":"ok","sources":"none"}"#);
+
+        let request = GetEverythingRequest::builder()
+            .search_term("rust".to_string())
+            .build();
+        let error = client.get_everything(&request).await.unwrap_err();
+        let context = error.get_context().unwrap();
+        assert!(context.get_url().contains("/v2/everything?q=rust"));
+        assert_eq!(context.get_status(), Some(400));
+        assert!(context.get_body().contains("parameterInvalid"));
+    }
+
+    #[tokio::test]
+    async fn test_error_body_with_success_status() {
+        let mut server = mockito::Server::new_async().await;
+        let _m = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"status":"error","code":"apiKeyInvalid","message":"Bad key"}"#)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .build()
+            .unwrap();
+
+        let error = client
+            .get_sources(&GetSourcesRequest::builder().build())
+            .await
+            .unwrap_err();
+        let response = error.get_response().unwrap();
+        assert_eq!(response.status, ResponseStatus::Error);
+        assert_eq!(response.code, ApiClientErrorCode::ApiKeyInvalid);
+        assert_eq!(error.get_context().unwrap().get_status(), Some(200));
+    }
+
+    #[tokio::test]
+    async fn test_error_mapper() {
+        let mut server = mockito::Server::new_async().await;
+        let _m = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(502)
+            .with_header("x-gateway-error", "upstream-timeout")
+            .with_body(r#"{"fault":{"detail":"upstream timed out"}}"#)
+            .create_async()
+            .await;
+        let _e = server
+            .mock("GET", "/v2/everything")
+            .match_query(mockito::Matcher::Any)
+            .with_status(401)
+            .with_body(r#"{"status":"error","code":"apiKeyInvalid","message":"Bad key"}"#)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .retry(RetryStrategy::None, 0)
+            .error_mapper(|status, headers, body| {
+                let fault = headers.get("x-gateway-error")?.to_str().ok()?;
+                assert_eq!(status, StatusCode::BAD_GATEWAY);
+                assert!(body.contains("upstream timed out"));
+                Some(ApiClientError::Transport(fault.into()))
+            })
+            .build()
+            .unwrap();
+
+        let error = client
+            .get_sources(&GetSourcesRequest::builder().build())
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Transport error");
+        assert_eq!(
+            std::error::Error::source(&error).unwrap().to_string(),
+            "upstream-timeout"
+        );
+
+        let request = GetEverythingRequest::builder()
+            .search_term("rust".to_string())
+            .build();
+        let error = client.get_everything(&request).await.unwrap_err();
+        assert_eq!(
+            error.get_response().unwrap().code,
+            ApiClientErrorCode::ApiKeyInvalid
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_sources_with_meta() {
+        let mut server = mockito::Server::new_async().await;
+        let m = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("etag", "\"v1\"")
+            .with_header("set-cookie", "session=secret")
+            .with_body(r#"{"status":"ok","sources":[]}"#)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .build()
+            .unwrap();
+
+        let envelope = client
+            .get_sources_with_meta(&GetSourcesRequest::builder().build())
+            .await
+            .unwrap();
+
+        assert_eq!(envelope.get_status(), Some(StatusCode::OK));
+        assert_eq!(envelope.get_attempts(), 1);
+        assert_eq!(envelope.get_headers()["etag"], "\"v1\"");
+        assert!(envelope.get_headers().get("set-cookie").is_none());
+        assert!(envelope.get_body().get_sources().is_empty());
+        m.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_get_everything_raw() {
+        let body = r#"{"status": "ok", "totalResults": 2, "nextPage": "abc", "articles": [
+            {"source": {"id": null, "name": "BBC"}, "title": "Title",
+             "url": "https://example.com/1", "publishedAt": "2023-05-01T12:00:00Z"},
+            {"source": null, "title": "Broken"}]}"#;
+        let mut server = mockito::Server::new_async().await;
+        let _m = server
+            .mock("GET", "/v2/everything")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .skip_malformed_articles(true)
+            .build()
+            .unwrap();
+
+        let request = GetEverythingRequest::builder()
+            .search_term("rust".to_string())
+            .build();
+        let raw = client.get_everything_raw(&request).await.unwrap();
+
+        assert_eq!(raw.get_response().get_articles().len(), 1);
+        assert_eq!(raw.get_bytes().as_ref(), body.as_bytes());
+        let value = raw.to_value().unwrap();
+        assert_eq!(value["nextPage"], "abc");
+        assert_eq!(value["articles"].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_request_id_shared_across_retries() {
+        let mut server = mockito::Server::new_async().await;
+        let m = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .match_header("x-correlation-id", "incoming-123")
+            .with_status(429)
+            .with_body(r#"{"status":"error","code":"rateLimited","message":"Slow down"}"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .retry(RetryStrategy::Constant(Duration::from_millis(1)), 1)
+            .request_id_header(HeaderName::from_static("x-correlation-id"))
+            .build()
+            .unwrap()
+            .with_request_id("incoming-123");
+
+        let error = client
+            .get_sources(&GetSourcesRequest::builder().build())
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.get_request_id(), Some("incoming-123"));
+        assert!(error.to_string().ends_with("request_id=incoming-123"));
+        m.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_generated_request_id() {
+        let mut server = mockito::Server::new_async().await;
+        let m = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .match_header(
+                "x-request-id",
+                mockito::Matcher::Regex("^[0-9a-f]{16}$".into()),
+            )
+            .with_status(200)
+            .with_body(r#"{"status":"ok","sources":[]}"#)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .request_id_header(HeaderName::from_static("x-request-id"))
+            .build()
+            .unwrap();
+
+        let response = client
+            .get_sources(&GetSourcesRequest::builder().build())
+            .await
+            .unwrap();
+
+        assert_eq!(response.get_meta().get_request_id().map(str::len), Some(16));
+        m.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_on_request_async() {
+        let mut server = mockito::Server::new_async().await;
+        let _rate_limited = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(429)
+            .with_body(r#"{"status":"error","code":"rateLimited","message":"Slow down"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let _ok = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"status":"ok","sources":[]}"#)
+            .create_async()
+            .await;
+
+        let requests = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .retry(RetryStrategy::Constant(Duration::from_millis(1)), 1)
+            .on_request(move |endpoint, _, error| {
+                recorded.lock().unwrap().push((endpoint, error.is_some()));
+            })
+            .build()
+            .unwrap();
+
+        let result = client
+            .get_sources(&GetSourcesRequest::builder().build())
+            .await;
+
+        assert!(result.is_ok());
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![(Endpoint::Sources, true), (Endpoint::Sources, false)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_history_in_meta_async() {
+        let sleeper = InstantSleeper::new();
+        let mut server = mockito::Server::new_async().await;
+        let _rate_limited = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(429)
+            .with_body(r#"{"status":"error","code":"rateLimited","message":"Slow down"}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        let _ok = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"status":"ok","sources":[]}"#)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .retry(RetryStrategy::Constant(Duration::from_secs(30)), 3)
+            .sleeper(sleeper.clone())
+            .build()
+            .unwrap();
+        let request = GetSourcesRequest::builder().build();
+
+        let meta = client
+            .get_sources(&request)
+            .await
+            .unwrap()
+            .get_meta()
+            .clone();
+        assert_eq!(sleeper.get_sleeps(), vec![Duration::from_secs(30); 2]);
+        assert_eq!(meta.get_attempts(), 3);
+        assert_eq!(meta.get_retries().len(), 2);
+        assert!(meta.get_retries()[0].get_error().contains("Slow down"));
+        assert_eq!(meta.get_retries()[1].get_delay(), Duration::from_secs(30));
+
+        let meta = client
+            .get_sources(&request)
+            .await
+            .unwrap()
+            .get_meta()
+            .clone();
+        assert_eq!(meta.get_attempts(), 1);
+        assert!(meta.get_retries().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_revalidate_uses_cached_body_on_not_modified_async() {
+        let mut server = mockito::Server::new_async().await;
+        let ok = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .match_header("if-none-match", mockito::Matcher::Missing)
+            .with_status(200)
+            .with_header("etag", "\"v1\"")
+            .with_body(r#"{"status":"ok","sources":[{"id":"bbc-news","name":"BBC News"}]}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let not_modified = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .match_header("if-none-match", "\"v1\"")
+            .with_status(304)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .cache_policy(Endpoint::Sources, CachePolicy::Revalidate)
+            .build()
+            .unwrap();
+        let request = GetSourcesRequest::builder().build();
+
+        let body_size = r#"{"status":"ok","sources":[{"id":"bbc-news","name":"BBC News"}]}"#.len();
+        for transfer_size in [body_size, 0, 0] {
+            let response = client.get_sources(&request).await.unwrap();
+            assert_eq!(response.get_sources()[0].get_name(), "BBC News");
+            assert!(!response.get_meta().is_stale());
+            assert_eq!(response.get_meta().get_body_size(), Some(body_size));
+            assert_eq!(response.get_meta().get_transfer_size(), Some(transfer_size));
+        }
+        ok.assert_async().await;
+        not_modified.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_offline_mode_serves_cache_only_async() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"status":"ok","sources":[{"id":"bbc-news","name":"BBC News"}]}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .cache_policy(Endpoint::Sources, CachePolicy::StaleIfError)
+            .build()
+            .unwrap();
+        let request = GetSourcesRequest::builder().build();
+        client.get_sources(&request).await.unwrap();
+
+        let offline = client.clone();
+        offline.set_offline_mode(OfflineMode::CacheOnly);
+        assert_eq!(client.get_offline_mode(), OfflineMode::CacheOnly);
+
+        let response = client.get_sources(&request).await.unwrap();
+        assert_eq!(response.get_sources()[0].get_name(), "BBC News");
+        assert!(response.get_meta().is_stale());
+
+        let miss = client
+            .get_sources(&GetSourcesRequest::builder().country(Country::US).build())
+            .await;
+        assert!(matches!(miss, Err(ApiClientError::Offline(_))));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_equal_requests_share_cache_entries_async() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v2/everything")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"status":"ok","totalResults":0,"articles":[]}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .cache_policy(Endpoint::Everything, CachePolicy::StaleIfError)
+            .build()
+            .unwrap();
+        let request = GetEverythingRequest::builder()
+            .search_term("bitcoin".to_string())
+            .sources("cnn,bbc-news".to_string())
+            .search_in(vec![SearchInOption::Title, SearchInOption::Content])
+            .build();
+        client.get_everything(&request).await.unwrap();
+
+        client.set_offline_mode(OfflineMode::CacheOnly);
+        let reordered = GetEverythingRequest::builder()
+            .search_term("bitcoin".to_string())
+            .sources("bbc-news,cnn".to_string())
+            .search_in(vec![SearchInOption::Content, SearchInOption::Title])
+            .build();
+        let response = client.get_everything(&reordered).await.unwrap();
+        assert!(response.get_meta().is_stale());
+        mock.assert_async().await;
     }
 
-    #[test]
-    fn test_get_top_headlines_query_params() {
-        let request = GetTopHeadlinesRequest::builder()
-            .country(Country::US)
-            .category(NewsCategory::Technology)
-            .search_term("ai".to_string())
-            .page_size(15)
-            .page(2)
+    #[tokio::test]
+    async fn test_sources_catalog_is_cached_for_ttl() {
+        let clock = ManualClock::new();
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(
+                r#"{"status":"ok","sources":[
+                    {"id":"bbc-news","name":"BBC News","category":"general","country":"gb"},
+                    {"id":"espn","name":"ESPN","category":"sports","country":"us"}
+                ]}"#,
+            )
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .clock(clock.clone())
+            .sources_catalog_ttl(Duration::from_secs(3600))
             .build()
             .unwrap();
 
-        let params = NewsApiClient::<reqwest::Client>::get_top_headlines_query_params(&request);
-        let params_map: HashMap<_, _> = params.into_iter().collect();
+        let catalog = client.sources_catalog().await.unwrap();
+        assert_eq!(catalog.get("espn").unwrap().get_source().get_name(), "ESPN");
+        assert_eq!(catalog.by_country(&Country::GB).len(), 1);
 
-        assert_eq!(params_map.get("country").unwrap(), "us");
-        assert_eq!(params_map.get("category").unwrap(), "technology");
-        assert_eq!(params_map.get("q").unwrap(), "ai");
-        assert_eq!(params_map.get("page").unwrap(), "2");
-        assert_eq!(params_map.get("pageSize").unwrap(), "15");
+        clock.advance(Duration::from_secs(3599));
+        let cached = client.clone().sources_catalog().await.unwrap();
+        assert!(Arc::ptr_eq(&catalog, &cached));
+
+        clock.advance(Duration::from_secs(1));
+        let refreshed = client.sources_catalog().await.unwrap();
+        assert!(!Arc::ptr_eq(&catalog, &refreshed));
+        mock.assert_async().await;
     }
 
-    #[test]
-    fn test_get_everything_query_params() {
-        let start_date = DateTime::<Utc>::from_str("2023-01-01T00:00:00Z").unwrap();
-        let end_date = DateTime::<Utc>::from_str("2023-01-31T23:59:59Z").unwrap();
+    #[cfg(feature = "embedded-sources")]
+    #[tokio::test]
+    async fn test_sources_catalog_falls_back_to_embedded() {
+        let client = NewsApiClient::builder()
+            .offline_mode(OfflineMode::CacheOnly)
+            .build()
+            .unwrap();
+
+        let catalog = client.sources_catalog().await.unwrap();
+        assert!(catalog.is_embedded());
+        assert!(catalog.get("cnn").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_strict_mode_fails_instead_of_recovering() {
+        let mut server = mockito::Server::new_async().await;
+        let ok = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"status":"ok","sources":[{"id":"bbc-news","name":"BBC News"}]}"#)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .retry(RetryStrategy::None, 0)
+            .cache_policy(Endpoint::Sources, CachePolicy::StaleIfError)
+            .strictness(Strictness::Strict)
+            .build()
+            .unwrap();
 
         let request = GetEverythingRequest::builder()
-            .search_term(format!("bitcoin"))
-            .language(Language::AR)
-            .start_date(start_date)
-            .end_date(end_date)
-            .page(3)
-            .page_size(20)
+            .search_term("bitcoin".to_string())
+            .page_size(500)
+            .build();
+        assert!(matches!(
+            client.get_everything(&request).await,
+            Err(ApiClientError::InvalidRequest(_))
+        ));
+
+        let request = GetSourcesRequest::builder().build();
+        assert!(client.get_sources(&request).await.is_ok());
+        ok.remove_async().await;
+        let _m = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(500)
+            .with_body(r#"{"status":"error","code":"unexpectedError","message":"Oops"}"#)
+            .create_async()
+            .await;
+        assert!(client.get_sources(&request).await.is_err());
+        assert!(client.sources_catalog().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_primes_sources_catalog() {
+        let mut server = mockito::Server::new_async().await;
+        let head = server
+            .mock("HEAD", "/")
+            .match_header("authorization", mockito::Matcher::Missing)
+            .with_status(301)
+            .expect(1)
+            .create_async()
+            .await;
+        let sources = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"status":"ok","sources":[{"id":"cnn","name":"CNN"}]}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .build()
+            .unwrap();
+        client.warm_up(true).await.unwrap();
+        let catalog = client.sources_catalog().await.unwrap();
+        assert!(catalog.get("cnn").is_some());
+
+        client.set_offline_mode(OfflineMode::CacheOnly);
+        client.warm_up(true).await.unwrap();
+        head.assert_async().await;
+        sources.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_ping_async() {
+        let mut server = mockito::Server::new_async().await;
+        let m = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(401)
+            .with_body(r#"{"status":"error","code":"apiKeyInvalid","message":"Invalid key"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .retry(RetryStrategy::Constant(Duration::from_millis(1)), 3)
+            .build()
+            .unwrap();
+
+        assert!(matches!(client.ping().await, Health::InvalidKey));
+        m.assert_async().await;
+
+        m.remove_async().await;
+        let _ok = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"status":"ok","sources":[]}"#)
+            .create_async()
+            .await;
+        assert!(client.ping().await.is_ok());
+
+        client.set_offline_mode(OfflineMode::CacheOnly);
+        assert!(matches!(client.ping().await, Health::NetworkDown));
+    }
+
+    #[tokio::test]
+    async fn test_endpoint_timeout_overrides_global_timeout() {
+        let mut server = mockito::Server::new_async().await;
+        let slow_body = |body: &'static str| {
+            move |writer: &mut dyn std::io::Write| {
+                std::thread::sleep(Duration::from_millis(300));
+                writer.write_all(body.as_bytes())
+            }
+        };
+        let _sources = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_chunked_body(slow_body(r#"{"status":"ok","sources":[]}"#))
+            .create_async()
+            .await;
+        let _everything = server
+            .mock("GET", "/v2/everything")
+            .match_query(mockito::Matcher::Any)
+            .with_chunked_body(slow_body(
+                r#"{"status":"ok","totalResults":0,"articles":[]}"#,
+            ))
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .timeout(Duration::from_secs(5))
+            .endpoint_timeout(Endpoint::Sources, Duration::from_millis(50))
+            .build()
+            .unwrap();
+
+        let error = client
+            .get_sources(&GetSourcesRequest::builder().build())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ApiClientError::Http(e) if e.is_timeout()));
+
+        let request = GetEverythingRequest::builder()
+            .search_term("bitcoin".to_string())
             .build();
+        assert!(client.get_everything(&request).await.is_ok());
+
+        assert!(NewsApiClient::builder()
+            .api_key("test-api-key")
+            .endpoint_timeout(Endpoint::Everything, Duration::ZERO)
+            .build()
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_to_backup_key() {
+        let clock = ManualClock::new();
+        let mut server = mockito::Server::new_async().await;
+        let exhausted = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .match_header("authorization", "Bearer primary-key")
+            .with_status(429)
+            .with_body(r#"{"status":"error","code":"apiKeyExhausted","message":"Spent"}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        let backup = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .match_header("authorization", "Bearer backup-key")
+            .with_status(200)
+            .with_body(r#"{"status":"ok","sources":[]}"#)
+            .expect(3)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("primary-key")
+            .backup_api_key("backup-key")
+            .key_cool_down(Duration::from_secs(600))
+            .base_url(server.url())
+            .clock(clock.clone())
+            .build()
+            .unwrap();
+        let request = GetSourcesRequest::builder().build();
+
+        assert!(client.get_sources(&request).await.is_ok());
+        assert!(client.get_sources(&request).await.is_ok());
+        assert!(!client.quota_status().is_paused());
+
+        clock.advance(Duration::from_secs(600));
+        assert!(client.get_sources(&request).await.is_ok());
+        exhausted.assert_async().await;
+        backup.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_log_summaries_aggregate_requests() {
+        let clock = ManualClock::new();
+        let mut server = mockito::Server::new_async().await;
+        let _m = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"status":"ok","sources":[]}"#)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .clock(clock.clone())
+            .log_summary_interval(Duration::from_secs(60))
+            .build()
+            .unwrap();
+        let request = GetSourcesRequest::builder().build();
+        let request_log = client.request_log.clone().unwrap();
+
+        assert!(client.get_sources(&request).await.is_ok());
+        assert!(client.get_sources(&request).await.is_ok());
+        assert_eq!(request_log.pending(Endpoint::Sources), 2);
+
+        clock.advance(Duration::from_secs(60));
+        assert!(client.get_sources(&request).await.is_ok());
+        assert_eq!(request_log.pending(Endpoint::Sources), 0);
+
+        assert!(matches!(
+            NewsApiClient::builder()
+                .api_key("test-api-key")
+                .log_summary_interval(Duration::ZERO)
+                .build(),
+            Err(ClientBuildError::InvalidSetting { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_manual_clock_async() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let clock = ManualClock::starting_at(start);
+        let mut server = mockito::Server::new_async().await;
+        let _rate_limited = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(429)
+            .with_body(r#"{"status":"error","code":"rateLimited","message":"Slow down"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let _ok = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"status":"ok","sources":[]}"#)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .retry(RetryStrategy::Constant(Duration::from_secs(30)), 3)
+            .clock(clock.clone())
+            .build()
+            .unwrap();
+
+        let response = client
+            .get_sources(&GetSourcesRequest::builder().build())
+            .await
+            .unwrap();
+
+        assert_eq!(clock.get_elapsed(), Duration::from_secs(30));
+        assert_eq!(
+            response.get_meta().get_fetched_at(),
+            Some(&(start + chrono::Duration::seconds(30)))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_custom_api_version_async() {
+        let mut server = mockito::Server::new_async().await;
+        let m = server
+            .mock("GET", "/proxy/newsapi/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"status":"ok","sources":[]}"#)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .api_version(ApiVersion::custom("/proxy/newsapi"))
+            .build()
+            .unwrap();
+
+        let response = client
+            .get_sources(&GetSourcesRequest::builder().build())
+            .await
+            .unwrap();
+        assert!(response.get_sources().is_empty());
+        m.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_max_retry_duration_async() {
+        let mut server = mockito::Server::new_async().await;
+        let _m = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(500)
+            .with_body(r#"{"status":"error","code":"unexpectedError","message":"Oops"}"#)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .retry(RetryStrategy::Constant(Duration::from_millis(100)), 10)
+            .max_retry_duration(Duration::from_millis(250))
+            .build()
+            .unwrap();
+        let start = Instant::now();
 
-        let params = NewsApiClient::<reqwest::Client>::get_everything_query_params(&request);
-        let params_map: HashMap<_, _> = params.into_iter().collect();
+        let result = client
+            .get_sources(&GetSourcesRequest::builder().build())
+            .await;
 
-        assert_eq!(params_map.get("q").unwrap(), "bitcoin");
-        assert_eq!(params_map.get("language").unwrap(), "ar"); // Fix expectation to "ar" instead of "en"
-        assert_eq!(params_map.get("from").unwrap(), "2023-01-01T00:00:00+00:00");
-        assert_eq!(params_map.get("to").unwrap(), "2023-01-31T23:59:59+00:00");
-        assert_eq!(params_map.get("page").unwrap(), "3");
-        assert_eq!(params_map.get("pageSize").unwrap(), "20");
+        assert!(result.is_err());
+        assert!(start.elapsed() < Duration::from_millis(250));
     }
 
     #[tokio::test]
-    async fn test_get_everything_async() {
-        let mock_response = r#"{
-            "status": "ok",
-            "totalResults": 2,
-            "articles": [
-                {
-                    "source": {"id": "test-source", "name": "Test Source"},
-                    "author": "Test Author",
-                    "title": "Test Title",
-                    "description": "Test Description",
-                    "url": "https://example.com/article1",
-                    "urlToImage": "https://example.com/image1.jpg",
-                    "publishedAt": "2023-05-01T12:00:00Z",
-                    "content": "Test content"
-                },
-                {
-                    "source": {"id": "test-source-2", "name": "Test Source 2"},
-                    "author": "Test Author 2",
-                    "title": "Test Title 2",
-                    "description": "Test Description 2",
-                    "url": "https://example.com/article2",
-                    "urlToImage": "https://example.com/image2.jpg",
-                    "publishedAt": "2023-05-02T12:00:00Z",
-                    "content": "Test content 2"
-                }
-            ]
-        }"#;
-
+    async fn test_circuit_breaker_fails_fast_async() {
         let mut server = mockito::Server::new_async().await;
-
-        let _m = server
-            .mock("GET", "/v2/everything")
+        let m = server
+            .mock("GET", "/v2/top-headlines/sources")
             .match_query(mockito::Matcher::Any)
-            .with_status(200)
-            .with_header("content-type", "application/json")
-            .with_body(mock_response)
+            .with_status(500)
+            .with_body("Internal Server Error")
+            .expect(2)
             .create_async()
             .await;
 
-        let mut client = NewsApiClient::new("test-api-key");
-        client.base_url = Url::parse(&format!("{}", server.url())).unwrap();
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .circuit_breaker(2, Duration::from_secs(60))
+            .build()
+            .unwrap();
+        let request = GetSourcesRequest::builder().build();
 
-        let request = GetEverythingRequest::builder()
-            .search_term(format!("test"))
-            .build();
+        assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+        for _ in 0..2 {
+            assert!(matches!(
+                client.get_sources(&request).await.unwrap_err(),
+                ApiClientError::InvalidResponse(_)
+            ));
+        }
+        assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+        assert!(matches!(
+            client.get_sources(&request).await.unwrap_err(),
+            ApiClientError::CircuitOpen(_)
+        ));
+        m.assert_async().await;
+    }
 
-        let response = client.get_everything(&request).await.unwrap();
+    #[tokio::test]
+    async fn test_open_circuit_is_not_retried_async() {
+        let clock = ManualClock::new();
+        let mut server = mockito::Server::new_async().await;
+        let m = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(500)
+            .with_body("Internal Server Error")
+            .expect(1)
+            .create_async()
+            .await;
 
-        assert_eq!(response.get_status(), "ok");
-        assert_eq!(*response.get_total_results(), 2);
-        assert_eq!(response.get_articles().len(), 2);
-        assert_eq!(response.get_articles()[0].get_title(), "Test Title");
-        assert_eq!(response.get_articles()[1].get_title(), "Test Title 2");
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .retry(RetryStrategy::Constant(Duration::from_millis(500)), 3)
+            .circuit_breaker(1, Duration::from_secs(60))
+            .clock(clock.clone())
+            .build()
+            .unwrap();
+        let request = GetSourcesRequest::builder().build();
+
+        assert!(matches!(
+            client.get_sources(&request).await.unwrap_err(),
+            ApiClientError::CircuitOpen(_)
+        ));
+        assert_eq!(clock.get_elapsed(), Duration::from_millis(500));
+
+        assert!(matches!(
+            client.get_sources(&request).await.unwrap_err(),
+            ApiClientError::CircuitOpen(_)
+        ));
+        assert_eq!(clock.get_elapsed(), Duration::from_millis(500));
+        m.assert_async().await;
     }
 
     #[tokio::test]
-    async fn test_get_top_headlines_async() {
-        let mock_response = r#"{
-            "status": "ok",
-            "totalResults": 1,
-            "articles": [
-                {
-                    "source": {"id": "test-source", "name": "Test Source"},
-                    "author": "Test Author",
-                    "title": "Breaking News",
-                    "description": "Test Description",
-                    "url": "https://example.com/article1",
-                    "urlToImage": "https://example.com/image1.jpg",
-                    "publishedAt": "2023-05-01T12:00:00Z",
-                    "content": "Test content"
-                }
-            ]
-        }"#;
+    async fn test_manual_clock_ends_pause_and_cool_down() {
+        let clock = ManualClock::new();
+        let mut server = mockito::Server::new_async().await;
+        let rate_limited = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(429)
+            .with_header("retry-after", "120")
+            .with_body(r#"{"status":"error","code":"rateLimited","message":"Slow down"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .circuit_breaker(1, Duration::from_secs(60))
+            .clock(clock.clone())
+            .build()
+            .unwrap();
+        let request = GetSourcesRequest::builder().build();
+
+        assert!(client.get_sources(&request).await.is_err());
+        assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+        assert!(client.quota_status().is_paused());
+        rate_limited.assert_async().await;
+        rate_limited.remove_async().await;
+        let ok = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"status":"ok","sources":[]}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        clock.advance(Duration::from_secs(60));
+        assert!(matches!(
+            client.get_sources(&request).await.unwrap_err(),
+            ApiClientError::Paused(_)
+        ));
+
+        clock.advance(Duration::from_secs(60));
+        assert!(!client.quota_status().is_paused());
+        assert!(client.get_sources(&request).await.is_ok());
+        assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+        ok.assert_async().await;
+    }
 
+    #[tokio::test]
+    async fn test_get_top_headlines_for_region_async() {
         let mut server = mockito::Server::new_async().await;
-        let _m = server
+        let m = server
             .mock("GET", "/v2/top-headlines")
-            .match_query(mockito::Matcher::Any)
+            .match_query(mockito::Matcher::Regex("country=(de|at|ch)".to_string()))
             .with_status(200)
-            .with_header("content-type", "application/json")
-            .with_body(mock_response)
+            .with_body_from_request(|request| {
+                let total = if request.path_and_query().contains("country=de") {
+                    2
+                } else {
+                    1
+                };
+                format!(r#"{{"status": "ok", "totalResults": {total}, "articles": []}}"#).into()
+            })
+            .expect(3)
             .create_async()
             .await;
-        let mut client = NewsApiClient::new("test-api-key");
-        client.base_url = Url::parse(&format!("{}", server.url())).unwrap();
 
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .build()
+            .unwrap();
         let request = GetTopHeadlinesRequest::builder()
-            .country(Country::US)
-            .search_term(String::new())
-            .page_size(20)
-            .page(1)
+            .category(NewsCategory::Business)
             .build()
             .unwrap();
 
-        let response = client.get_top_headlines(&request).await.unwrap();
+        let responses = client
+            .get_top_headlines_for_region(&Region::Dach, &request)
+            .await
+            .unwrap();
 
-        assert_eq!(response.get_status(), "ok");
-        assert_eq!(*response.get_total_results(), 1);
-        assert_eq!(response.get_articles().len(), 1);
-        assert_eq!(response.get_articles()[0].get_title(), "Breaking News");
+        let totals: Vec<(String, i32)> = responses
+            .iter()
+            .map(|(country, response)| (country.to_string(), *response.get_total_results()))
+            .collect();
+        assert_eq!(
+            totals,
+            vec![
+                ("de".to_string(), 2),
+                ("at".to_string(), 1),
+                ("ch".to_string(), 1)
+            ]
+        );
+        m.assert_async().await;
     }
 
     #[tokio::test]
-    async fn test_error_responses_async() {
-        let error_response = r#"{
-            "status": "error",
-            "code": "apiKeyInvalid",
-            "message": "Your API key is invalid or incorrect"
-        }"#;
-
+    async fn test_compare_pins_end_date_async() {
         let mut server = mockito::Server::new_async().await;
-        let _m = server
+        let m = server
             .mock("GET", "/v2/everything")
-            .match_query(mockito::Matcher::Any)
-            .with_status(401)
-            .with_body(error_response)
+            .match_query(mockito::Matcher::Regex("to=".to_string()))
+            .with_status(200)
+            .with_body_from_request(|request| {
+                let url = if request.path_and_query().contains("q=first") {
+                    "https://example.com/first"
+                } else {
+                    "https://example.com/second"
+                };
+                format!(
+                    r#"{{"status": "ok", "totalResults": 2, "articles": [
+                        {{"source": {{"id": null, "name": "Source"}}, "title": "Shared",
+                        "url": "https://example.com/shared", "publishedAt": "2023-05-01T12:00:00Z"}},
+                        {{"source": {{"id": null, "name": "Source"}}, "title": "Own",
+                        "url": "{url}", "publishedAt": "2023-05-01T12:00:00Z"}}]}}"#
+                )
+                .into()
+            })
+            .expect(2)
             .create_async()
             .await;
 
         let mut client = NewsApiClient::new("test-api-key");
-        client.base_url = Url::parse(&format!("{}", server.url())).unwrap();
-
-        let request = GetEverythingRequest::builder()
-            .search_term(format!("test"))
-            .build();
-
-        let result = client.get_everything(&request).await;
-        assert!(result.is_err());
+        client.base_url = Url::parse(&server.url()).unwrap();
+
+        let comparison = client
+            .compare(
+                &GetEverythingRequest::builder()
+                    .search_term("first".to_string())
+                    .build(),
+                &GetEverythingRequest::builder()
+                    .search_term("second".to_string())
+                    .build(),
+            )
+            .await
+            .unwrap();
 
-        match result.unwrap_err() {
-            ApiClientError::InvalidResponse(response) => {
-                assert_eq!(response.code, ApiClientErrorCode::ApiKeyInvalid);
-            }
-            _ => panic!("Expected InvalidResponse error"),
-        }
+        assert_eq!(
+            comparison.get_overlap(),
+            &vec!["https://example.com/shared"]
+        );
+        assert_eq!(
+            comparison.get_unique_a(),
+            &vec!["https://example.com/first"]
+        );
+        assert_eq!(
+            comparison.get_unique_b(),
+            &vec!["https://example.com/second"]
+        );
+        m.assert_async().await;
     }
 
     #[cfg(feature = "blocking")]
@@ -1010,13 +5397,13 @@ mod tests {
                 .create();
 
             let mut client = NewsApiClient::new_blocking("test-api-key");
-            client.base_url = Url::parse(&format!("{}", server.url())).unwrap();
+            client.base_url = Url::parse(&server.url()).unwrap();
             let request = GetEverythingRequest::builder()
                 .search_term("test".to_string())
                 .build();
             let response = client.get_everything(&request).unwrap();
 
-            assert_eq!(response.get_status(), "ok");
+            assert_eq!(response.get_status(), &ResponseStatus::Ok);
             assert_eq!(*response.get_total_results(), 1);
             assert_eq!(
                 response.get_articles()[0].get_title(),
@@ -1029,12 +5416,109 @@ mod tests {
     fn test_builder_pattern() {
         let client = NewsApiClient::<reqwest::Client>::builder()
             .api_key("test-api-key")
-            .retry(RetryStrategy::Exponential(Duration::from_millis(100)), 3)
+            .retry(
+                RetryStrategy::Exponential {
+                    base: Duration::from_millis(100),
+                    max: Duration::from_secs(10),
+                },
+                3,
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(client.keys.primary(), "test-api-key");
+        assert_eq!(client.get_config().get_max_retries(), 3);
+    }
+
+    #[test]
+    fn test_builder_rate_limit() {
+        let client = NewsApiClient::<reqwest::Client>::builder()
+            .api_key("test-api-key")
+            .rate_limit(100, Duration::from_secs(86400))
+            .build()
+            .unwrap();
+        assert!(client.config.load().rate_limiter.is_some());
+
+        let result = NewsApiClient::<reqwest::Client>::builder()
+            .api_key("test-api-key")
+            .rate_limit(0, Duration::from_secs(1))
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_builder_max_concurrent_requests() {
+        let client = NewsApiClient::<reqwest::Client>::builder()
+            .api_key("test-api-key")
+            .max_concurrent_requests(4)
+            .build()
+            .unwrap();
+        let clone = client.clone();
+        assert!(Arc::ptr_eq(
+            client.concurrency_limiter.as_ref().unwrap(),
+            clone.concurrency_limiter.as_ref().unwrap()
+        ));
+
+        let result = NewsApiClient::<reqwest::Client>::builder()
+            .api_key("test-api-key")
+            .max_concurrent_requests(0)
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "governor")]
+    #[test]
+    fn test_builder_governor_rate_limiter() {
+        let quota = governor::Quota::per_second(std::num::NonZeroU32::new(1).unwrap());
+        let limiter = Arc::new(DefaultDirectRateLimiter::direct(quota));
+
+        let client = NewsApiClient::<reqwest::Client>::builder()
+            .api_key("test-api-key")
+            .rate_limit(100, Duration::from_secs(60))
+            .governor_rate_limiter(limiter, Some(Jitter::up_to(Duration::from_millis(10))))
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            client.config.load().rate_limiter,
+            Some(Limiter::Governor(_, Some(_)))
+        ));
+    }
+
+    #[test]
+    fn test_builder_invalid_base_url() {
+        let error = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url("not a url")
+            .build()
+            .unwrap_err();
+
+        assert!(matches!(
+            error,
+            ClientBuildError::InvalidBaseUrl(url::ParseError::RelativeUrlWithoutBase)
+        ));
+    }
+
+    #[cfg(feature = "governor")]
+    #[test]
+    fn test_reconfigure_removes_governor_rate_limiter() {
+        let quota = governor::Quota::per_second(std::num::NonZeroU32::new(1).unwrap());
+        let client = NewsApiClient::<reqwest::Client>::builder()
+            .api_key("test-api-key")
+            .governor_rate_limiter(Arc::new(DefaultDirectRateLimiter::direct(quota)), None)
             .build()
             .unwrap();
 
-        assert_eq!(client.api_key, "test-api-key");
-        assert_eq!(client.max_retries, 3);
+        client
+            .reconfigure(|config| config.retry(RetryStrategy::None, 2))
+            .unwrap();
+        assert!(matches!(
+            client.config.load().rate_limiter,
+            Some(Limiter::Governor(..))
+        ));
+
+        client.reconfigure(RuntimeConfig::no_rate_limit).unwrap();
+        assert!(client.config.load().rate_limiter.is_none());
     }
 
     #[serial]
@@ -1056,7 +5540,7 @@ mod tests {
 
         assert!(result.is_err());
         assert_eq!(
-            result.unwrap_err(),
+            result.unwrap_err().to_string(),
             format!(
                 "API key must be provided either explicitly or via {} environment variable",
                 NEWS_API_KEY_ENV
@@ -1064,6 +5548,29 @@ mod tests {
         );
     }
 
+    #[serial]
+    #[test]
+    fn test_builder_offline_mode_without_api_key() {
+        let api_key = std::env::var(NEWS_API_KEY_ENV).ok();
+        std::env::remove_var(NEWS_API_KEY_ENV);
+
+        struct Defer<'a>(&'a str, Option<String>);
+        impl<'a> Drop for Defer<'a> {
+            fn drop(&mut self) {
+                match &self.1 {
+                    Some(val) => std::env::set_var(self.0, val),
+                    None => std::env::remove_var(self.0),
+                }
+            }
+        }
+        let _defer = Defer(NEWS_API_KEY_ENV, api_key);
+        let result = NewsApiClient::builder()
+            .offline_mode(OfflineMode::CacheOnly)
+            .build();
+
+        assert!(result.is_ok());
+    }
+
     #[serial]
     #[test]
     fn test_builder_from_env() {
@@ -1082,7 +5589,120 @@ mod tests {
         let _defer = Defer(NEWS_API_KEY_ENV, api_key);
 
         let result = NewsApiClientBuilder::from_env().build().unwrap();
-        assert_eq!(result.api_key, "env-api-key");
+        assert_eq!(result.keys.primary(), "env-api-key");
+    }
+
+    #[serial]
+    #[test]
+    fn test_builder_api_key_env_precedence() {
+        std::env::remove_var("NEWSAPI_TEST_PRIMARY_KEY");
+        std::env::set_var("NEWSAPI_TEST_EMPTY_KEY", "");
+        std::env::set_var("NEWSAPI_TEST_FALLBACK_KEY", "fallback-api-key");
+        let envs = [
+            "NEWSAPI_TEST_PRIMARY_KEY",
+            "NEWSAPI_TEST_EMPTY_KEY",
+            "NEWSAPI_TEST_FALLBACK_KEY",
+        ];
+
+        let client = NewsApiClient::builder().api_key_envs(envs).build().unwrap();
+        assert_eq!(client.keys.primary(), "fallback-api-key");
+
+        std::env::set_var("NEWSAPI_TEST_PRIMARY_KEY", "primary-api-key");
+        let client = NewsApiClient::builder().api_key_envs(envs).build().unwrap();
+        assert_eq!(client.keys.primary(), "primary-api-key");
+
+        let client = NewsApiClient::builder()
+            .api_key("explicit-api-key")
+            .api_key_envs(envs)
+            .build()
+            .unwrap();
+        assert_eq!(client.keys.primary(), "explicit-api-key");
+
+        let error = NewsApiClient::builder()
+            .api_key_envs(["NEWSAPI_TEST_EMPTY_KEY", "NEWSAPI_TEST_MISSING_KEY"])
+            .build()
+            .unwrap_err();
+        assert!(matches!(
+            &error,
+            ClientBuildError::MissingApiKey { env_vars } if env_vars.len() == 2
+        ));
+        assert_eq!(
+            error.to_string(),
+            "API key must be provided either explicitly or via NEWSAPI_TEST_EMPTY_KEY or \
+             NEWSAPI_TEST_MISSING_KEY environment variables"
+        );
+
+        for name in envs {
+            std::env::remove_var(name);
+        }
+    }
+
+    #[cfg(feature = "config-file")]
+    #[serial]
+    #[test]
+    fn test_builder_from_config_file() {
+        let path = std::env::temp_dir().join("newsapi-rs-test-config.toml");
+        std::fs::write(
+            &path,
+            r#"
+            api_key = "file-api-key"
+            api_key_envs = ["NEWSAPI_TEST_CONFIG_KEY"]
+            timeout_ms = 5000
+
+            [retry]
+            strategy = "constant"
+            delay_ms = 250
+            max_retries = 2
+
+            [profiles.prod]
+            api_key = "prod-api-key"
+            "#,
+        )
+        .unwrap();
+
+        std::env::remove_var("NEWSAPI_TEST_CONFIG_KEY");
+        let client = NewsApiClientBuilder::from_config_file(&path)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(client.keys.primary(), "file-api-key");
+        assert_eq!(client.timeout, Some(Duration::from_secs(5)));
+        assert_eq!(client.get_config().get_max_retries(), 2);
+
+        std::env::set_var("NEWSAPI_TEST_CONFIG_KEY", "env-api-key");
+        let client = NewsApiClientBuilder::from_config_file(&path)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(client.keys.primary(), "env-api-key");
+
+        let client = NewsApiClientBuilder::from_config_file(&path)
+            .unwrap()
+            .api_key("explicit-api-key")
+            .build()
+            .unwrap();
+        assert_eq!(client.keys.primary(), "explicit-api-key");
+
+        std::env::remove_var("NEWSAPI_TEST_CONFIG_KEY");
+        let client = NewsApiClientBuilder::from_config_file_profile(&path, "prod")
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(client.keys.primary(), "prod-api-key");
+        assert_eq!(client.timeout, Some(Duration::from_secs(5)));
+
+        std::env::set_var(NEWS_API_PROFILE_ENV, "prod");
+        let client = NewsApiClientBuilder::from_config_file(&path)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(client.keys.primary(), "prod-api-key");
+        std::env::set_var(NEWS_API_PROFILE_ENV, "staging");
+        assert!(NewsApiClientBuilder::from_config_file(&path).is_err());
+
+        std::env::remove_var(NEWS_API_PROFILE_ENV);
+        std::fs::remove_file(&path).unwrap();
+        assert!(NewsApiClientBuilder::from_config_file(&path).is_err());
     }
 
     #[cfg(feature = "blocking")]
@@ -1094,7 +5714,7 @@ mod tests {
             .build()
             .unwrap();
 
-        assert_eq!(client.api_key, "test-api-key");
-        assert_eq!(client.max_retries, 2);
+        assert_eq!(client.keys.primary(), "test-api-key");
+        assert_eq!(client.get_config().get_max_retries(), 2);
     }
 }
@@ -0,0 +1,146 @@
+use crate::sleep::{Sleeper, TokioSleeper};
+use chrono::{DateTime, Utc};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Source of time for retry deadlines, rate limiting and cache timestamps, see
+/// [`NewsApiClientBuilder::clock`](crate::client::NewsApiClientBuilder::clock).
+pub trait Clock: Sleeper {
+    /// Monotonic time, for measuring intervals.
+    fn now(&self) -> Instant;
+
+    /// Wall-clock time, for timestamps.
+    fn now_utc(&self) -> DateTime<Utc>;
+}
+
+/// The system clock, sleeping like [`TokioSleeper`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Sleeper for SystemClock {
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        TokioSleeper.sleep(duration)
+    }
+
+    fn sleep_blocking(&self, duration: Duration) {
+        TokioSleeper.sleep_blocking(duration);
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn now_utc(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that only moves when advanced. Sleeping advances it by the slept duration and
+/// returns immediately, so time-dependent behavior runs without waiting. Clones share the
+/// same time.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    start: Instant,
+    start_utc: DateTime<Utc>,
+    elapsed: Arc<Mutex<Duration>>,
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ManualClock {
+    /// A clock starting at the current time.
+    pub fn new() -> Self {
+        Self::starting_at(Utc::now())
+    }
+
+    /// A clock whose wall-clock time starts at `start_utc`.
+    pub fn starting_at(start_utc: DateTime<Utc>) -> Self {
+        Self {
+            start: Instant::now(),
+            start_utc,
+            elapsed: Arc::new(Mutex::new(Duration::ZERO)),
+        }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        let mut elapsed = self.elapsed.lock().unwrap();
+        *elapsed = elapsed.saturating_add(duration);
+    }
+
+    /// Time the clock has been advanced by in total.
+    pub fn get_elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap()
+    }
+}
+
+impl Sleeper for ManualClock {
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        self.advance(duration);
+        Box::pin(std::future::ready(()))
+    }
+
+    fn sleep_blocking(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.start + self.get_elapsed()
+    }
+
+    fn now_utc(&self) -> DateTime<Utc> {
+        self.start_utc
+            + chrono::Duration::from_std(self.get_elapsed()).unwrap_or(chrono::Duration::MAX)
+    }
+}
+
+/// The system clock with a custom [`Sleeper`].
+#[derive(Debug)]
+pub(crate) struct SleeperClock<S>(pub(crate) S);
+
+impl<S: Sleeper> Sleeper for SleeperClock<S> {
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        self.0.sleep(duration)
+    }
+
+    fn sleep_blocking(&self, duration: Duration) {
+        self.0.sleep_blocking(duration);
+    }
+}
+
+impl<S: Sleeper> Clock for SleeperClock<S> {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn now_utc(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_manual_clock_advances_on_sleep() {
+        let start_utc = Utc::now() - chrono::Duration::days(1);
+        let clock = ManualClock::starting_at(start_utc);
+        let start = clock.now();
+
+        clock.sleep(Duration::from_secs(30)).await;
+        clock.clone().advance(Duration::from_secs(30));
+
+        assert_eq!(clock.now() - start, Duration::from_secs(60));
+        assert_eq!(clock.now_utc() - start_utc, chrono::Duration::seconds(60));
+    }
+}
@@ -0,0 +1,274 @@
+use crate::model::{Article, GetEverythingResponse};
+use getset::{CopyGetters, Getters};
+use std::collections::{BTreeMap, HashSet};
+use strum::Display;
+
+/// Number of articles a source contributed to each side of a comparison
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, CopyGetters)]
+#[getset(get_copy = "pub with_prefix")]
+pub struct SourceCounts {
+    a: usize,
+    b: usize,
+}
+
+impl SourceCounts {
+    /// Articles on side B minus articles on side A.
+    pub fn diff(&self) -> i64 {
+        self.b as i64 - self.a as i64
+    }
+}
+
+/// Result of running two everything queries side by side, see
+/// [`NewsApiClient::compare`](crate::client::NewsApiClient::compare).
+#[derive(Debug, Clone, Getters, CopyGetters)]
+pub struct QueryComparison {
+    #[getset(get_copy = "pub with_prefix")]
+    total_results_a: i32,
+
+    #[getset(get_copy = "pub with_prefix")]
+    total_results_b: i32,
+
+    /// URLs returned by both queries, in the order of side A
+    #[getset(get = "pub with_prefix")]
+    overlap: Vec<String>,
+
+    #[getset(get = "pub with_prefix")]
+    unique_a: Vec<String>,
+
+    #[getset(get = "pub with_prefix")]
+    unique_b: Vec<String>,
+
+    /// Article counts per source name
+    #[getset(get = "pub with_prefix")]
+    source_distribution: BTreeMap<String, SourceCounts>,
+}
+
+impl QueryComparison {
+    pub fn from_responses(a: &GetEverythingResponse, b: &GetEverythingResponse) -> Self {
+        let urls_a = urls(a.get_articles());
+        let urls_b = urls(b.get_articles());
+        let set_a: HashSet<&str> = urls_a.iter().copied().collect();
+        let set_b: HashSet<&str> = urls_b.iter().copied().collect();
+
+        let mut source_distribution: BTreeMap<String, SourceCounts> = BTreeMap::new();
+        for article in a.get_articles() {
+            source_distribution
+                .entry(article.get_source().get_name().to_string())
+                .or_default()
+                .a += 1;
+        }
+        for article in b.get_articles() {
+            source_distribution
+                .entry(article.get_source().get_name().to_string())
+                .or_default()
+                .b += 1;
+        }
+
+        Self {
+            total_results_a: *a.get_total_results(),
+            total_results_b: *b.get_total_results(),
+            overlap: urls_a
+                .iter()
+                .filter(|url| set_b.contains(*url))
+                .map(|url| url.to_string())
+                .collect(),
+            unique_a: urls_a
+                .iter()
+                .filter(|url| !set_b.contains(*url))
+                .map(|url| url.to_string())
+                .collect(),
+            unique_b: urls_b
+                .iter()
+                .filter(|url| !set_a.contains(*url))
+                .map(|url| url.to_string())
+                .collect(),
+            source_distribution,
+        }
+    }
+
+    /// Share of all distinct URLs that both queries returned, between 0 and 1.
+    pub fn jaccard(&self) -> f64 {
+        let union = self.overlap.len() + self.unique_a.len() + self.unique_b.len();
+        if union == 0 {
+            return 0.0;
+        }
+        self.overlap.len() as f64 / union as f64
+    }
+}
+
+/// Field of an article compared by [`Article::diff`]
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash)]
+#[strum(serialize_all = "snake_case")]
+pub enum ArticleField {
+    Title,
+    Description,
+    Content,
+    /// The `urlToImage` of the article
+    Image,
+}
+
+/// A field whose value differs between two versions of an article
+#[derive(Debug, Clone, PartialEq, Eq, Getters, CopyGetters)]
+pub struct FieldChange {
+    #[getset(get_copy = "pub with_prefix")]
+    field: ArticleField,
+
+    #[getset(get = "pub with_prefix")]
+    old: Option<String>,
+
+    #[getset(get = "pub with_prefix")]
+    new: Option<String>,
+}
+
+/// Fields that changed between two versions of an article, e.g. after a correction, see
+/// [`Article::diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Getters)]
+pub struct ArticleDiff {
+    /// Changed fields in the order of [`ArticleField`]
+    #[getset(get = "pub with_prefix")]
+    changes: Vec<FieldChange>,
+}
+
+impl ArticleDiff {
+    pub(crate) fn between(old: &Article, new: &Article) -> Self {
+        let fields = [
+            (
+                ArticleField::Title,
+                Some(old.get_title()),
+                Some(new.get_title()),
+            ),
+            (
+                ArticleField::Description,
+                old.get_description().as_ref(),
+                new.get_description().as_ref(),
+            ),
+            (
+                ArticleField::Content,
+                old.get_content().as_ref(),
+                new.get_content().as_ref(),
+            ),
+            (
+                ArticleField::Image,
+                old.get_url_to_image().as_ref(),
+                new.get_url_to_image().as_ref(),
+            ),
+        ];
+        Self {
+            changes: fields
+                .into_iter()
+                .filter(|(_, old, new)| old != new)
+                .map(|(field, old, new)| FieldChange {
+                    field,
+                    old: old.cloned(),
+                    new: new.cloned(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Whether the two versions have the same title, description, content and image.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The change to `field`, if it changed.
+    pub fn get(&self, field: ArticleField) -> Option<&FieldChange> {
+        self.changes.iter().find(|change| change.field == field)
+    }
+}
+
+/// Distinct article URLs in result order.
+fn urls(articles: &[Article]) -> Vec<&str> {
+    let mut seen = HashSet::new();
+    articles
+        .iter()
+        .map(|article| article.get_url().as_str())
+        .filter(|url| seen.insert(*url))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(articles: &[(&str, &str)]) -> GetEverythingResponse {
+        let articles: Vec<String> = articles
+            .iter()
+            .map(|(source, url)| {
+                format!(
+                    r#"{{"source": {{"id": null, "name": "{source}"}}, "title": "Title",
+                    "url": "{url}", "publishedAt": "2023-05-01T12:00:00Z"}}"#
+                )
+            })
+            .collect();
+        serde_json::from_str(&format!(
+            r#"{{"status": "ok", "totalResults": {}, "articles": [{}]}}"#,
+            articles.len(),
+            articles.join(",")
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_from_responses() {
+        let a = response(&[
+            ("BBC", "https://a/1"),
+            ("BBC", "https://a/2"),
+            ("CNN", "https://a/3"),
+        ]);
+        let b = response(&[("CNN", "https://a/3"), ("Reuters", "https://b/1")]);
+
+        let comparison = QueryComparison::from_responses(&a, &b);
+
+        assert_eq!(comparison.get_total_results_a(), 3);
+        assert_eq!(comparison.get_total_results_b(), 2);
+        assert_eq!(comparison.get_overlap(), &vec!["https://a/3".to_string()]);
+        assert_eq!(
+            comparison.get_unique_a(),
+            &vec!["https://a/1".to_string(), "https://a/2".to_string()]
+        );
+        assert_eq!(comparison.get_unique_b(), &vec!["https://b/1".to_string()]);
+        assert_eq!(comparison.jaccard(), 0.25);
+
+        let sources = comparison.get_source_distribution();
+        assert_eq!(sources["BBC"].diff(), -2);
+        assert_eq!(sources["CNN"], SourceCounts { a: 1, b: 1 });
+        assert_eq!(sources["Reuters"].get_b(), 1);
+    }
+
+    #[test]
+    fn test_article_diff() {
+        let article = |title: &str, content: Option<&str>| -> Article {
+            serde_json::from_value(serde_json::json!({
+                "source": {"id": null, "name": "BBC"},
+                "title": title,
+                "description": "Description",
+                "url": "https://a/1",
+                "content": content,
+                "publishedAt": "2023-05-01T12:00:00Z"
+            }))
+            .unwrap()
+        };
+        let original = article("Titel", None);
+        let corrected = article("Title", Some("Content"));
+
+        assert!(original.diff(&original).is_empty());
+
+        let diff = original.diff(&corrected);
+        assert_eq!(diff.get_changes().len(), 2);
+        let title = diff.get(ArticleField::Title).unwrap();
+        assert_eq!(title.get_old().as_deref(), Some("Titel"));
+        assert_eq!(title.get_new().as_deref(), Some("Title"));
+        let content = diff.get(ArticleField::Content).unwrap();
+        assert_eq!(content.get_old(), &None);
+        assert!(diff.get(ArticleField::Description).is_none());
+    }
+
+    #[test]
+    fn test_from_empty_responses() {
+        let comparison = QueryComparison::from_responses(&response(&[]), &response(&[]));
+
+        assert!(comparison.get_overlap().is_empty());
+        assert_eq!(comparison.jaccard(), 0.0);
+    }
+}
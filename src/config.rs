@@ -0,0 +1,177 @@
+use crate::clock::Clock;
+use crate::error::ClientBuildError;
+use crate::rate_limit::{Limiter, RateLimiter};
+use crate::retry::{Delays, RetryOn, RetryStrategy};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// Settings of a client that can be changed while it is in use, see
+/// [`NewsApiClient::reconfigure`](crate::client::NewsApiClient::reconfigure).
+#[derive(Debug, Clone, Default)]
+pub struct RuntimeConfig {
+    retry_strategy: RetryStrategy,
+    #[cfg(feature = "backoff")]
+    backoff: Option<backoff::ExponentialBackoff>,
+    max_retries: usize,
+    retry_on: RetryOn,
+    rate_limit: Option<(u32, Duration)>,
+    /// Whether the rate limit was set since the configuration was last applied, so that an
+    /// installed `governor` limiter is replaced even when the value is unchanged.
+    rate_limit_set: bool,
+    default_page_size: Option<i32>,
+}
+
+impl RuntimeConfig {
+    pub fn retry(mut self, strategy: RetryStrategy, max_retries: usize) -> Self {
+        self.retry_strategy = strategy;
+        self.max_retries = max_retries;
+        #[cfg(feature = "backoff")]
+        {
+            self.backoff = None;
+        }
+        self
+    }
+
+    /// See [`NewsApiClientBuilder::backoff`](crate::client::NewsApiClientBuilder::backoff).
+    #[cfg(feature = "backoff")]
+    pub fn backoff(mut self, policy: backoff::ExponentialBackoff) -> Self {
+        self.backoff = Some(policy);
+        self.max_retries = usize::MAX;
+        self
+    }
+
+    pub fn retry_on(mut self, retry_on: RetryOn) -> Self {
+        self.retry_on = retry_on;
+        self
+    }
+
+    /// Replaces the client's rate limiter, including a shared `governor` limiter, with a
+    /// token bucket allowing `requests_per_window` requests per `window`.
+    pub fn rate_limit(mut self, requests_per_window: u32, window: Duration) -> Self {
+        self.rate_limit = Some((requests_per_window, window));
+        self.rate_limit_set = true;
+        self
+    }
+
+    /// Removes the client's rate limiter, including a shared `governor` limiter.
+    pub fn no_rate_limit(mut self) -> Self {
+        self.rate_limit = None;
+        self.rate_limit_set = true;
+        self
+    }
+
+    /// Page size of requests that don't set one.
+    pub fn default_page_size(mut self, page_size: i32) -> Self {
+        self.default_page_size = Some(page_size);
+        self
+    }
+
+    pub fn get_retry_strategy(&self) -> RetryStrategy {
+        self.retry_strategy
+    }
+
+    pub fn get_max_retries(&self) -> usize {
+        self.max_retries
+    }
+
+    pub fn get_retry_on(&self) -> RetryOn {
+        self.retry_on
+    }
+
+    pub fn get_rate_limit(&self) -> Option<(u32, Duration)> {
+        self.rate_limit
+    }
+
+    pub fn get_default_page_size(&self) -> Option<i32> {
+        self.default_page_size
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), ClientBuildError> {
+        if let Some((requests_per_window, window)) = self.rate_limit {
+            if requests_per_window == 0 || window.is_zero() {
+                return Err(ClientBuildError::InvalidSetting {
+                    setting: "rate limit",
+                    reason: "must allow at least one request per non-empty window",
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn delays(&self) -> Delays {
+        #[cfg(feature = "backoff")]
+        if let Some(policy) = &self.backoff {
+            return policy.clone().into();
+        }
+        self.retry_strategy.into()
+    }
+}
+
+/// A [`RuntimeConfig`] together with the rate limiter built from it.
+#[derive(Debug)]
+pub(crate) struct LiveConfig {
+    pub(crate) config: RuntimeConfig,
+    pub(crate) rate_limiter: Option<Limiter>,
+}
+
+/// The current [`LiveConfig`] of a client, shared by its clones. Requests take a snapshot,
+/// so a swap never affects a call in progress.
+#[derive(Debug)]
+pub(crate) struct ConfigCell {
+    current: RwLock<Arc<LiveConfig>>,
+}
+
+impl ConfigCell {
+    pub(crate) fn new(mut live: LiveConfig) -> Self {
+        live.config.rate_limit_set = false;
+        Self {
+            current: RwLock::new(Arc::new(live)),
+        }
+    }
+
+    pub(crate) fn load(&self) -> Arc<LiveConfig> {
+        self.current.read().unwrap().clone()
+    }
+
+    /// Applies `update` to the current configuration. The rate limiter is only rebuilt,
+    /// losing its state, when the rate limit changed, or when it was set while a `governor`
+    /// limiter is installed.
+    pub(crate) fn update(
+        &self,
+        update: impl FnOnce(RuntimeConfig) -> RuntimeConfig,
+        clock: &Arc<dyn Clock>,
+    ) -> Result<(), ClientBuildError> {
+        let mut current = self.current.write().unwrap();
+        let mut config = update(current.config.clone());
+        config.validate()?;
+        let replaces_external = config.rate_limit_set
+            && current
+                .rate_limiter
+                .as_ref()
+                .is_some_and(|limiter| !limiter.is_token_bucket());
+        let rate_limiter = if config.rate_limit == current.config.rate_limit && !replaces_external {
+            current.rate_limiter.clone()
+        } else {
+            token_bucket(config.rate_limit, clock)
+        };
+        config.rate_limit_set = false;
+        *current = Arc::new(LiveConfig {
+            config,
+            rate_limiter,
+        });
+        Ok(())
+    }
+}
+
+pub(crate) fn token_bucket(
+    rate_limit: Option<(u32, Duration)>,
+    clock: &Arc<dyn Clock>,
+) -> Option<Limiter> {
+    rate_limit.map(|(requests_per_window, window)| {
+        Limiter::TokenBucket(Arc::new(RateLimiter::new(
+            requests_per_window,
+            window,
+            clock.clone(),
+        )))
+    })
+}
@@ -0,0 +1,277 @@
+use crate::client::NewsApiClientBuilder;
+use crate::error::ConfigFileError;
+use crate::model::ArticleSortBy;
+use crate::retry::RetryStrategy;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Client settings read from a TOML file, see
+/// [`NewsApiClientBuilder::from_config_file`]. Every setting is optional, and named profiles
+/// in `[profiles.<name>]` tables override them.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct ConfigFile {
+    api_key: Option<String>,
+    api_key_envs: Option<Vec<String>>,
+    base_url: Option<String>,
+    timeout_ms: Option<u64>,
+    max_retry_duration_ms: Option<u64>,
+    retry: Option<RetryConfig>,
+    default_sort_by: Option<String>,
+    default_page_size: Option<i32>,
+    profiles: HashMap<String, ConfigFile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RetryConfig {
+    strategy: RetryStrategyName,
+    #[serde(default)]
+    delay_ms: u64,
+    max_delay_ms: Option<u64>,
+    #[serde(default)]
+    max_retries: usize,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum RetryStrategyName {
+    None,
+    Constant,
+    Linear,
+    Exponential,
+    ExponentialJitter,
+    DecorrelatedJitter,
+}
+
+impl RetryConfig {
+    fn strategy(&self) -> Result<RetryStrategy, ConfigFileError> {
+        let delay = Duration::from_millis(self.delay_ms);
+        let max_delay = |strategy: &str| {
+            self.max_delay_ms.map(Duration::from_millis).ok_or_else(|| {
+                ConfigFileError::InvalidSetting {
+                    setting: "retry.max_delay_ms",
+                    reason: format!("required for the {strategy} retry strategy"),
+                }
+            })
+        };
+        Ok(match self.strategy {
+            RetryStrategyName::None => RetryStrategy::None,
+            RetryStrategyName::Constant => RetryStrategy::Constant(delay),
+            RetryStrategyName::Linear => RetryStrategy::Linear(delay),
+            RetryStrategyName::Exponential => RetryStrategy::Exponential {
+                base: delay,
+                max: max_delay("exponential")?,
+            },
+            RetryStrategyName::ExponentialJitter => RetryStrategy::ExponentialJitter {
+                base: delay,
+                max: max_delay("exponential_jitter")?,
+            },
+            RetryStrategyName::DecorrelatedJitter => RetryStrategy::DecorrelatedJitter {
+                base: delay,
+                max: max_delay("decorrelated_jitter")?,
+            },
+        })
+    }
+}
+
+impl ConfigFile {
+    pub(crate) fn load(path: &Path) -> Result<Self, ConfigFileError> {
+        let text = std::fs::read_to_string(path).map_err(ConfigFileError::Read)?;
+        Self::parse(&text)
+    }
+
+    pub(crate) fn parse(text: &str) -> Result<Self, ConfigFileError> {
+        toml::from_str(text).map_err(ConfigFileError::Parse)
+    }
+
+    /// The settings of the named profile, falling back to the top-level settings for those
+    /// it leaves out. A profile's `[retry]` table replaces the top-level one as a whole.
+    pub(crate) fn profile(mut self, name: &str) -> Result<Self, ConfigFileError> {
+        let profile = self
+            .profiles
+            .remove(name)
+            .ok_or_else(|| ConfigFileError::MissingProfile(name.to_string()))?;
+        if !profile.profiles.is_empty() {
+            return Err(ConfigFileError::NestedProfiles(name.to_string()));
+        }
+        Ok(Self {
+            api_key: profile.api_key.or(self.api_key),
+            api_key_envs: profile.api_key_envs.or(self.api_key_envs),
+            base_url: profile.base_url.or(self.base_url),
+            timeout_ms: profile.timeout_ms.or(self.timeout_ms),
+            max_retry_duration_ms: profile.max_retry_duration_ms.or(self.max_retry_duration_ms),
+            retry: profile.retry.or(self.retry),
+            default_sort_by: profile.default_sort_by.or(self.default_sort_by),
+            default_page_size: profile.default_page_size.or(self.default_page_size),
+            profiles: HashMap::new(),
+        })
+    }
+
+    /// Applies the settings to `builder`. The API key only takes effect when none is given
+    /// explicitly or through the environment.
+    pub(crate) fn apply(
+        self,
+        mut builder: NewsApiClientBuilder,
+    ) -> Result<NewsApiClientBuilder, ConfigFileError> {
+        if let Some(api_key) = self.api_key {
+            builder = builder.config_file_api_key(api_key.into());
+        }
+        if let Some(names) = self.api_key_envs {
+            builder = builder.api_key_envs(names);
+        }
+        if let Some(base_url) = self.base_url {
+            builder = builder.base_url(&base_url);
+        }
+        if let Some(timeout) = self.timeout_ms {
+            builder = builder.timeout(Duration::from_millis(timeout));
+        }
+        if let Some(duration) = self.max_retry_duration_ms {
+            builder = builder.max_retry_duration(Duration::from_millis(duration));
+        }
+        if let Some(retry) = self.retry {
+            builder = builder.retry(retry.strategy()?, retry.max_retries);
+        }
+        if let Some(sort_by) = self.default_sort_by {
+            let sort_by =
+                ArticleSortBy::from_str(&sort_by).map_err(|_| ConfigFileError::InvalidSetting {
+                    setting: "default_sort_by",
+                    reason: format!("unknown sort order {sort_by:?}"),
+                })?;
+            builder = builder.default_sort_by(sort_by);
+        }
+        if let Some(page_size) = self.default_page_size {
+            builder = builder.default_page_size(page_size);
+        }
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ClientBuildError;
+    use serial_test::serial;
+
+    #[test]
+    fn test_parse_config_file() {
+        let config = ConfigFile::parse(
+            r#"
+            api_key = "file-api-key"
+            base_url = "https://proxy.example.com"
+            timeout_ms = 5000
+            default_sort_by = "publishedAt"
+            default_page_size = 50
+
+            [retry]
+            strategy = "exponential"
+            delay_ms = 100
+            max_delay_ms = 10000
+            max_retries = 3
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.api_key.as_deref(), Some("file-api-key"));
+        assert_eq!(config.timeout_ms, Some(5000));
+        assert!(matches!(
+            config.retry.as_ref().unwrap().strategy(),
+            Ok(RetryStrategy::Exponential { base, max })
+                if base == Duration::from_millis(100) && max == Duration::from_secs(10)
+        ));
+        assert!(config.apply(NewsApiClientBuilder::new()).is_ok());
+
+        assert!(ConfigFile::parse("api_kye = \"typo\"").is_err());
+        let invalid = ConfigFile::parse("default_sort_by = \"newest\"").unwrap();
+        assert!(invalid.apply(NewsApiClientBuilder::new()).is_err());
+        let exponential = ConfigFile::parse("[retry]\nstrategy = \"exponential\"").unwrap();
+        assert!(exponential.apply(NewsApiClientBuilder::new()).is_err());
+    }
+
+    #[test]
+    fn test_profile_overrides_top_level_settings() {
+        let text = r#"
+            api_key = "default-api-key"
+            timeout_ms = 5000
+
+            [profiles.dev]
+            base_url = "http://localhost:8080"
+
+            [profiles.backup-key]
+            api_key = "backup-api-key"
+            "#;
+
+        let dev = ConfigFile::parse(text).unwrap().profile("dev").unwrap();
+        assert_eq!(dev.api_key.as_deref(), Some("default-api-key"));
+        assert_eq!(dev.base_url.as_deref(), Some("http://localhost:8080"));
+        assert_eq!(dev.timeout_ms, Some(5000));
+
+        let backup = ConfigFile::parse(text)
+            .unwrap()
+            .profile("backup-key")
+            .unwrap();
+        assert_eq!(backup.api_key.as_deref(), Some("backup-api-key"));
+        assert_eq!(backup.base_url, None);
+
+        assert!(ConfigFile::parse(text).unwrap().profile("prod").is_err());
+    }
+
+    #[serial]
+    #[test]
+    fn test_config_file_errors_keep_their_source() {
+        use std::error::Error as _;
+
+        let path =
+            std::env::temp_dir().join(format!("newsapi-rs-config-{}.toml", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let missing = NewsApiClientBuilder::from_config_file(&path).err().unwrap();
+        assert!(matches!(
+            &missing,
+            ClientBuildError::ConfigFile { source: ConfigFileError::Read(e), .. }
+                if e.kind() == std::io::ErrorKind::NotFound
+        ));
+        assert!(missing.to_string().contains(&path.display().to_string()));
+
+        std::fs::write(&path, "api_kye = \"typo\"").unwrap();
+        let invalid = NewsApiClientBuilder::from_config_file(&path).err().unwrap();
+        let source = invalid.source().unwrap();
+        assert!(matches!(
+            source.downcast_ref::<ConfigFileError>(),
+            Some(ConfigFileError::Parse(_))
+        ));
+        assert!(source
+            .source()
+            .unwrap()
+            .downcast_ref::<toml::de::Error>()
+            .is_some());
+
+        std::fs::write(&path, "default_sort_by = \"newest\"").unwrap();
+        assert!(matches!(
+            NewsApiClientBuilder::from_config_file(&path).err().unwrap(),
+            ClientBuildError::ConfigFile {
+                source: ConfigFileError::InvalidSetting {
+                    setting: "default_sort_by",
+                    ..
+                },
+                ..
+            }
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_base_url_fails_build() {
+        let config = ConfigFile::parse("api_key = \"file-api-key\"\nbase_url = \"proxy\"").unwrap();
+        let error = config
+            .apply(NewsApiClientBuilder::new())
+            .unwrap()
+            .build()
+            .unwrap_err();
+
+        assert!(matches!(error, ClientBuildError::InvalidBaseUrl(_)));
+    }
+}
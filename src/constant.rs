@@ -1,6 +1,13 @@
 pub const NEWS_API_KEY_ENV: &str = "NEWS_API_KEY";
-pub const NEWS_API_CLIENT_USER_AGENT: &str = "newsapi-rs/0.1.0";
+pub const NEWS_API_PROFILE_ENV: &str = "NEWS_API_PROFILE";
+pub const NEWS_API_CLIENT_USER_AGENT: &str = concat!("newsapi-rs/", env!("CARGO_PKG_VERSION"));
+pub const NEWS_API_REQUEST_ID_HEADER: &str = "x-request-id";
+pub const RATE_LIMIT_REMAINING_HEADER: &str = "x-ratelimit-remaining";
 pub const NEWS_API_URI: &str = "https://newsapi.org/";
 pub const TOP_HEADLINES_ENDPOINT: &str = "/v2/top-headlines";
 pub const EVERYTHING_ENDPOINT: &str = "/v2/everything";
 pub const SOURCES_ENDPOINT: &str = "/v2/top-headlines/sources";
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+pub const MAX_PAGE_SIZE: i32 = 100;
+pub const NEWS_API_PROVIDER: &str = "newsapi";
+pub const MAX_QUERY_LENGTH: usize = 500;
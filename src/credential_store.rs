@@ -0,0 +1,42 @@
+use crate::secret::SecretString;
+use keyring::Entry;
+
+/// Service the API key is stored under in the OS credential store: the macOS Keychain, the
+/// Windows Credential Manager or the Secret Service on Linux.
+pub const KEYRING_SERVICE: &str = "newsapi-rs";
+
+/// Reads the API key stored for `account`, `None` when there is none.
+pub fn load_api_key(account: &str) -> Result<Option<SecretString>, keyring::Error> {
+    match Entry::new(KEYRING_SERVICE, account)?.get_password() {
+        Ok(api_key) => Ok(Some(api_key.into())),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Stores `api_key` for `account`, replacing any key stored before.
+pub fn store_api_key(account: &str, api_key: &SecretString) -> Result<(), keyring::Error> {
+    Entry::new(KEYRING_SERVICE, account)?.set_password(api_key.expose_secret())
+}
+
+/// Removes the API key stored for `account`, if any.
+pub fn delete_api_key(account: &str) -> Result<(), keyring::Error> {
+    match Entry::new(KEYRING_SERVICE, account)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_key_is_none() {
+        keyring::set_default_credential_builder(keyring::mock::default_credential_builder());
+
+        assert!(load_api_key("missing-account").unwrap().is_none());
+        assert!(delete_api_key("missing-account").is_ok());
+        assert!(store_api_key("account", &"test-api-key".into()).is_ok());
+    }
+}
@@ -0,0 +1,85 @@
+use crate::constant::{EVERYTHING_ENDPOINT, SOURCES_ENDPOINT, TOP_HEADLINES_ENDPOINT};
+use strum::Display;
+
+/// NewsAPI endpoints supported by the client.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash)]
+#[strum(serialize_all = "snake_case")]
+pub enum Endpoint {
+    TopHeadlines,
+    Everything,
+    Sources,
+}
+
+impl Endpoint {
+    pub const ALL: [Endpoint; 3] = [
+        Endpoint::TopHeadlines,
+        Endpoint::Everything,
+        Endpoint::Sources,
+    ];
+
+    /// Path of the endpoint under [`ApiVersion::V2`].
+    pub fn path(&self) -> &'static str {
+        match self {
+            Endpoint::TopHeadlines => TOP_HEADLINES_ENDPOINT,
+            Endpoint::Everything => EVERYTHING_ENDPOINT,
+            Endpoint::Sources => SOURCES_ENDPOINT,
+        }
+    }
+
+    /// Path of the endpoint below the version prefix.
+    pub fn relative_path(&self) -> &'static str {
+        let path = self.path();
+        &path[ApiVersion::V2.prefix().len() + 1..]
+    }
+}
+
+/// Version prefix that endpoint paths are resolved under.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum ApiVersion {
+    /// `/v2`, the current NewsAPI version
+    #[default]
+    V2,
+    /// Any other prefix, e.g. for a future API version or a proxy serving NewsAPI under
+    /// `/newsapi/v2`
+    Custom(String),
+}
+
+impl ApiVersion {
+    pub fn custom(prefix: impl Into<String>) -> Self {
+        ApiVersion::Custom(prefix.into())
+    }
+
+    /// The path prefix, without a trailing slash.
+    pub fn prefix(&self) -> &str {
+        match self {
+            ApiVersion::V2 => "/v2",
+            ApiVersion::Custom(prefix) => prefix.trim_end_matches('/'),
+        }
+    }
+
+    /// Resolves the path of `endpoint` under this version.
+    pub fn path(&self, endpoint: Endpoint) -> String {
+        format!("{}/{}", self.prefix(), endpoint.relative_path())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_paths() {
+        for endpoint in [
+            Endpoint::TopHeadlines,
+            Endpoint::Everything,
+            Endpoint::Sources,
+        ] {
+            assert_eq!(ApiVersion::V2.path(endpoint), endpoint.path());
+        }
+        assert_eq!(Endpoint::Sources.relative_path(), "top-headlines/sources");
+        assert_eq!(
+            ApiVersion::custom("/proxy/newsapi/").path(Endpoint::Everything),
+            "/proxy/newsapi/everything"
+        );
+    }
+}
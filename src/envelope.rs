@@ -0,0 +1,298 @@
+use crate::constant::NEWS_API_PROVIDER;
+use crate::model::{Article, ResponseMeta};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use reqwest::header::{
+    HeaderMap, HeaderName, AGE, CACHE_CONTROL, CONTENT_TYPE, DATE, ETAG, LAST_MODIFIED, RETRY_AFTER,
+};
+use reqwest::StatusCode;
+use std::time::Duration;
+
+/// Response headers kept in a [`ResponseEnvelope`], besides the request ID header.
+const ENVELOPE_HEADERS: [HeaderName; 7] = [
+    AGE,
+    CACHE_CONTROL,
+    CONTENT_TYPE,
+    DATE,
+    ETAG,
+    LAST_MODIFIED,
+    RETRY_AFTER,
+];
+
+/// Where an item came from and how it was obtained, for auditing data downstream.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Provenance {
+    provider: String,
+    request_key: Option<String>,
+    fetched_at: Option<DateTime<Utc>>,
+    stale: bool,
+}
+
+impl Provenance {
+    pub fn new(
+        provider: impl Into<String>,
+        request_key: Option<String>,
+        fetched_at: Option<DateTime<Utc>>,
+        stale: bool,
+    ) -> Self {
+        Self {
+            provider: provider.into(),
+            request_key,
+            fetched_at,
+            stale,
+        }
+    }
+
+    pub(crate) fn from_meta(meta: &ResponseMeta) -> Self {
+        Self::new(
+            NEWS_API_PROVIDER,
+            meta.get_request_key().cloned(),
+            meta.get_fetched_at().copied(),
+            meta.is_stale(),
+        )
+    }
+
+    /// Name of the provider that served the item, `newsapi` for this client.
+    pub fn get_provider(&self) -> &str {
+        &self.provider
+    }
+
+    /// Key of the originating request, the request URL for NewsAPI responses.
+    pub fn get_request_key(&self) -> Option<&String> {
+        self.request_key.as_ref()
+    }
+
+    pub fn get_fetched_at(&self) -> Option<&DateTime<Utc>> {
+        self.fetched_at.as_ref()
+    }
+
+    /// Whether the item was served from the cache because the API was unavailable.
+    pub fn is_stale(&self) -> bool {
+        self.stale
+    }
+}
+
+/// An item, usually an [`Article`], together with its [`Provenance`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArticleEnvelope<T = Article> {
+    item: T,
+    provenance: Provenance,
+}
+
+impl<T> ArticleEnvelope<T> {
+    pub fn new(item: T, provenance: Provenance) -> Self {
+        Self { item, provenance }
+    }
+
+    pub fn get_item(&self) -> &T {
+        &self.item
+    }
+
+    pub fn get_provenance(&self) -> &Provenance {
+        &self.provenance
+    }
+
+    pub fn into_inner(self) -> T {
+        self.item
+    }
+
+    /// Transforms the item, e.g. after enrichment, keeping its provenance.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ArticleEnvelope<U> {
+        ArticleEnvelope {
+            item: f(self.item),
+            provenance: self.provenance,
+        }
+    }
+}
+
+/// A parsed response body together with how it was obtained, for callers who need
+/// observability for individual calls without installing hooks.
+#[derive(Debug, Clone)]
+pub struct ResponseEnvelope<T> {
+    body: T,
+    latency: Duration,
+    attempts: usize,
+    status: Option<StatusCode>,
+    headers: HeaderMap,
+    raw: Bytes,
+}
+
+impl<T> ResponseEnvelope<T> {
+    pub(crate) fn new(
+        body: T,
+        attempts: usize,
+        status: Option<StatusCode>,
+        headers: HeaderMap,
+    ) -> Self {
+        Self {
+            body,
+            latency: Duration::ZERO,
+            attempts,
+            status,
+            headers,
+            raw: Bytes::new(),
+        }
+    }
+
+    /// Keeps the body exactly as it was received or cached, before parsing.
+    pub(crate) fn with_raw(mut self, raw: impl Into<Bytes>) -> Self {
+        self.raw = raw.into();
+        self
+    }
+
+    pub(crate) fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    pub fn get_body(&self) -> &T {
+        &self.body
+    }
+
+    /// Time the call took, retries and the delays between them included.
+    pub fn get_latency(&self) -> Duration {
+        self.latency
+    }
+
+    /// Number of requests sent for the call: one unless it was retried, and zero when it was
+    /// served from the cache while offline.
+    pub fn get_attempts(&self) -> usize {
+        self.attempts
+    }
+
+    /// Status of the response the body was read from, `None` when it was served from the
+    /// cache after an error or while offline.
+    pub fn get_status(&self) -> Option<StatusCode> {
+        self.status
+    }
+
+    /// Selected headers of that response: `Age`, `Cache-Control`, `Content-Type`, `Date`,
+    /// `ETag`, `Last-Modified`, `Retry-After` and the request ID header, when present.
+    pub fn get_headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    pub fn into_body(self) -> T {
+        self.body
+    }
+
+    /// Transforms the body, keeping the rest of the envelope.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ResponseEnvelope<U> {
+        ResponseEnvelope {
+            body: f(self.body),
+            latency: self.latency,
+            attempts: self.attempts,
+            status: self.status,
+            headers: self.headers,
+            raw: self.raw,
+        }
+    }
+
+    pub(crate) fn into_raw(self) -> RawResponse<T> {
+        RawResponse {
+            response: self.body,
+            bytes: self.raw,
+        }
+    }
+}
+
+/// A parsed response together with the body it was parsed from, byte for byte, for archiving
+/// exact payloads or reading fields the models don't cover.
+#[derive(Debug, Clone)]
+pub struct RawResponse<T> {
+    response: T,
+    bytes: Bytes,
+}
+
+impl<T> RawResponse<T> {
+    pub fn get_response(&self) -> &T {
+        &self.response
+    }
+
+    /// The body as received from the API, or as cached when the response was served from
+    /// the cache.
+    pub fn get_bytes(&self) -> &Bytes {
+        &self.bytes
+    }
+
+    /// Parses the body into a JSON value.
+    pub fn to_value(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::from_slice(&self.bytes)
+    }
+
+    pub fn into_parts(self) -> (T, Bytes) {
+        (self.response, self.bytes)
+    }
+}
+
+/// Copies the headers a [`ResponseEnvelope`] keeps out of a response's headers.
+pub(crate) fn select_headers(headers: &HeaderMap, request_id_header: &HeaderName) -> HeaderMap {
+    ENVELOPE_HEADERS
+        .iter()
+        .chain(std::iter::once(request_id_header))
+        .flat_map(|name| {
+            headers
+                .get_all(name)
+                .iter()
+                .map(move |value| (name.clone(), value.clone()))
+        })
+        .fold(HeaderMap::new(), |mut selected, (name, value)| {
+            selected.append(name, value);
+            selected
+        })
+}
+
+/// Wraps each article with the provenance of the response it came from.
+pub(crate) fn envelop(articles: Vec<Article>, meta: &ResponseMeta) -> Vec<ArticleEnvelope> {
+    let provenance = Provenance::from_meta(meta);
+    articles
+        .into_iter()
+        .map(|article| ArticleEnvelope::new(article, provenance.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::GetEverythingResponse;
+
+    #[test]
+    fn test_envelope_map_keeps_provenance() {
+        let provenance = Provenance::new("archive", Some("key".to_string()), None, true);
+        let envelope = ArticleEnvelope::new("Title", provenance.clone()).map(str::len);
+
+        assert_eq!(*envelope.get_item(), 5);
+        assert_eq!(envelope.get_provenance(), &provenance);
+        assert_eq!(envelope.get_provenance().get_provider(), "archive");
+    }
+
+    #[test]
+    fn test_into_enveloped_articles() {
+        let response: GetEverythingResponse = serde_json::from_str(
+            r#"{"status": "ok", "totalResults": 1, "articles": [{"source": {"id": null,
+            "name": "BBC"}, "title": "Title", "url": "https://example.com/1",
+            "publishedAt": "2023-05-01T12:00:00Z"}]}"#,
+        )
+        .unwrap();
+
+        let envelopes = response.into_enveloped_articles();
+        assert_eq!(envelopes.len(), 1);
+        assert_eq!(envelopes[0].get_item().get_title(), "Title");
+        assert_eq!(envelopes[0].get_provenance().get_provider(), "newsapi");
+        assert!(!envelopes[0].get_provenance().is_stale());
+    }
+
+    #[test]
+    fn test_select_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, "\"abc\"".parse().unwrap());
+        headers.insert("x-request-id", "req-1".parse().unwrap());
+        headers.insert("set-cookie", "session=secret".parse().unwrap());
+
+        let selected = select_headers(&headers, &HeaderName::from_static("x-request-id"));
+
+        assert_eq!(selected.len(), 2);
+        assert_eq!(selected[ETAG], "\"abc\"");
+        assert_eq!(selected["x-request-id"], "req-1");
+    }
+}
@@ -1,9 +1,16 @@
-use serde::Deserialize;
-use std::error::Error;
+use crate::model::ResponseStatus;
+use crate::redact::redact_url;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer};
 use std::fmt;
+use std::time::Duration;
+use thiserror::Error;
+use url::Url;
 
-#[derive(Debug, Deserialize, PartialEq)]
-#[serde(rename_all = "camelCase")]
+/// Bytes of a response body kept in a [`ResponseContext`].
+pub const ERROR_BODY_SNIPPET_LEN: usize = 512;
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum ApiClientErrorCode {
     ApiKeyDisabled,
     ApiKeyExhausted,
@@ -15,24 +22,160 @@ pub enum ApiClientErrorCode {
     SourcesTooMany,
     SourceDoesNotExist,
     UnexpectedError,
-    #[serde(other)]
-    Unknown,
+    /// A code this version of the client does not know, as sent by the API.
+    Unknown(String),
+}
+
+impl From<&str> for ApiClientErrorCode {
+    fn from(code: &str) -> Self {
+        match code {
+            "apiKeyDisabled" => ApiClientErrorCode::ApiKeyDisabled,
+            "apiKeyExhausted" => ApiClientErrorCode::ApiKeyExhausted,
+            "apiKeyInvalid" => ApiClientErrorCode::ApiKeyInvalid,
+            "apiKeyMissing" => ApiClientErrorCode::ApiKeyMissing,
+            "parameterInvalid" => ApiClientErrorCode::ParameterInvalid,
+            "parametersMissing" => ApiClientErrorCode::ParametersMissing,
+            "rateLimited" => ApiClientErrorCode::RateLimited,
+            "sourcesTooMany" => ApiClientErrorCode::SourcesTooMany,
+            "sourceDoesNotExist" => ApiClientErrorCode::SourceDoesNotExist,
+            "unexpectedError" => ApiClientErrorCode::UnexpectedError,
+            code => ApiClientErrorCode::Unknown(code.to_string()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ApiClientErrorCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(|code| ApiClientErrorCode::from(code.as_str()))
+    }
 }
 
 #[derive(Debug, Deserialize)]
 pub struct ApiClientErrorResponse {
-    #[allow(dead_code)]
-    pub status: String,
+    pub status: ResponseStatus,
     pub code: ApiClientErrorCode,
     pub message: String,
+    /// Correlation ID the failed request was sent with, see
+    /// [`NewsApiClientBuilder::request_id_header`](crate::client::NewsApiClientBuilder::request_id_header).
+    #[serde(skip)]
+    pub request_id: Option<String>,
+    /// The response the error was read from.
+    #[serde(skip)]
+    pub context: Option<Box<ResponseContext>>,
+}
+
+/// The request and response an error came from, for debugging failed calls.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseContext {
+    url: String,
+    status: Option<u16>,
+    body: String,
 }
 
-#[derive(Debug)]
+impl ResponseContext {
+    /// Context for a response to `url` with the first [`ERROR_BODY_SNIPPET_LEN`] bytes of
+    /// `body`. `status` is `None` for a body served from the cache.
+    pub(crate) fn new(url: &Url, status: Option<u16>, body: &str) -> Self {
+        let mut end = body.len().min(ERROR_BODY_SNIPPET_LEN);
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+        Self {
+            url: redact_url(url).to_string(),
+            status,
+            body: body[..end].to_string(),
+        }
+    }
+
+    /// The request URL, with the API key redacted.
+    pub fn get_url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn get_status(&self) -> Option<u16> {
+        self.status
+    }
+
+    /// The start of the raw response body, at most [`ERROR_BODY_SNIPPET_LEN`] bytes.
+    pub fn get_body(&self) -> &str {
+        &self.body
+    }
+}
+
+impl fmt::Display for ResponseContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.url)?;
+        match self.status {
+            Some(status) => write!(f, " (status {status})"),
+            None => write!(f, " (cached)"),
+        }
+    }
+}
+
+/// Why a call to the API failed.
+///
+/// Errors from reqwest, the transport and serde are kept as the [`Error::source`] of the
+/// variants wrapping them.
+#[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum ApiClientError {
-    Http(reqwest::Error),
+    #[error("HTTP error")]
+    Http(#[from] reqwest::Error),
+    /// A custom [`HttpTransport`](crate::transport::HttpTransport) failed to send the request
+    /// or read the response.
+    #[error("Transport error")]
+    Transport(#[source] Box<dyn std::error::Error + Send + Sync>),
+    /// The request was rejected before it was sent, e.g. a date range ending before it starts.
+    #[error("Invalid request: {0}")]
     InvalidRequest(String),
+    /// The API returned an error.
+    #[error("Invalid response: {0}")]
     InvalidResponse(ApiClientErrorResponse),
-    InvalidHeaderValue(reqwest::header::InvalidHeaderValue),
+    /// The API answered `429 Too Many Requests`, because the key is rate limited or its
+    /// quota is exhausted.
+    #[error("Rate limited: {response}")]
+    RateLimited {
+        /// How long the API asked to wait before retrying, from the `Retry-After` header.
+        retry_after: Option<Duration>,
+        /// Requests left in the current window, from the `X-RateLimit-Remaining` header.
+        remaining: Option<u32>,
+        /// The error the API returned.
+        response: Box<ApiClientErrorResponse>,
+    },
+    /// A response body could not be parsed.
+    #[error("Failed to parse response from {context}{}", at_path(path))]
+    ParseResponse {
+        context: ResponseContext,
+        /// Where in the body parsing failed, e.g. `articles[37].source`, or `.` for the body
+        /// itself.
+        path: String,
+        source: serde_json::Error,
+    },
+    #[error("Invalid header value")]
+    InvalidHeaderValue(#[from] reqwest::header::InvalidHeaderValue),
+    /// Requests are paused client-wide until the quota resets.
+    #[error("Requests paused until {0}")]
+    Paused(DateTime<Utc>),
+    /// The local [`QuotaBudget`](crate::quota::QuotaBudget) is spent until `resets_at`.
+    #[error("Quota of {limit} requests exceeded until {resets_at}")]
+    QuotaExceeded {
+        limit: u32,
+        resets_at: DateTime<Utc>,
+    },
+    /// The circuit breaker is open after repeated failures until the given time.
+    #[error("Circuit open until {0}")]
+    CircuitOpen(DateTime<Utc>),
+    /// The client is in [`OfflineMode::CacheOnly`](crate::cache::OfflineMode::CacheOnly)
+    /// and has no cached response for the request with the given key.
+    #[error("No cached response for {0} while offline")]
+    Offline(String),
+    /// A task sending one of several concurrent requests was cancelled before it finished.
+    #[error("Request task cancelled")]
+    Cancelled,
+    /// A [`Translator`](crate::translate::Translator) failed.
+    #[cfg(feature = "translate")]
+    #[error("Translation failed: {0}")]
+    Translation(String),
 }
 
 impl fmt::Display for ApiClientErrorCode {
@@ -48,38 +191,210 @@ impl fmt::Display for ApiClientErrorCode {
             ApiClientErrorCode::SourcesTooMany => write!(f, "sourcesTooMany"),
             ApiClientErrorCode::SourceDoesNotExist => write!(f, "sourceDoesNotExist"),
             ApiClientErrorCode::UnexpectedError => write!(f, "unexpectedError"),
-            ApiClientErrorCode::Unknown => write!(f, "unknown"),
+            ApiClientErrorCode::Unknown(code) => write!(f, "{code}"),
         }
     }
 }
 
-impl fmt::Display for ApiClientError {
+impl fmt::Display for ApiClientErrorResponse {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            ApiClientError::Http(err) => write!(f, "HTTP error: {err}"),
-            ApiClientError::InvalidRequest(msg) => write!(f, "Invalid request: {msg}"),
-            ApiClientError::InvalidResponse(response) => {
-                write!(
-                    f,
-                    "Invalid response: status={}, code={}, message={}",
-                    response.status, response.code, response.message
-                )
-            }
-            ApiClientError::InvalidHeaderValue(err) => write!(f, "Invalid header value: {err}"),
+        write!(
+            f,
+            "status={}, code={}, message={}",
+            self.status, self.code, self.message
+        )?;
+        match &self.request_id {
+            Some(request_id) => write!(f, ", request_id={request_id}"),
+            None => Ok(()),
         }
     }
 }
 
-impl Error for ApiClientError {}
+/// Describes where in a response body parsing failed, unless it was the body itself.
+pub(crate) fn at_path(path: &str) -> String {
+    if path == "." {
+        String::new()
+    } else {
+        format!(" at {path}")
+    }
+}
 
-impl From<reqwest::Error> for ApiClientError {
-    fn from(err: reqwest::Error) -> ApiClientError {
-        ApiClientError::Http(err)
+/// The output of a spawned request task, resuming its panic if it panicked. Tasks are only
+/// cancelled when the runtime shuts down, which is reported as
+/// [`ApiClientError::Cancelled`].
+pub(crate) fn join_output<T>(
+    joined: Result<T, tokio::task::JoinError>,
+) -> Result<T, ApiClientError> {
+    match joined {
+        Ok(output) => Ok(output),
+        Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+        Err(_) => Err(ApiClientError::Cancelled),
+    }
+}
+
+impl ApiClientError {
+    /// The error the API returned, for [`InvalidResponse`](Self::InvalidResponse) and
+    /// [`RateLimited`](Self::RateLimited).
+    pub fn get_response(&self) -> Option<&ApiClientErrorResponse> {
+        match self {
+            ApiClientError::InvalidResponse(response) => Some(response),
+            ApiClientError::RateLimited { response, .. } => Some(response),
+            _ => None,
+        }
+    }
+
+    /// Correlation ID of the request the API rejected, if IDs are enabled.
+    pub fn get_request_id(&self) -> Option<&str> {
+        self.get_response()
+            .and_then(|response| response.request_id.as_deref())
+    }
+
+    /// URL, status and start of the body of the response the error came from, for errors
+    /// returned by the API and responses that could not be parsed.
+    pub fn get_context(&self) -> Option<&ResponseContext> {
+        match self {
+            ApiClientError::ParseResponse { context, .. } => Some(context),
+            error => error
+                .get_response()
+                .and_then(|response| response.context.as_deref()),
+        }
     }
 }
 
-impl From<reqwest::header::InvalidHeaderValue> for ApiClientError {
-    fn from(err: reqwest::header::InvalidHeaderValue) -> ApiClientError {
-        ApiClientError::InvalidHeaderValue(err)
+/// Why a [`NewsApiClientBuilder`](crate::client::NewsApiClientBuilder) could not build a client.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ClientBuildError {
+    /// No API key was given explicitly, found in any of these environment variables or read
+    /// from the OS credential store or a config file.
+    #[error(
+        "API key must be provided either explicitly or via {} environment variable{}",
+        .env_vars.join(" or "),
+        if .env_vars.len() > 1 { "s" } else { "" }
+    )]
+    MissingApiKey { env_vars: Vec<String> },
+    /// A base URL could not be parsed, see
+    /// [`NewsApiClientBuilder::base_url`](crate::client::NewsApiClientBuilder::base_url).
+    #[error("Invalid base URL")]
+    InvalidBaseUrl(#[from] url::ParseError),
+    /// A setting is out of range or conflicts with another, such as a zero timeout or rate
+    /// limit.
+    #[error("Invalid {setting}: {reason}")]
+    InvalidSetting {
+        setting: &'static str,
+        reason: &'static str,
+    },
+    /// The HTTP client could not be created with the TLS settings, e.g. an invalid root
+    /// certificate.
+    #[error("Invalid TLS configuration")]
+    InvalidTls(#[source] reqwest::Error),
+    /// The HTTP client could not be created, e.g. because the TLS backend failed to
+    /// initialise.
+    #[error("Failed to create the HTTP client")]
+    HttpClient(#[source] reqwest::Error),
+    /// A config file could not be read or holds invalid settings.
+    #[cfg(feature = "config-file")]
+    #[error("Failed to load config file {}", .path.display())]
+    ConfigFile {
+        path: std::path::PathBuf,
+        source: ConfigFileError,
+    },
+    /// The OS credential store could not be read.
+    #[cfg(feature = "keyring")]
+    #[error("Failed to read API key from the OS credential store")]
+    CredentialStore(#[source] keyring::Error),
+}
+
+/// Why a config file could not be used, see [`ClientBuildError::ConfigFile`].
+#[cfg(feature = "config-file")]
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ConfigFileError {
+    /// The file could not be read.
+    #[error("Failed to read the file")]
+    Read(#[source] std::io::Error),
+    /// The file is not valid TOML or has unknown settings.
+    #[error("Failed to parse the file")]
+    Parse(#[source] toml::de::Error),
+    /// The selected profile is not defined in the file.
+    #[error("No profile {0:?}")]
+    MissingProfile(String),
+    /// A profile defines profiles of its own.
+    #[error("Profile {0:?} cannot define profiles")]
+    NestedProfiles(String),
+    /// A setting has a value the client does not accept.
+    #[error("Invalid {setting}: {reason}")]
+    InvalidSetting {
+        setting: &'static str,
+        reason: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as _;
+
+    #[test]
+    fn test_sources_are_chained() {
+        let url = Url::parse("https://newsapi.org/v2/everything?apiKey=secret-key").unwrap();
+        let error = ApiClientError::ParseResponse {
+            context: ResponseContext::new(&url, Some(200), "not json"),
+            path: ".".to_string(),
+            source: serde_json::from_str::<u32>("not json").unwrap_err(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Failed to parse response from https://newsapi.org/v2/everything?apiKey=REDACTED \
+             (status 200)"
+        );
+        assert!(error.source().unwrap().is::<serde_json::Error>());
+
+        let error = ClientBuildError::from(Url::parse("not a url").unwrap_err());
+        assert_eq!(error.to_string(), "Invalid base URL");
+        assert_eq!(
+            error.source().unwrap().to_string(),
+            "relative URL without a base"
+        );
+    }
+
+    #[test]
+    fn test_unknown_error_code_is_kept() {
+        let response: ApiClientErrorResponse = serde_json::from_str(
+            r#"{"status": "error", "code": "maximumResultsReached", "message": "Too deep"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            response.code,
+            ApiClientErrorCode::Unknown("maximumResultsReached".to_string())
+        );
+        assert_eq!(response.code.to_string(), "maximumResultsReached");
+        assert_eq!(
+            ApiClientErrorCode::from("rateLimited"),
+            ApiClientErrorCode::RateLimited
+        );
+    }
+
+    #[test]
+    fn test_missing_api_key_message() {
+        let error = ClientBuildError::MissingApiKey {
+            env_vars: vec!["NEWS_API_KEY".to_string(), "NEWSAPI_KEY".to_string()],
+        };
+        assert_eq!(
+            error.to_string(),
+            "API key must be provided either explicitly or via NEWS_API_KEY or NEWSAPI_KEY \
+             environment variables"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_task_is_reported() {
+        let task = tokio::spawn(std::future::pending::<()>());
+        task.abort();
+
+        assert!(matches!(
+            join_output(task.await),
+            Err(ApiClientError::Cancelled)
+        ));
     }
 }
@@ -0,0 +1,88 @@
+use crate::error::{ApiClientError, ApiClientErrorCode};
+
+/// Outcome of a [`NewsApiClient::ping`](crate::client::NewsApiClient::ping), for startup
+/// checks and readiness probes.
+#[derive(Debug)]
+pub enum Health {
+    /// The API answered and accepted the key.
+    Ok,
+    /// The API rejected the key as missing, invalid or disabled.
+    InvalidKey,
+    /// The key is rate limited or exhausted, or the client paused requests or spent its
+    /// [`QuotaBudget`](crate::quota::QuotaBudget).
+    RateLimited,
+    /// The API could not be reached, the circuit breaker is open or the client is offline.
+    NetworkDown,
+    /// Any other failure, such as an unexpected error from the API.
+    Failed(ApiClientError),
+}
+
+impl Health {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Health::Ok)
+    }
+
+    pub(crate) fn from_result<R>(result: Result<R, ApiClientError>) -> Self {
+        let error = match result {
+            Ok(_) => return Health::Ok,
+            Err(error) => error,
+        };
+        match &error {
+            ApiClientError::RateLimited { .. } => Health::RateLimited,
+            ApiClientError::InvalidResponse(response) => match response.code {
+                ApiClientErrorCode::ApiKeyMissing
+                | ApiClientErrorCode::ApiKeyInvalid
+                | ApiClientErrorCode::ApiKeyDisabled => Health::InvalidKey,
+                ApiClientErrorCode::RateLimited | ApiClientErrorCode::ApiKeyExhausted => {
+                    Health::RateLimited
+                }
+                _ => Health::Failed(error),
+            },
+            ApiClientError::Paused(_) | ApiClientError::QuotaExceeded { .. } => Health::RateLimited,
+            ApiClientError::Http(_)
+            | ApiClientError::Transport(_)
+            | ApiClientError::CircuitOpen(_)
+            | ApiClientError::Offline(_) => Health::NetworkDown,
+            _ => Health::Failed(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ApiClientErrorResponse;
+    use crate::model::ResponseStatus;
+    use chrono::Utc;
+
+    fn api_error(code: ApiClientErrorCode) -> ApiClientError {
+        ApiClientError::InvalidResponse(ApiClientErrorResponse {
+            status: ResponseStatus::Error,
+            code,
+            message: String::new(),
+            request_id: None,
+            context: None,
+        })
+    }
+
+    #[test]
+    fn test_from_result() {
+        assert!(Health::from_result(Ok(())).is_ok());
+        assert!(matches!(
+            Health::from_result::<()>(Err(api_error(ApiClientErrorCode::ApiKeyInvalid))),
+            Health::InvalidKey
+        ));
+        assert!(matches!(
+            Health::from_result::<()>(Err(api_error(ApiClientErrorCode::RateLimited))),
+            Health::RateLimited
+        ));
+        assert!(matches!(
+            Health::from_result::<()>(Err(ApiClientError::CircuitOpen(Utc::now()))),
+            Health::NetworkDown
+        ));
+        assert!(matches!(
+            Health::from_result::<()>(Err(api_error(ApiClientErrorCode::UnexpectedError))),
+            Health::Failed(_)
+        ));
+    }
+}
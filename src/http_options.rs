@@ -0,0 +1,84 @@
+use crate::error::ClientBuildError;
+#[cfg(feature = "__tls")]
+use reqwest::Certificate;
+use std::time::Duration;
+
+/// Settings for the HTTP client a builder creates, see
+/// [`NewsApiClientBuilder::pool_max_idle_per_host`](crate::client::NewsApiClientBuilder::pool_max_idle_per_host).
+/// Unset ones keep reqwest's defaults.
+#[derive(Debug, Default)]
+pub(crate) struct HttpClientOptions {
+    pub(crate) pool_max_idle_per_host: Option<usize>,
+    pub(crate) pool_idle_timeout: Option<Duration>,
+    pub(crate) http2_keep_alive_interval: Option<Duration>,
+    pub(crate) http2_keep_alive_timeout: Option<Duration>,
+    pub(crate) http2_keep_alive_while_idle: bool,
+    #[cfg(feature = "__tls")]
+    pub(crate) root_certificates: Vec<Certificate>,
+    #[cfg(feature = "__tls")]
+    pub(crate) accept_invalid_certs: bool,
+}
+
+/// Applies the options to either reqwest client builder, which share method names but no trait.
+macro_rules! apply_options {
+    ($options:expr, $builder:expr) => {{
+        let options = $options;
+        let mut builder = $builder;
+        if let Some(max) = options.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = options.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(interval) = options.http2_keep_alive_interval {
+            builder = builder.http2_keep_alive_interval(interval);
+        }
+        if let Some(timeout) = options.http2_keep_alive_timeout {
+            builder = builder.http2_keep_alive_timeout(timeout);
+        }
+        builder = builder.http2_keep_alive_while_idle(options.http2_keep_alive_while_idle);
+        #[cfg(feature = "__tls")]
+        {
+            builder = builder
+                .tls_certs_merge(options.root_certificates)
+                .tls_danger_accept_invalid_certs(options.accept_invalid_certs);
+        }
+        builder
+    }};
+}
+
+impl HttpClientOptions {
+    /// Whether any option is set, which only the client created by the builder can honor.
+    pub(crate) fn is_set(&self) -> bool {
+        let is_set = self.pool_max_idle_per_host.is_some()
+            || self.pool_idle_timeout.is_some()
+            || self.http2_keep_alive_interval.is_some()
+            || self.http2_keep_alive_timeout.is_some()
+            || self.http2_keep_alive_while_idle;
+        #[cfg(feature = "__tls")]
+        let is_set = is_set || !self.root_certificates.is_empty() || self.accept_invalid_certs;
+        is_set
+    }
+
+    /// Wraps a failure to create the client with these options, blaming the TLS settings
+    /// when any were given.
+    pub(crate) fn build_error(&self) -> fn(reqwest::Error) -> ClientBuildError {
+        #[cfg(feature = "__tls")]
+        if !self.root_certificates.is_empty() || self.accept_invalid_certs {
+            return ClientBuildError::InvalidTls;
+        }
+        ClientBuildError::HttpClient
+    }
+
+    pub(crate) fn apply(self, builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        apply_options!(self, builder)
+    }
+
+    #[cfg(feature = "blocking")]
+    pub(crate) fn apply_blocking(
+        self,
+        builder: reqwest::blocking::ClientBuilder,
+    ) -> reqwest::blocking::ClientBuilder {
+        apply_options!(self, builder)
+    }
+}
@@ -0,0 +1,143 @@
+use crate::error::ApiClientError;
+use crate::transport::{HttpRequest, HttpResponse, HttpTransport};
+use http_body_util::{BodyExt, Full};
+use hyper::body::Bytes;
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use hyper_util::client::legacy::connect::HttpConnector;
+use hyper_util::client::legacy::Client;
+use hyper_util::rt::TokioExecutor;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+
+/// The hyper client a [`HyperTransport`] sends its requests with.
+pub type HyperClient = Client<HttpsConnector<HttpConnector>, Full<Bytes>>;
+
+/// [`HttpTransport`] built directly on hyper and rustls, for a smaller dependency footprint
+/// and lower-level control over connections than reqwest gives.
+///
+/// Unlike reqwest it does not read proxy settings from the environment. Pass it to
+/// [`NewsApiClientBuilder::build_with_transport`].
+///
+/// [`NewsApiClientBuilder::build_with_transport`]:
+///     crate::client::NewsApiClientBuilder::build_with_transport
+#[derive(Clone)]
+pub struct HyperTransport {
+    client: HyperClient,
+}
+
+impl HyperTransport {
+    /// A transport trusting the system's root certificates, speaking HTTP/1.1 or HTTP/2 as
+    /// negotiated. Fails if the root certificates cannot be loaded.
+    pub fn new() -> io::Result<Self> {
+        let connector = HttpsConnectorBuilder::new()
+            .with_native_roots()?
+            .https_or_http()
+            .enable_http1()
+            .enable_http2()
+            .build();
+        Ok(Self::with_client(
+            Client::builder(TokioExecutor::new()).build(connector),
+        ))
+    }
+
+    /// A transport sending requests with `client`, e.g. one with its own connector, pool or
+    /// HTTP/2 settings.
+    pub fn with_client(client: HyperClient) -> Self {
+        Self { client }
+    }
+}
+
+impl fmt::Debug for HyperTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HyperTransport").finish_non_exhaustive()
+    }
+}
+
+impl HttpTransport for HyperTransport {
+    fn send(
+        &self,
+        request: HttpRequest,
+    ) -> Pin<Box<dyn Future<Output = Result<HttpResponse, ApiClientError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut builder = hyper::Request::builder()
+                .method(request.get_method().clone())
+                .uri(request.get_url());
+            if let Some(headers) = builder.headers_mut() {
+                headers.extend(request.get_headers().clone());
+            }
+            let hyper_request = builder
+                .body(Full::new(Bytes::new()))
+                .map_err(transport_error)?;
+
+            let exchange = async {
+                let response = self
+                    .client
+                    .request(hyper_request)
+                    .await
+                    .map_err(transport_error)?;
+                let (parts, body) = response.into_parts();
+                let body = body.collect().await.map_err(transport_error)?.to_bytes();
+                Ok(HttpResponse::new(parts.status, parts.headers, body.into()))
+            };
+            match request.get_timeout() {
+                Some(timeout) => tokio::time::timeout(timeout, exchange).await.map_err(|_| {
+                    transport_error(io::Error::new(io::ErrorKind::TimedOut, "request timed out"))
+                })?,
+                None => exchange.await,
+            }
+        })
+    }
+}
+
+fn transport_error(error: impl Into<Box<dyn Error + Send + Sync>>) -> ApiClientError {
+    ApiClientError::Transport(error.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::NewsApiClient;
+    use crate::model::GetSourcesRequest;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_sends_request_with_hyper() {
+        let mut server = mockito::Server::new_async().await;
+        let m = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .match_header("authorization", "Bearer test-api-key")
+            .with_status(200)
+            .with_body(r#"{"status":"ok","sources":[]}"#)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .build_with_transport(HyperTransport::new().unwrap())
+            .unwrap();
+
+        let result = client
+            .get_sources(&GetSourcesRequest::builder().build())
+            .await;
+
+        assert!(result.is_ok());
+        m.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_times_out() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let request =
+            HttpRequest::new(hyper::Method::GET, url).with_timeout(Some(Duration::from_millis(50)));
+
+        let result = HyperTransport::new().unwrap().send(request).await;
+
+        assert!(matches!(result, Err(ApiClientError::Transport(_))));
+    }
+}
@@ -0,0 +1,121 @@
+use crate::error::{ApiClientError, ApiClientErrorCode};
+use crate::secret::SecretString;
+use chrono::{DateTime, Utc};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// How long an exhausted or rate-limited key is skipped when the API sends no `Retry-After`.
+pub(crate) const DEFAULT_KEY_COOL_DOWN: Duration = Duration::from_secs(60 * 60);
+
+/// API keys of a client in failover order, each cooling down after the API reported it
+/// exhausted or rate limited.
+pub(crate) struct KeyRing {
+    keys: Vec<SecretString>,
+    cooling_until: Mutex<Vec<Option<DateTime<Utc>>>>,
+    cool_down: chrono::Duration,
+}
+
+impl KeyRing {
+    pub(crate) fn new(
+        primary: SecretString,
+        backups: Vec<SecretString>,
+        cool_down: Duration,
+    ) -> Self {
+        let mut keys = vec![primary];
+        keys.extend(backups);
+        Self {
+            cooling_until: Mutex::new(vec![None; keys.len()]),
+            keys,
+            cool_down: chrono::Duration::from_std(cool_down).unwrap_or(chrono::Duration::MAX),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn primary(&self) -> &str {
+        self.keys[0].expose_secret()
+    }
+
+    pub(crate) fn has_backups(&self) -> bool {
+        self.keys.len() > 1
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// The first key not cooling down at `now` and its index, or [`ApiClientError::Paused`]
+    /// until the first one is usable again.
+    pub(crate) fn active(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<(usize, &SecretString), ApiClientError> {
+        let cooling_until = self.cooling_until.lock().unwrap();
+        match cooling_until
+            .iter()
+            .position(|until| until.is_none_or(|until| until <= now))
+        {
+            Some(index) => Ok((index, &self.keys[index])),
+            None => Err(ApiClientError::Paused(
+                cooling_until.iter().flatten().min().copied().unwrap_or(now),
+            )),
+        }
+    }
+
+    /// Skips the key at `index` until `retry_after`, or for the cool-down period.
+    pub(crate) fn cool_down(
+        &self,
+        index: usize,
+        retry_after: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) {
+        let until = retry_after.unwrap_or_else(|| {
+            now.checked_add_signed(self.cool_down)
+                .unwrap_or(DateTime::<Utc>::MAX_UTC)
+        });
+        log::warn!("API key #{index} is cooling down until {until}");
+        self.cooling_until.lock().unwrap()[index] = Some(until);
+    }
+}
+
+/// Whether `error` reports that the key used is exhausted or rate limited.
+pub(crate) fn is_key_exhausted(error: &ApiClientError) -> bool {
+    match error {
+        ApiClientError::RateLimited { .. } => true,
+        ApiClientError::InvalidResponse(response) => matches!(
+            response.code,
+            ApiClientErrorCode::ApiKeyExhausted | ApiClientErrorCode::RateLimited
+        ),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fails_over_in_order_and_recovers() {
+        let keys = KeyRing::new(
+            "primary".into(),
+            vec!["backup".into()],
+            Duration::from_secs(60),
+        );
+        let now = Utc::now();
+        assert_eq!(keys.active(now).unwrap().0, 0);
+
+        keys.cool_down(0, None, now);
+        assert_eq!(keys.active(now).unwrap().0, 1);
+
+        let retry_after = now + chrono::Duration::seconds(30);
+        keys.cool_down(1, Some(retry_after), now);
+        assert!(matches!(
+            keys.active(now),
+            Err(ApiClientError::Paused(until)) if until == retry_after
+        ));
+        assert_eq!(keys.active(retry_after).unwrap().0, 1);
+        assert_eq!(
+            keys.active(now + chrono::Duration::seconds(60)).unwrap().0,
+            0
+        );
+    }
+}
@@ -7,11 +7,55 @@
 //!
 //! - Async client as the default implementation
 //! - Optional blocking client available with the `blocking` feature
+//! - Pluggable HTTP transport for sending the async client's requests with a backend other
+//!   than reqwest, including a lighter one built directly on hyper (`hyper-transport` feature)
+//! - TLS through rustls (`rustls-tls` feature, default) or the platform's library
+//!   (`native-tls` or `native-tls-vendored` feature)
 //! - Support for all NewsAPI endpoints (top headlines, everything, sources)
+//! - Health check for startup checks and readiness probes
 //! - Strongly typed request and response models
 //! - Builder patterns for easy request construction
-//! - Automatic API key detection from environment variables
-//! - Configurable retry mechanisms with different strategies
+//! - Client profiles for interactive, backfill and low-quota deployments
+//! - Client settings loaded from a TOML file (`config-file` feature)
+//! - API keys read from the OS credential store (`keyring` feature)
+//! - Automatic API key detection from environment variables, with keys zeroized on drop and
+//!   redacted from debug output
+//! - Configurable log redaction of query parameters such as search terms, and of response
+//!   content in logged errors
+//! - Configurable retry mechanisms with different strategies, or `backoff` crate policies
+//!   (`backoff` feature), and a pluggable sleeper for testing them without waiting
+//! - Pluggable clock driving retries, rate limiting, cache TTLs, quotas and the circuit
+//!   breaker, with a manual clock for testing time-dependent behavior
+//! - Client-wide pause when the API reports an exhausted or rate-limited key, or failover to
+//!   backup keys
+//! - Optional client-side rate limiting shared across all endpoints
+//! - Circuit breaker that fails fast after repeated failures
+//! - OpenTelemetry client spans for each request, with trace context injected into the
+//!   request headers (`opentelemetry` feature)
+//! - Request, error and retry counters and latency histograms through the `metrics` facade
+//!   (`metrics` feature)
+//! - Response envelopes with each call's latency, attempts, status code and selected headers
+//! - Raw response bodies alongside the parsed responses for archiving exact payloads
+//! - Periodic request log summaries in place of per-request logs for high-volume pollers
+//! - Per-endpoint serving of stale cached responses while the API is unavailable, and an
+//!   offline mode serving from the cache only
+//! - Conditional requests with `ETag`/`Last-Modified` revalidation for cheaper polling
+//! - Body and transfer sizes in response metadata for bandwidth accounting
+//! - Unknown response fields kept for forward compatibility (`extra-fields` feature)
+//! - Pagination and quota-saving sampling of everything search results
+//! - Article summaries synthesized from descriptions and truncated content
+//! - Side-by-side comparison of two everything queries
+//! - Field-level diffs between versions of an article for tracking corrections
+//! - Linting of everything queries before they spend quota
+//! - Per-article provenance envelopes for auditing where results came from
+//! - Regional top headline bundles fanned out over their countries
+//! - Source catalog with optional homepage liveness probing (`probe` feature), cached for a
+//!   configurable TTL and backed by a compiled-in snapshot (`embedded-sources` feature)
+//! - `newsapi-mock` server with generated data for end-to-end tests (`mock-server` feature)
+//! - Seeded, reproducible fake articles for fixtures and snapshot tests (`test-support` feature)
+//! - Translation hook for article titles and descriptions (`translate` feature)
+//! - `tower::Service` implementations for composing tower middleware around requests
+//!   (`tower` feature)
 //!
 //! ## Endpoints
 //!
@@ -118,7 +162,13 @@
 //! async fn main() {
 //!     // Exponential backoff strategy
 //!     let client = NewsApiClient::builder()
-//!         .retry(RetryStrategy::Exponential(Duration::from_millis(100)), 3)
+//!         .retry(
+//!             RetryStrategy::Exponential {
+//!                 base: Duration::from_millis(100),
+//!                 max: Duration::from_secs(10),
+//!             },
+//!             3,
+//!         )
 //!         .build()
 //!         .expect("Failed to build client");
 //!
@@ -136,19 +186,91 @@
 //! }
 //! ```
 
+pub mod auth;
+pub mod cache;
+pub mod catalog;
+pub mod circuit_breaker;
 pub mod client;
+pub mod clock;
+pub mod compare;
+pub mod config;
+#[cfg(feature = "config-file")]
+mod config_file;
 pub mod constant;
+#[cfg(feature = "keyring")]
+pub mod credential_store;
+pub mod endpoint;
+pub mod envelope;
 pub mod error;
+pub mod health;
+mod http_options;
+#[cfg(feature = "hyper-transport")]
+pub mod hyper_transport;
+mod keys;
+mod log_summary;
+#[cfg(feature = "mock-server")]
+pub mod mock_server;
 pub mod model;
+#[cfg(feature = "opentelemetry")]
+pub mod otel;
+pub mod paginate;
+pub mod profile;
+pub mod query;
+pub mod quota;
+mod rate_limit;
+mod redact;
+pub mod region;
+#[cfg(feature = "metrics")]
+pub mod request_metrics;
 pub mod retry;
+pub mod secret;
+#[cfg(feature = "tower")]
+pub mod service;
+pub mod sleep;
+pub mod summary;
+#[cfg(feature = "test-support")]
+pub mod test_support;
+#[cfg(feature = "translate")]
+pub mod translate;
+pub mod transport;
 
-pub use client::NewsApiClient;
-pub use error::{ApiClientError, ApiClientErrorCode, ApiClientErrorResponse};
+pub use auth::AuthMode;
+pub use cache::{CachePolicy, OfflineMode};
+pub use catalog::{CatalogEntry, Liveness, SourceCatalog};
+pub use circuit_breaker::CircuitState;
+pub use client::{NewsApiClient, PreviewRequest};
+pub use clock::{Clock, ManualClock, SystemClock};
+pub use compare::{ArticleDiff, ArticleField, FieldChange, QueryComparison};
+pub use config::RuntimeConfig;
+pub use endpoint::{ApiVersion, Endpoint};
+pub use envelope::{ArticleEnvelope, Provenance, RawResponse, ResponseEnvelope};
+pub use error::{
+    ApiClientError, ApiClientErrorCode, ApiClientErrorResponse, ClientBuildError, ResponseContext,
+};
+pub use health::Health;
 pub use model::{
-    GetEverythingRequest, GetEverythingResponse, GetSourcesRequest, GetSourcesResponse,
-    GetTopHeadlinesRequest, Source, TopHeadlinesResponse,
+    ArticleWarning, DateRange, GetEverythingRequest, GetEverythingResponse, GetSourcesRequest,
+    GetSourcesResponse, GetTopHeadlinesRequest, Normalization, PageSizePolicy, ResponseMeta,
+    ResponseStatus, Source, Strictness, TopHeadlinesResponse,
 };
-pub use retry::{retry, RetryStrategy};
+pub use paginate::{EverythingPaginator, PagePlan, SampleStrategy};
+pub use profile::Profile;
+pub use query::{MatchField, Query, QueryWarning, TermMatch};
+pub use quota::{FileQuotaStore, QuotaBudget, QuotaStatus, QuotaStore};
+pub use redact::LogRedaction;
+pub use region::Region;
+pub use retry::{retry, retry_if, RetryAttempt, RetryOn, RetryStrategy};
+pub use secret::SecretString;
+pub use sleep::{InstantSleeper, Sleeper, TokioSleeper};
+pub use transport::{HttpRequest, HttpResponse, HttpTransport};
 
+#[cfg(feature = "config-file")]
+pub use error::ConfigFileError;
+#[cfg(feature = "hyper-transport")]
+pub use hyper_transport::HyperTransport;
+#[cfg(feature = "blocking")]
+pub use paginate::BlockingEverythingPaginator;
+#[cfg(feature = "blocking")]
+pub use retry::{retry_blocking, retry_blocking_if};
 #[cfg(feature = "blocking")]
-pub use retry::retry_blocking;
+pub use transport::BlockingHttpTransport;
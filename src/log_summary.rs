@@ -0,0 +1,174 @@
+use crate::endpoint::Endpoint;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Aggregates requests into one summary per endpoint and interval, logged at info level in
+/// place of the per-request debug logs, see
+/// [`NewsApiClientBuilder::log_summary_interval`](crate::client::NewsApiClientBuilder::log_summary_interval).
+///
+/// A summary is logged by the first request completing after the interval has elapsed, so
+/// nothing is logged while the client is idle.
+pub(crate) struct RequestLog {
+    interval: Duration,
+    windows: Mutex<HashMap<Endpoint, Window>>,
+}
+
+struct Window {
+    started: Instant,
+    latencies: Vec<Duration>,
+    errors: usize,
+}
+
+impl Window {
+    fn new(started: Instant) -> Self {
+        Self {
+            started,
+            latencies: Vec::new(),
+            errors: 0,
+        }
+    }
+}
+
+impl RequestLog {
+    pub(crate) fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            windows: Mutex::default(),
+        }
+    }
+
+    /// Records a request to `endpoint` that took `latency`, logging the summary of the
+    /// endpoint's window if it is over. A window starts with the first request after the last.
+    pub(crate) fn record(
+        &self,
+        endpoint: Endpoint,
+        latency: Duration,
+        success: bool,
+        now: Instant,
+    ) {
+        if let Some(summary) = self.add(endpoint, latency, success, now) {
+            log::info!("{summary}");
+        }
+    }
+
+    /// Requests to `endpoint` recorded in the current window.
+    #[cfg(test)]
+    pub(crate) fn pending(&self, endpoint: Endpoint) -> usize {
+        self.windows
+            .lock()
+            .unwrap()
+            .get(&endpoint)
+            .map_or(0, |window| window.latencies.len())
+    }
+
+    fn add(
+        &self,
+        endpoint: Endpoint,
+        latency: Duration,
+        success: bool,
+        now: Instant,
+    ) -> Option<RequestSummary> {
+        let mut windows = self.windows.lock().unwrap();
+        let window = windows.entry(endpoint).or_insert_with(|| Window::new(now));
+        window.latencies.push(latency);
+        if !success {
+            window.errors += 1;
+        }
+        let elapsed = now.saturating_duration_since(window.started);
+        if elapsed < self.interval {
+            return None;
+        }
+        let window = windows.remove(&endpoint)?;
+        Some(RequestSummary::new(endpoint, window, elapsed))
+    }
+}
+
+/// Requests completed within a window of the [`RequestLog`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RequestSummary {
+    endpoint: Endpoint,
+    elapsed: Duration,
+    requests: usize,
+    errors: usize,
+    p50: Duration,
+    p95: Duration,
+}
+
+impl RequestSummary {
+    fn new(endpoint: Endpoint, mut window: Window, elapsed: Duration) -> Self {
+        window.latencies.sort_unstable();
+        Self {
+            endpoint,
+            elapsed,
+            requests: window.latencies.len(),
+            errors: window.errors,
+            p50: percentile(&window.latencies, 50),
+            p95: percentile(&window.latencies, 95),
+        }
+    }
+}
+
+impl fmt::Display for RequestSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} requests in the last {}s, p50 {}ms, p95 {}ms, {} errors",
+            self.endpoint,
+            self.requests,
+            self.elapsed.as_secs(),
+            self.p50.as_millis(),
+            self.p95.as_millis(),
+            self.errors
+        )
+    }
+}
+
+/// Nearest-rank percentile of sorted `latencies`.
+fn percentile(latencies: &[Duration], percent: usize) -> Duration {
+    match latencies.len() {
+        0 => Duration::ZERO,
+        len => latencies[(len * percent).div_ceil(100).max(1) - 1],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarizes_each_interval() {
+        let start = Instant::now();
+        let log = RequestLog::new(Duration::from_secs(60));
+        let at = |secs| start + Duration::from_secs(secs);
+
+        for millis in 1..=19 {
+            let latency = Duration::from_millis(millis);
+            assert_eq!(
+                log.add(Endpoint::Everything, latency, true, at(millis)),
+                None
+            );
+        }
+        let latency = Duration::from_millis(30);
+        assert_eq!(log.add(Endpoint::Sources, latency, true, at(20)), None);
+
+        let summary = log
+            .add(
+                Endpoint::Everything,
+                Duration::from_millis(500),
+                false,
+                at(62),
+            )
+            .unwrap();
+        assert_eq!(
+            summary.to_string(),
+            "everything: 20 requests in the last 61s, p50 10ms, p95 19ms, 1 errors"
+        );
+        assert_eq!(log.pending(Endpoint::Everything), 0);
+
+        let next = log.add(Endpoint::Sources, latency, true, at(80)).unwrap();
+        assert_eq!((next.requests, next.errors), (2, 0));
+        assert_eq!(next.p95, latency);
+    }
+}
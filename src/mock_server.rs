@@ -0,0 +1,467 @@
+use crate::constant::MAX_PAGE_SIZE;
+use crate::endpoint::{ApiVersion, Endpoint};
+use crate::test_support::{source_json, ArticleFaker, SourceRow, SOURCES};
+use chrono::{DateTime, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::{json, Value};
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+use tokio::task::JoinHandle;
+use url::Url;
+
+const DEFAULT_TOTAL_RESULTS: usize = 250;
+
+/// What the mock server serves, see [`MockServer`].
+#[derive(Debug, Clone)]
+pub struct MockServerConfig {
+    seed: u64,
+    total_results: usize,
+    fail_with: Option<String>,
+    failure_rate: f64,
+    published_between: Option<(DateTime<Utc>, DateTime<Utc>)>,
+}
+
+impl Default for MockServerConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            total_results: DEFAULT_TOTAL_RESULTS,
+            fail_with: None,
+            failure_rate: 0.0,
+            published_between: None,
+        }
+    }
+}
+
+impl MockServerConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seed of the generated data and injected failures. The same seed and requests always
+    /// produce the same responses.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Number of articles matching each query, 250 by default.
+    pub fn total_results(mut self, total_results: usize) -> Self {
+        self.total_results = total_results;
+        self
+    }
+
+    /// Range the publication times of each query's articles are spread over, see
+    /// [`ArticleFaker::published_between`].
+    pub fn published_between(mut self, from: DateTime<Utc>, to: DateTime<Utc>) -> Self {
+        self.published_between = Some((from, to));
+        self
+    }
+
+    /// Answers every request with the given NewsAPI error code, e.g. `rateLimited`.
+    pub fn fail_with(mut self, code: impl Into<String>) -> Self {
+        self.fail_with = Some(code.into());
+        self
+    }
+
+    /// Share of requests, between 0 and 1, answered with an `unexpectedError`.
+    pub fn failure_rate(mut self, failure_rate: f64) -> Self {
+        self.failure_rate = failure_rate.clamp(0.0, 1.0);
+        self
+    }
+}
+
+/// A local HTTP server imitating NewsAPI with generated data, for end-to-end tests without a
+/// real API key.
+///
+/// All three endpoints honour their filters and paging parameters and reject requests without
+/// an API key or required parameters the way NewsAPI does. The server stops when dropped.
+#[derive(Debug)]
+pub struct MockServer {
+    addr: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Starts a server on a free local port.
+    pub async fn start(config: MockServerConfig) -> io::Result<Self> {
+        Self::bind("127.0.0.1:0", config).await
+    }
+
+    pub async fn bind(addr: impl ToSocketAddrs, config: MockServerConfig) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        let addr = listener.local_addr()?;
+        let task = tokio::spawn(serve(listener, config));
+        Ok(Self { addr, task })
+    }
+
+    pub fn get_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Base URL to pass to [`NewsApiClientBuilder::base_url`](crate::client::NewsApiClientBuilder::base_url).
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Serves requests until the task is aborted.
+    pub async fn wait(mut self) {
+        let _ = (&mut self.task).await;
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn serve(listener: TcpListener, config: MockServerConfig) {
+    let config = Arc::new(config);
+    let requests = Arc::new(AtomicU64::new(0));
+    loop {
+        let (stream, _) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(e) => {
+                log::warn!("Mock server failed to accept a connection: {e}");
+                continue;
+            }
+        };
+        let config = config.clone();
+        let request_number = requests.fetch_add(1, Ordering::Relaxed);
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, &config, request_number).await {
+                log::warn!("Mock server connection failed: {e}");
+            }
+        });
+    }
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    config: &MockServerConfig,
+    request_number: u64,
+) -> io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+
+    let mut has_api_key = false;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).await? == 0 || header.trim().is_empty() {
+            break;
+        }
+        let name = header.split(':').next().unwrap_or_default().trim();
+        if name.eq_ignore_ascii_case("authorization") || name.eq_ignore_ascii_case("x-api-key") {
+            has_api_key = true;
+        }
+    }
+
+    let target = request_line.split_whitespace().nth(1).unwrap_or("/");
+    let (status, body) = match Url::parse(&format!("http://localhost{target}")) {
+        Ok(url) => respond(config, request_number, &url, has_api_key),
+        Err(_) => error(400, "parameterInvalid", "The request could not be parsed."),
+    };
+    log::debug!("Mock server {} -> {status}", request_line.trim());
+
+    let body = body.to_string();
+    let response = format!(
+        "HTTP/1.1 {status} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        reason(status),
+        body.len()
+    );
+    let mut stream = reader.into_inner();
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+fn respond(
+    config: &MockServerConfig,
+    request_number: u64,
+    url: &Url,
+    has_api_key: bool,
+) -> (u16, Value) {
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+            .filter(|value| !value.is_empty())
+    };
+
+    if !has_api_key && param("apiKey").is_none() {
+        return error(
+            401,
+            "apiKeyMissing",
+            "Your API key is missing. Append this to the URL with the apiKey param, or use \
+             the x-api-key HTTP header.",
+        );
+    }
+    if let Some(code) = &config.fail_with {
+        return error(status_for(code), code, "Injected failure.");
+    }
+    let mut rng = StdRng::seed_from_u64(config.seed ^ request_number.wrapping_mul(0x9e37_79b9));
+    if rng.random::<f64>() < config.failure_rate {
+        return error(500, "unexpectedError", "Injected failure.");
+    }
+
+    let version = ApiVersion::V2;
+    let path = url.path();
+    if path == version.path(Endpoint::Sources) {
+        let sources: Vec<Value> = matching_sources(&param)
+            .into_iter()
+            .map(|source| source_json(source, true))
+            .collect();
+        (200, json!({"status": "ok", "sources": sources}))
+    } else if path == version.path(Endpoint::TopHeadlines) {
+        if ["country", "category", "sources", "q"]
+            .iter()
+            .all(|name| param(name).is_none())
+        {
+            return error(
+                400,
+                "parametersMissing",
+                "Required parameters are missing. Please set any of the following parameters \
+                 and try again: sources, q, country, category.",
+            );
+        }
+        articles_response(config, &param)
+    } else if path == version.path(Endpoint::Everything) {
+        if ["q", "sources", "domains"]
+            .iter()
+            .all(|name| param(name).is_none())
+        {
+            return error(
+                400,
+                "parametersMissing",
+                "Required parameters are missing, the scope of your search is too broad. \
+                 Please set any of the following required parameters and try again: q, \
+                 sources, domains.",
+            );
+        }
+        articles_response(config, &param)
+    } else {
+        error(404, "routeNotFound", "Invalid endpoint.")
+    }
+}
+
+fn matching_sources(param: &impl Fn(&str) -> Option<String>) -> Vec<SourceRow> {
+    let ids = param("sources").map(|ids| {
+        ids.split(',')
+            .map(|id| id.trim().to_string())
+            .collect::<Vec<_>>()
+    });
+    SOURCES
+        .into_iter()
+        .filter(|(id, _, category, language, country)| {
+            ids.as_ref().is_none_or(|ids| ids.iter().any(|i| i == id))
+                && param("category").is_none_or(|c| c == *category)
+                && param("language").is_none_or(|l| l == *language)
+                && param("country").is_none_or(|c| c == *country)
+        })
+        .collect()
+}
+
+fn articles_response(
+    config: &MockServerConfig,
+    param: &impl Fn(&str) -> Option<String>,
+) -> (u16, Value) {
+    let sources = matching_sources(param);
+    let total = if sources.is_empty() {
+        0
+    } else {
+        config.total_results
+    };
+    let page_size = param("pageSize")
+        .and_then(|size| size.parse::<usize>().ok())
+        .unwrap_or(MAX_PAGE_SIZE as usize)
+        .clamp(1, MAX_PAGE_SIZE as usize);
+    let page = param("page")
+        .and_then(|page| page.parse::<usize>().ok())
+        .unwrap_or(1)
+        .max(1);
+    let term = param("q");
+
+    // Articles depend only on the seed, the query and their position, so pages of the same
+    // query are consistent with each other.
+    let query_seed = fnv1a(&format!(
+        "{:?}|{:?}|{:?}|{:?}|{:?}",
+        term,
+        param("sources"),
+        param("category"),
+        param("country"),
+        param("language")
+    ));
+    let mut faker = ArticleFaker::new(config.seed ^ query_seed);
+    if let Some((from, to)) = config.published_between {
+        faker = faker.published_between(from, to);
+    }
+    if let Some(term) = term {
+        faker = faker.about(term);
+    }
+    let start = ((page - 1) * page_size).min(total);
+    let end = (page * page_size).min(total);
+    let articles: Vec<Value> = (start..end)
+        .map(|index| faker.article_json(index, total, &sources))
+        .collect();
+
+    (
+        200,
+        json!({"status": "ok", "totalResults": total, "articles": articles}),
+    )
+}
+
+/// FNV-1a, which unlike the standard library's hasher is stable across releases.
+fn fnv1a(value: &str) -> u64 {
+    value.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn error(status: u16, code: &str, message: &str) -> (u16, Value) {
+    (
+        status,
+        json!({"status": "error", "code": code, "message": message}),
+    )
+}
+
+/// HTTP status NewsAPI answers an error code with.
+fn status_for(code: &str) -> u16 {
+    match code {
+        "apiKeyExhausted" | "rateLimited" => 429,
+        "apiKeyDisabled" | "apiKeyInvalid" | "apiKeyMissing" => 401,
+        "unexpectedError" => 500,
+        _ => 400,
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::NewsApiClient;
+    use crate::error::{ApiClientError, ApiClientErrorCode};
+    use crate::model::{GetEverythingRequest, GetSourcesRequest, NewsCategory};
+
+    async fn start_with_client(
+        config: MockServerConfig,
+    ) -> (MockServer, NewsApiClient<reqwest::Client>) {
+        let server = MockServer::start(config).await.unwrap();
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .build()
+            .unwrap();
+        (server, client)
+    }
+
+    fn titles(response: &crate::model::GetEverythingResponse) -> Vec<String> {
+        response
+            .get_articles()
+            .iter()
+            .map(|article| article.get_title().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_everything_is_paged_and_deterministic() {
+        let (_server, client) =
+            start_with_client(MockServerConfig::new().seed(7).total_results(15)).await;
+        let request = GetEverythingRequest::builder()
+            .search_term("rust".to_string())
+            .page_size(10)
+            .build();
+
+        let first = client.get_everything(&request).await.unwrap();
+        assert_eq!(*first.get_total_results(), 15);
+        assert_eq!(first.get_articles().len(), 10);
+        assert!(titles(&first)
+            .iter()
+            .all(|title| title.ends_with("in Rust")));
+
+        let second = client.get_everything(&request.with_page(2)).await.unwrap();
+        assert_eq!(second.get_articles().len(), 5);
+
+        let (_other, other_client) =
+            start_with_client(MockServerConfig::new().seed(7).total_results(15)).await;
+        let again = other_client.get_everything(&request).await.unwrap();
+        assert_eq!(titles(&again), titles(&first));
+    }
+
+    #[tokio::test]
+    async fn test_articles_are_published_within_range() {
+        let to = Utc::now();
+        let from = to - chrono::Duration::days(7);
+        let (_server, client) = start_with_client(
+            MockServerConfig::new()
+                .total_results(20)
+                .published_between(from, to),
+        )
+        .await;
+        let request = GetEverythingRequest::builder()
+            .search_term("rust".to_string())
+            .build();
+
+        let response = client.get_everything(&request).await.unwrap();
+        let dates: Vec<DateTime<Utc>> = response
+            .get_articles()
+            .iter()
+            .map(|article| *article.get_published_at())
+            .collect();
+        assert_eq!(dates.len(), 20);
+        assert!(dates
+            .iter()
+            .all(|date| *date >= from - chrono::Duration::seconds(1) && *date <= to));
+        assert!(dates[19] < from + chrono::Duration::days(1));
+    }
+
+    #[tokio::test]
+    async fn test_sources_are_filtered() {
+        let (_server, client) = start_with_client(MockServerConfig::new()).await;
+        let request = GetSourcesRequest::builder()
+            .category(NewsCategory::Technology)
+            .build();
+
+        let response = client.get_sources(&request).await.unwrap();
+        let ids: Vec<_> = response
+            .get_sources()
+            .iter()
+            .filter_map(|source| source.get_id().cloned())
+            .collect();
+        assert_eq!(ids, vec!["tech-daily"]);
+    }
+
+    #[tokio::test]
+    async fn test_injected_errors() {
+        let (_server, client) =
+            start_with_client(MockServerConfig::new().fail_with("rateLimited")).await;
+        let request = GetSourcesRequest::builder().build();
+
+        assert!(matches!(
+            client.get_sources(&request).await,
+            Err(ApiClientError::RateLimited { response, .. })
+                if response.code == ApiClientErrorCode::RateLimited
+        ));
+
+        let server = MockServer::start(MockServerConfig::new()).await.unwrap();
+        let response = reqwest::get(format!("{}/v2/everything?q=rust", server.url()))
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 401);
+    }
+}
@@ -1,19 +1,72 @@
-use chrono::serde::ts_seconds_option;
-use chrono::{DateTime, Utc};
+use crate::compare::ArticleDiff;
+use crate::envelope::{envelop, ArticleEnvelope};
+use crate::error::ApiClientError;
+use crate::query::{Query, TermMatch};
+use crate::retry::RetryAttempt;
+use crate::summary;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use getset::{Getters, MutGetters};
+use serde::Deserializer;
 use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "extra-fields")]
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use strum::{Display, EnumString};
 use validator::Validate;
 
-fn default_page_size() -> i32 {
-    1
+/// Items of a comma-separated parameter, lowercased, sorted and deduplicated, so that
+/// equivalent lists compare equal.
+fn normalized_list(list: &Option<String>) -> Option<Vec<String>> {
+    list.as_deref().map(|list| {
+        let mut items: Vec<String> = list
+            .split(',')
+            .map(|item| item.trim().to_lowercase())
+            .filter(|item| !item.is_empty())
+            .collect();
+        items.sort_unstable();
+        items.dedup();
+        items
+    })
 }
 
-fn default_page() -> i32 {
-    1
+/// `endpoint?key=value&...` with the set parameters sorted by key.
+fn canonical_key(endpoint: &str, params: Vec<(&str, Option<String>)>) -> String {
+    let mut params: Vec<(&str, String)> = params
+        .into_iter()
+        .filter_map(|(key, value)| value.map(|value| (key, value)))
+        .collect();
+    params.sort_unstable();
+    let query: Vec<String> = params
+        .into_iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect();
+    format!("{endpoint}?{}", query.join("&"))
 }
 
-#[derive(Serialize, Deserialize, Debug, EnumString, Display, Clone)]
+/// How the client treats a `pageSize` outside the range of 1 to 100 accepted by the API
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PageSizePolicy {
+    /// The page size is clamped into range with a warning.
+    #[default]
+    Clamp,
+    /// The request fails locally with [`ApiClientError::InvalidRequest`](crate::error::ApiClientError::InvalidRequest).
+    Strict,
+}
+
+/// Whether the client recovers automatically where it can, or fails instead
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Strictness {
+    /// Out-of-range page sizes are handled by the [`PageSizePolicy`], stale responses are
+    /// served under [`CachePolicy::StaleIfError`](crate::cache::CachePolicy::StaleIfError)
+    /// and the sources catalog falls back to the embedded snapshot.
+    #[default]
+    Lenient,
+    /// Each of those fails with the error that would have been recovered from, whatever the
+    /// page size and cache policies. Defaults configured on the client are still applied.
+    Strict,
+}
+
+#[derive(Serialize, Deserialize, Debug, EnumString, Display, Clone, PartialEq, Eq, Hash)]
 pub enum ArticleSortBy {
     #[strum(serialize = "publishedAt")]
     PublishedAt,
@@ -23,7 +76,9 @@ pub enum ArticleSortBy {
     Popularity,
 }
 
-#[derive(Serialize, Deserialize, Debug, EnumString, Display, Clone)]
+#[derive(
+    Serialize, Deserialize, Debug, EnumString, Display, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
 #[strum(serialize_all = "lowercase")]
 pub enum SearchInOption {
     Title,
@@ -31,7 +86,7 @@ pub enum SearchInOption {
     Content,
 }
 
-#[derive(Serialize, Deserialize, Debug, EnumString, Display, Clone)]
+#[derive(Serialize, Deserialize, Debug, EnumString, Display, Clone, PartialEq, Eq, Hash)]
 #[strum(serialize_all = "lowercase")]
 pub enum NewsCategory {
     Business,
@@ -43,7 +98,7 @@ pub enum NewsCategory {
     Technology,
 }
 
-#[derive(Serialize, Deserialize, Debug, EnumString, Display, Clone)]
+#[derive(Serialize, Deserialize, Debug, EnumString, Display, Clone, PartialEq, Eq, Hash)]
 #[strum(serialize_all = "lowercase")]
 pub enum Country {
     AE,
@@ -102,7 +157,7 @@ pub enum Country {
     ZA,
 }
 
-#[derive(Serialize, Deserialize, Debug, EnumString, Display, Clone)]
+#[derive(Serialize, Deserialize, Debug, EnumString, Display, Clone, PartialEq, Eq, Hash)]
 #[strum(serialize_all = "lowercase")]
 pub enum Language {
     AR,
@@ -121,6 +176,41 @@ pub enum Language {
     ZH,
 }
 
+/// Parses an article timestamp, RFC 3339 as the API documents, or a near miss: a space
+/// instead of `T`, an offset without a colon, no offset at all, read as UTC, or only a date.
+fn parse_published_at(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim().replacen(' ', "T", 1);
+    DateTime::parse_from_rfc3339(&value)
+        .or_else(|_| DateTime::parse_from_str(&value, "%Y-%m-%dT%H:%M:%S%.f%z"))
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .or_else(|_| {
+            NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y-%m-%dT%H:%M:%S%.f")
+                .map(|timestamp| timestamp.and_utc())
+        })
+        .or_else(|_| {
+            NaiveDate::parse_from_str(&value, "%Y-%m-%d")
+                .map(|date| date.and_time(NaiveTime::MIN).and_utc())
+        })
+        .ok()
+}
+
+fn deserialize_published_at<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<DateTime<Utc>, D::Error> {
+    let value = <String as serde::Deserialize>::deserialize(deserializer)?;
+    parse_published_at(&value)
+        .ok_or_else(|| serde::de::Error::custom(format!("invalid timestamp `{value}`")))
+}
+
+/// The `status` reported in a response body.
+#[derive(Serialize, Deserialize, Debug, EnumString, Display, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum ResponseStatus {
+    Ok,
+    Error,
+}
+
 #[derive(Serialize, Deserialize, Debug, Getters)]
 #[getset(get = "pub with_prefix")]
 pub struct Article {
@@ -137,10 +227,76 @@ pub struct Article {
     #[serde(rename = "urlToImage")]
     url_to_image: Option<String>,
 
-    #[serde(rename = "publishedAt")]
-    published_at: String,
+    #[serde(rename = "publishedAt", deserialize_with = "deserialize_published_at")]
+    published_at: DateTime<Utc>,
 
     content: Option<String>,
+
+    /// Fields the API sent that this version of the client does not model.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    extra: HashMap<String, serde_json::Value>,
+
+    #[serde(skip)]
+    #[getset(skip)]
+    term_matches: Vec<TermMatch>,
+
+    #[serde(skip)]
+    #[getset(skip)]
+    summary: Option<String>,
+}
+
+impl Article {
+    /// Distinct search terms found in the title or description, in order of first
+    /// occurrence. Computed by the client from the request's search term.
+    pub fn matched_terms(&self) -> Vec<&str> {
+        let mut terms: Vec<&str> = Vec::new();
+        for term_match in &self.term_matches {
+            if !terms.contains(&term_match.get_term()) {
+                terms.push(term_match.get_term());
+            }
+        }
+        terms
+    }
+
+    /// Where search terms occur in the title and description, e.g. for highlighting.
+    pub fn get_term_matches(&self) -> &[TermMatch] {
+        &self.term_matches
+    }
+
+    pub(crate) fn highlight(&mut self, query: &Query) {
+        self.term_matches = query.find_matches(&self.title, self.description.as_deref());
+    }
+
+    /// Changes to the title, description, content and image from this version of the article
+    /// to `other`, e.g. to track corrections of a story fetched again later.
+    pub fn diff(&self, other: &Article) -> ArticleDiff {
+        ArticleDiff::between(self, other)
+    }
+
+    /// Summary synthesized by the client from the description and the truncated content,
+    /// whichever of them the source populated. `None` when it provided neither.
+    pub fn get_summary(&self) -> Option<&str> {
+        self.summary.as_deref()
+    }
+
+    pub(crate) fn summarize(&mut self, max_chars: usize) {
+        self.summary = summary::synthesize(
+            self.description.as_deref(),
+            self.content.as_deref(),
+            max_chars,
+        );
+    }
+
+    #[cfg(feature = "translate")]
+    pub(crate) fn set_title(&mut self, title: String) {
+        self.title = title;
+    }
+
+    #[cfg(feature = "translate")]
+    pub(crate) fn set_description(&mut self, description: Option<String>) {
+        self.description = description;
+    }
 }
 
 #[derive(Serialize, Deserialize, Validate, Debug, Getters, MutGetters, Clone)]
@@ -152,14 +308,14 @@ pub struct GetTopHeadlinesRequest {
 
     sources: Option<String>,
 
-    #[serde(rename = "q")]
+    #[serde(rename = "q", default)]
     search_term: String,
 
-    #[serde(rename = "pageSize", default = "default_page_size")]
+    #[serde(rename = "pageSize", default)]
     #[validate(range(min = 1, max = 100))]
     page_size: i32,
 
-    #[serde(default = "default_page")]
+    #[serde(default)]
     #[validate(range(min = 1))]
     page: i32,
 }
@@ -168,6 +324,74 @@ impl GetTopHeadlinesRequest {
     pub fn builder() -> GetTopHeadlinesRequestBuilder {
         GetTopHeadlinesRequestBuilder::new()
     }
+
+    pub(crate) fn with_country(&self, country: Country) -> Self {
+        Self {
+            country: Some(country),
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn with_page_size(&self, page_size: i32) -> Self {
+        Self {
+            page_size,
+            ..self.clone()
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn normalized(
+        &self,
+    ) -> (
+        &Option<Country>,
+        &Option<NewsCategory>,
+        Option<Vec<String>>,
+        &str,
+        i32,
+        i32,
+    ) {
+        (
+            &self.country,
+            &self.category,
+            normalized_list(&self.sources),
+            self.search_term.trim(),
+            self.page_size,
+            self.page,
+        )
+    }
+
+    /// Canonical form of the request, equal for requests that are
+    /// [equal](#impl-PartialEq-for-GetTopHeadlinesRequest), for use as a persistent cache key.
+    pub fn cache_key(&self) -> String {
+        let (country, category, sources, search_term, page_size, page) = self.normalized();
+        canonical_key(
+            "top-headlines",
+            vec![
+                ("country", country.as_ref().map(ToString::to_string)),
+                ("category", category.as_ref().map(ToString::to_string)),
+                ("sources", sources.map(|sources| sources.join(","))),
+                ("q", Some(search_term.to_string()).filter(|q| !q.is_empty())),
+                ("pageSize", Some(page_size.to_string())),
+                ("page", Some(page.to_string())),
+            ],
+        )
+    }
+}
+
+/// Requests are equal when they query the same thing; surrounding whitespace in the search
+/// term and the order and case of comma-separated sources are ignored.
+impl PartialEq for GetTopHeadlinesRequest {
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized() == other.normalized()
+    }
+}
+
+impl Eq for GetTopHeadlinesRequest {}
+
+impl Hash for GetTopHeadlinesRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.normalized().hash(state);
+    }
 }
 
 #[derive(Default)]
@@ -238,20 +462,57 @@ impl GetTopHeadlinesRequestBuilder {
 #[derive(Serialize, Deserialize, Debug, Getters)]
 #[getset(get = "pub with_prefix")]
 pub struct TopHeadlinesResponse {
-    status: String,
+    status: ResponseStatus,
 
     #[serde(rename = "totalResults")]
     total_results: i32,
 
     articles: Vec<Article>,
+
+    /// Fields the API sent that this version of the client does not model.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    extra: HashMap<String, serde_json::Value>,
+
+    /// Articles left out because they could not be parsed, see
+    /// [`NewsApiClientBuilder::skip_malformed_articles`](crate::client::NewsApiClientBuilder::skip_malformed_articles).
+    #[serde(skip)]
+    warnings: Vec<ArticleWarning>,
+
+    #[serde(skip)]
+    meta: ResponseMeta,
+}
+
+impl TopHeadlinesResponse {
+    pub(crate) fn highlight(&mut self, query: &Query) {
+        self.articles
+            .iter_mut()
+            .for_each(|article| article.highlight(query));
+    }
+
+    pub(crate) fn summarize(&mut self, max_chars: usize) {
+        self.articles
+            .iter_mut()
+            .for_each(|article| article.summarize(max_chars));
+    }
+
+    pub fn into_articles(self) -> Vec<Article> {
+        self.articles
+    }
+
+    /// Articles wrapped with the provenance of this response.
+    pub fn into_enveloped_articles(self) -> Vec<ArticleEnvelope> {
+        envelop(self.articles, &self.meta)
+    }
 }
 
 #[derive(Serialize, Deserialize, Validate, Debug, Getters, MutGetters, Clone)]
 #[getset(get = "pub with_prefix")]
 pub struct GetEverythingRequest {
-    #[serde(rename = "q")]
+    #[serde(rename = "q", default)]
     search_term: String,
 
+    #[serde(default)]
     search_in: Vec<SearchInOption>,
 
     sources: Option<String>,
@@ -261,10 +522,10 @@ pub struct GetEverythingRequest {
     #[serde(rename = "excludeDomains")]
     exclude_domains: Option<String>,
 
-    #[serde(rename = "from", with = "ts_seconds_option")]
+    #[serde(rename = "from", default)]
     start_date: Option<DateTime<Utc>>,
 
-    #[serde(rename = "to", with = "ts_seconds_option")]
+    #[serde(rename = "to", default)]
     end_date: Option<DateTime<Utc>>,
 
     language: Option<Language>,
@@ -272,11 +533,11 @@ pub struct GetEverythingRequest {
     #[serde(rename = "sortBy")]
     sort_by: Option<String>,
 
-    #[serde(rename = "pageSize", default = "default_page_size")]
+    #[serde(rename = "pageSize", default)]
     #[validate(range(min = 1, max = 100))]
     page_size: i32,
 
-    #[serde(default = "default_page")]
+    #[serde(default)]
     #[validate(range(min = 1))]
     page: i32,
 }
@@ -285,6 +546,211 @@ impl GetEverythingRequest {
     pub fn builder() -> GetEverythingRequestBuilder {
         GetEverythingRequestBuilder::new()
     }
+
+    pub(crate) fn with_page(&self, page: i32) -> Self {
+        Self {
+            page,
+            ..self.clone()
+        }
+    }
+
+    /// The `from` and `to` bounds of the search; unset bounds are open and not sent.
+    pub fn get_date_range(&self) -> DateRange {
+        DateRange {
+            from: self.start_date,
+            to: self.end_date,
+        }
+    }
+
+    pub(crate) fn with_end_date(&self, end_date: DateTime<Utc>) -> Self {
+        Self {
+            end_date: Some(end_date),
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn with_page_size(&self, page_size: i32) -> Self {
+        Self {
+            page_size,
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn with_sort_by(&self, sort_by: &ArticleSortBy) -> Self {
+        Self {
+            sort_by: Some(sort_by.to_string()),
+            ..self.clone()
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn normalized(
+        &self,
+    ) -> (
+        &str,
+        Vec<&SearchInOption>,
+        [Option<Vec<String>>; 3],
+        (&Option<DateTime<Utc>>, &Option<DateTime<Utc>>),
+        &Option<Language>,
+        Option<String>,
+        (i32, i32),
+    ) {
+        let mut search_in: Vec<&SearchInOption> = self.search_in.iter().collect();
+        search_in.sort_unstable();
+        search_in.dedup();
+        (
+            self.search_term.trim(),
+            search_in,
+            [
+                normalized_list(&self.sources),
+                normalized_list(&self.domains),
+                normalized_list(&self.exclude_domains),
+            ],
+            (&self.start_date, &self.end_date),
+            &self.language,
+            self.sort_by.as_deref().map(str::to_lowercase),
+            (self.page_size, self.page),
+        )
+    }
+
+    /// Canonical form of the request, equal for requests that are
+    /// [equal](#impl-PartialEq-for-GetEverythingRequest), for use as a persistent cache key.
+    pub fn cache_key(&self) -> String {
+        let (
+            search_term,
+            search_in,
+            [sources, domains, exclude_domains],
+            dates,
+            language,
+            sort_by,
+            (page_size, page),
+        ) = self.normalized();
+        let list = |items: Option<Vec<String>>| items.map(|items| items.join(","));
+        canonical_key(
+            "everything",
+            vec![
+                ("q", Some(search_term.to_string()).filter(|q| !q.is_empty())),
+                (
+                    "searchIn",
+                    Some(
+                        search_in
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join(","),
+                    )
+                    .filter(|search_in| !search_in.is_empty()),
+                ),
+                ("sources", list(sources)),
+                ("domains", list(domains)),
+                ("excludeDomains", list(exclude_domains)),
+                ("from", dates.0.map(|from| from.to_rfc3339())),
+                ("to", dates.1.map(|to| to.to_rfc3339())),
+                ("language", language.as_ref().map(ToString::to_string)),
+                ("sortBy", sort_by),
+                ("pageSize", Some(page_size.to_string())),
+                ("page", Some(page.to_string())),
+            ],
+        )
+    }
+}
+
+/// Requests are equal when they query the same thing; surrounding whitespace in the search
+/// term, the order of `search_in` options, the order and case of comma-separated sources and
+/// domains, and the case of the sort order are ignored.
+impl PartialEq for GetEverythingRequest {
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized() == other.normalized()
+    }
+}
+
+impl Eq for GetEverythingRequest {}
+
+impl Hash for GetEverythingRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.normalized().hash(state);
+    }
+}
+
+/// Publication date bounds of an everything search, inclusive at both ends.
+///
+/// Either bound may be open: [`since`](Self::since) matches everything published from a time
+/// on and [`until`](Self::until) everything published up to one, leaving the other side to the
+/// API's defaults. Open bounds are omitted from the request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DateRange {
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+}
+
+impl DateRange {
+    /// No bounds.
+    pub fn unbounded() -> Self {
+        Self::default()
+    }
+
+    /// Published at or after `from`, with no upper bound.
+    pub fn since(from: DateTime<Utc>) -> Self {
+        Self {
+            from: Some(from),
+            to: None,
+        }
+    }
+
+    /// Published at or before `to`, with no lower bound.
+    pub fn until(to: DateTime<Utc>) -> Self {
+        Self {
+            from: None,
+            to: Some(to),
+        }
+    }
+
+    /// Published between `from` and `to`. Fails if `from` is after `to`.
+    pub fn between(from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Self, ApiClientError> {
+        Self::default().with_from(from).with_to(to).checked()
+    }
+
+    pub fn get_from(&self) -> Option<&DateTime<Utc>> {
+        self.from.as_ref()
+    }
+
+    pub fn get_to(&self) -> Option<&DateTime<Utc>> {
+        self.to.as_ref()
+    }
+
+    pub fn is_unbounded(&self) -> bool {
+        self.from.is_none() && self.to.is_none()
+    }
+
+    pub fn contains(&self, date: &DateTime<Utc>) -> bool {
+        self.from.is_none_or(|from| from <= *date) && self.to.is_none_or(|to| *date <= to)
+    }
+
+    fn with_from(self, from: DateTime<Utc>) -> Self {
+        Self {
+            from: Some(from),
+            ..self
+        }
+    }
+
+    fn with_to(self, to: DateTime<Utc>) -> Self {
+        Self {
+            to: Some(to),
+            ..self
+        }
+    }
+
+    /// Rejects a range whose lower bound is after its upper bound.
+    pub(crate) fn checked(self) -> Result<Self, ApiClientError> {
+        match (self.from, self.to) {
+            (Some(from), Some(to)) if from > to => Err(ApiClientError::InvalidRequest(format!(
+                "from ({}) is after to ({})",
+                from.to_rfc3339(),
+                to.to_rfc3339()
+            ))),
+            _ => Ok(self),
+        }
+    }
 }
 
 #[derive(Default)]
@@ -352,6 +818,23 @@ impl GetEverythingRequestBuilder {
         self
     }
 
+    /// Sets both date bounds, clearing any bound the range leaves open.
+    pub fn date_range(mut self, date_range: DateRange) -> Self {
+        self.start_date = date_range.from;
+        self.end_date = date_range.to;
+        self
+    }
+
+    /// Articles published at or after `from`, with no upper bound. See [`DateRange::since`].
+    pub fn since(self, from: DateTime<Utc>) -> Self {
+        self.date_range(DateRange::since(from))
+    }
+
+    /// Articles published at or before `to`, with no lower bound. See [`DateRange::until`].
+    pub fn until(self, to: DateTime<Utc>) -> Self {
+        self.date_range(DateRange::until(to))
+    }
+
     pub fn language(mut self, language: Language) -> Self {
         self.language = Option::Some(language);
         self
@@ -392,12 +875,48 @@ impl GetEverythingRequestBuilder {
 #[derive(Serialize, Deserialize, Getters, Debug)]
 #[getset(get = "pub with_prefix")]
 pub struct GetEverythingResponse {
-    status: String,
+    status: ResponseStatus,
 
     #[serde(rename = "totalResults")]
     total_results: i32,
 
     articles: Vec<Article>,
+
+    /// Fields the API sent that this version of the client does not model.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    extra: HashMap<String, serde_json::Value>,
+
+    /// Articles left out because they could not be parsed, see
+    /// [`NewsApiClientBuilder::skip_malformed_articles`](crate::client::NewsApiClientBuilder::skip_malformed_articles).
+    #[serde(skip)]
+    warnings: Vec<ArticleWarning>,
+
+    #[serde(skip)]
+    meta: ResponseMeta,
+}
+
+impl GetEverythingResponse {
+    pub(crate) fn highlight(&mut self, query: &Query) {
+        self.articles
+            .iter_mut()
+            .for_each(|article| article.highlight(query));
+    }
+
+    pub(crate) fn summarize(&mut self, max_chars: usize) {
+        self.articles
+            .iter_mut()
+            .for_each(|article| article.summarize(max_chars));
+    }
+
+    pub fn into_articles(self) -> Vec<Article> {
+        self.articles
+    }
+
+    /// Articles wrapped with the provenance of this response.
+    pub fn into_enveloped_articles(self) -> Vec<ArticleEnvelope> {
+        envelop(self.articles, &self.meta)
+    }
 }
 
 /// Source representation from NewsAPI
@@ -410,6 +929,10 @@ pub struct Source {
     category: Option<String>,
     language: Option<String>,
     country: Option<String>,
+    /// Fields the API sent that this version of the client does not model.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    extra: HashMap<String, serde_json::Value>,
 }
 
 impl Source {
@@ -440,23 +963,219 @@ impl Source {
     pub fn get_country(&self) -> Option<&String> {
         self.country.as_ref()
     }
+
+    #[cfg(feature = "extra-fields")]
+    pub fn get_extra(&self) -> &HashMap<String, serde_json::Value> {
+        &self.extra
+    }
 }
 
 /// Response for the sources endpoint
 #[derive(Debug, Deserialize, Serialize)]
 pub struct GetSourcesResponse {
-    status: String,
+    status: ResponseStatus,
     sources: Vec<Source>,
+    /// Fields the API sent that this version of the client does not model.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    extra: HashMap<String, serde_json::Value>,
+    #[serde(skip)]
+    meta: ResponseMeta,
 }
 
 impl GetSourcesResponse {
-    pub fn get_status(&self) -> &str {
+    pub fn get_status(&self) -> &ResponseStatus {
         &self.status
     }
 
     pub fn get_sources(&self) -> &[Source] {
         &self.sources
     }
+
+    pub fn into_sources(self) -> Vec<Source> {
+        self.sources
+    }
+
+    #[cfg(feature = "extra-fields")]
+    pub fn get_extra(&self) -> &HashMap<String, serde_json::Value> {
+        &self.extra
+    }
+
+    pub fn get_meta(&self) -> &ResponseMeta {
+        &self.meta
+    }
+}
+
+/// An article left out of a response because it could not be parsed.
+#[derive(Debug, Clone, PartialEq, Getters)]
+#[getset(get = "pub with_prefix")]
+pub struct ArticleWarning {
+    /// Position of the article in the page as sent by the API.
+    index: usize,
+    /// Why it could not be parsed, e.g. ``source: missing field `name` ``.
+    message: String,
+}
+
+impl ArticleWarning {
+    pub(crate) fn new(index: usize, message: String) -> Self {
+        Self { index, message }
+    }
+}
+
+/// Client-side metadata describing how a response was obtained
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResponseMeta {
+    fetched_at: Option<DateTime<Utc>>,
+    request_key: Option<String>,
+    stale: bool,
+    body_size: Option<usize>,
+    transfer_size: Option<usize>,
+    retries: Vec<RetryAttempt>,
+    request_id: Option<String>,
+    normalizations: Vec<Normalization>,
+}
+
+impl ResponseMeta {
+    pub(crate) fn fresh(fetched_at: DateTime<Utc>, request_key: &str) -> Self {
+        Self {
+            fetched_at: Some(fetched_at),
+            request_key: Some(request_key.to_string()),
+            stale: false,
+            body_size: None,
+            transfer_size: None,
+            retries: Vec::new(),
+            request_id: None,
+            normalizations: Vec::new(),
+        }
+    }
+
+    pub(crate) fn stale(fetched_at: DateTime<Utc>, request_key: &str) -> Self {
+        Self {
+            fetched_at: Some(fetched_at),
+            request_key: Some(request_key.to_string()),
+            stale: true,
+            body_size: None,
+            transfer_size: None,
+            retries: Vec::new(),
+            request_id: None,
+            normalizations: Vec::new(),
+        }
+    }
+
+    /// When the response body was received from the API.
+    pub fn get_fetched_at(&self) -> Option<&DateTime<Utc>> {
+        self.fetched_at.as_ref()
+    }
+
+    /// URL of the request the response answers.
+    pub fn get_request_key(&self) -> Option<&String> {
+        self.request_key.as_ref()
+    }
+
+    /// Whether the response was served from the cache because the API was unavailable.
+    pub fn is_stale(&self) -> bool {
+        self.stale
+    }
+
+    pub(crate) fn with_sizes(mut self, body_size: usize, transfer_size: Option<usize>) -> Self {
+        self.body_size = Some(body_size);
+        self.transfer_size = transfer_size;
+        self
+    }
+
+    /// Size in bytes of the response body after any decompression.
+    pub fn get_body_size(&self) -> Option<usize> {
+        self.body_size
+    }
+
+    /// Bytes of response body transferred over the network for this response, as sent by
+    /// the server: zero when a cached response was revalidated with `304 Not Modified`, and
+    /// `None` when it was served from the cache without a request.
+    pub fn get_transfer_size(&self) -> Option<usize> {
+        self.transfer_size
+    }
+
+    pub(crate) fn with_retries(mut self, retries: Vec<RetryAttempt>) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Number of requests sent for this response, one unless it was retried.
+    pub fn get_attempts(&self) -> usize {
+        self.retries.len() + 1
+    }
+
+    /// The failed attempts that were retried before this response, oldest first.
+    pub fn get_retries(&self) -> &[RetryAttempt] {
+        &self.retries
+    }
+
+    pub(crate) fn with_request_id(mut self, request_id: Option<String>) -> Self {
+        self.request_id = request_id;
+        self
+    }
+
+    /// Correlation ID the request was sent with, see
+    /// [`NewsApiClientBuilder::request_id_header`](crate::client::NewsApiClientBuilder::request_id_header).
+    pub fn get_request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+
+    pub(crate) fn with_normalizations(mut self, normalizations: Vec<Normalization>) -> Self {
+        self.normalizations = normalizations;
+        self
+    }
+
+    /// Changes the client made to the request before sending it, in the order applied.
+    pub fn get_normalizations(&self) -> &[Normalization] {
+        &self.normalizations
+    }
+}
+
+/// A change the client made to a request before sending it, see
+/// [`ResponseMeta::get_normalizations`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Normalization {
+    /// An out-of-range `pageSize` was clamped under [`PageSizePolicy::Clamp`].
+    PageSizeClamped { from: i32, to: i32 },
+    /// The client's default `sortBy` was applied to a request without one.
+    DefaultSortBy(String),
+    /// The client's default `pageSize` was applied to a request without one.
+    DefaultPageSize(i32),
+}
+
+/// Responses carrying a [`ResponseMeta`] filled in by the client
+pub(crate) trait WithResponseMeta {
+    fn set_meta(&mut self, meta: ResponseMeta);
+
+    /// Records articles skipped while parsing, for responses that have articles.
+    fn set_warnings(&mut self, _warnings: Vec<ArticleWarning>) {}
+}
+
+impl WithResponseMeta for TopHeadlinesResponse {
+    fn set_meta(&mut self, meta: ResponseMeta) {
+        self.meta = meta;
+    }
+
+    fn set_warnings(&mut self, warnings: Vec<ArticleWarning>) {
+        self.warnings = warnings;
+    }
+}
+
+impl WithResponseMeta for GetEverythingResponse {
+    fn set_meta(&mut self, meta: ResponseMeta) {
+        self.meta = meta;
+    }
+
+    fn set_warnings(&mut self, warnings: Vec<ArticleWarning>) {
+        self.warnings = warnings;
+    }
+}
+
+impl WithResponseMeta for GetSourcesResponse {
+    fn set_meta(&mut self, meta: ResponseMeta) {
+        self.meta = meta;
+    }
 }
 
 /// Builder for sources request
@@ -493,7 +1212,7 @@ impl GetSourcesRequestBuilder {
 }
 
 /// Request parameters for the sources endpoint
-#[derive(Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GetSourcesRequest {
     category: Option<NewsCategory>,
     language: Option<Language>,
@@ -516,4 +1235,251 @@ impl GetSourcesRequest {
     pub fn get_country(&self) -> Option<&Country> {
         self.country.as_ref()
     }
+
+    /// Canonical form of the request, for use as a persistent cache key.
+    pub fn cache_key(&self) -> String {
+        canonical_key(
+            "sources",
+            vec![
+                ("category", self.category.as_ref().map(ToString::to_string)),
+                ("language", self.language.as_ref().map(ToString::to_string)),
+                ("country", self.country.as_ref().map(ToString::to_string)),
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_everything_request_equality_ignores_list_order() {
+        let request = |search_in, domains: &str| {
+            GetEverythingRequest::builder()
+                .search_term("bitcoin".to_string())
+                .search_in(search_in)
+                .domains(domains.to_string())
+                .build()
+        };
+        let a = request(
+            vec![SearchInOption::Title, SearchInOption::Content],
+            "bbc.co.uk,cnn.com",
+        );
+        let b = request(
+            vec![SearchInOption::Content, SearchInOption::Title],
+            "cnn.com, bbc.co.uk",
+        );
+        let c = request(vec![SearchInOption::Title], "cnn.com,bbc.co.uk");
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+
+        let memo: HashSet<GetEverythingRequest> = [a, b, c].into_iter().collect();
+        assert_eq!(memo.len(), 2);
+    }
+
+    #[test]
+    fn test_top_headlines_and_sources_request_equality() {
+        let request = |sources: &str| {
+            GetTopHeadlinesRequest::builder()
+                .sources(sources.to_string())
+                .build()
+                .unwrap()
+        };
+        assert_eq!(request("bbc-news,cnn"), request("cnn,bbc-news"));
+        assert_ne!(request("bbc-news"), request("cnn"));
+
+        assert_eq!(
+            GetSourcesRequest::builder().country(Country::US).build(),
+            GetSourcesRequest::builder().country(Country::US).build()
+        );
+    }
+
+    #[test]
+    fn test_cache_keys_are_canonical() {
+        let a = GetEverythingRequest::builder()
+            .search_term(" bitcoin ".to_string())
+            .sources("CNN, bbc-news".to_string())
+            .search_in(vec![SearchInOption::Title, SearchInOption::Content])
+            .sort_by(ArticleSortBy::PublishedAt)
+            .build();
+        let b = GetEverythingRequest::builder()
+            .search_term("bitcoin".to_string())
+            .sources("bbc-news,cnn".to_string())
+            .search_in(vec![SearchInOption::Content, SearchInOption::Title])
+            .sort_by(ArticleSortBy::PublishedAt)
+            .build();
+        assert_eq!(a, b);
+        assert_eq!(a.cache_key(), b.cache_key());
+        assert_eq!(
+            a.cache_key(),
+            "everything?page=0&pageSize=0&q=bitcoin&searchIn=title,content\
+             &sortBy=publishedat&sources=bbc-news,cnn"
+        );
+
+        let headlines = |q: &str| {
+            GetTopHeadlinesRequest::builder()
+                .search_term(q.to_string())
+                .country(Country::US)
+                .build()
+                .unwrap()
+                .cache_key()
+        };
+        assert_eq!(headlines("rust "), headlines("rust"));
+        assert_ne!(headlines("rust"), headlines("go"));
+        assert_eq!(
+            GetSourcesRequest::builder()
+                .country(Country::US)
+                .build()
+                .cache_key(),
+            "sources?country=us"
+        );
+    }
+
+    #[test]
+    fn test_date_range_bounds() {
+        let early = "2024-01-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let late = "2024-02-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        let request = GetEverythingRequest::builder()
+            .start_date(early)
+            .end_date(late)
+            .since(late)
+            .build();
+        assert_eq!(request.get_date_range(), DateRange::since(late));
+        assert_eq!(*request.get_end_date(), None);
+
+        let until = DateRange::until(early);
+        assert!(until.contains(&(early - chrono::Duration::days(365))));
+        assert!(!until.contains(&late));
+        assert!(DateRange::unbounded().contains(&late));
+
+        assert!(DateRange::between(early, late).unwrap().contains(&early));
+        assert!(matches!(
+            DateRange::between(late, early),
+            Err(ApiClientError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_requests_round_trip_through_json() {
+        let from = Utc.with_ymd_and_hms(2024, 1, 1, 8, 30, 0).unwrap()
+            + chrono::Duration::milliseconds(250);
+        let everything = GetEverythingRequest::builder()
+            .search_term("bitcoin".to_string())
+            .search_in(vec![SearchInOption::Title])
+            .exclude_domains("example.com".to_string())
+            .date_range(DateRange::since(from))
+            .language(Language::EN)
+            .sort_by(ArticleSortBy::Popularity)
+            .page_size(50)
+            .page(2)
+            .build();
+        let json = serde_json::to_string(&everything).unwrap();
+        let decoded: GetEverythingRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, everything);
+        assert_eq!(decoded.get_start_date(), &Some(from));
+        assert_eq!(decoded.get_end_date(), &None);
+
+        let headlines = GetTopHeadlinesRequest::builder()
+            .country(Country::US)
+            .category(NewsCategory::Business)
+            .page_size(10)
+            .build()
+            .unwrap();
+        let json = serde_json::to_string(&headlines).unwrap();
+        assert_eq!(
+            serde_json::from_str::<GetTopHeadlinesRequest>(&json).unwrap(),
+            headlines
+        );
+
+        let sources = GetSourcesRequest::builder().language(Language::DE).build();
+        let json = serde_json::to_string(&sources).unwrap();
+        assert_eq!(
+            serde_json::from_str::<GetSourcesRequest>(&json).unwrap(),
+            sources
+        );
+    }
+
+    #[test]
+    fn test_missing_request_fields_match_builder_defaults() {
+        let everything: GetEverythingRequest = serde_json::from_str(r#"{"q": "bitcoin"}"#).unwrap();
+        assert_eq!(
+            everything,
+            GetEverythingRequest::builder()
+                .search_term("bitcoin".to_string())
+                .build()
+        );
+        assert_eq!(*everything.get_page_size(), 0);
+
+        let headlines: GetTopHeadlinesRequest = serde_json::from_str("{}").unwrap();
+        assert_eq!(
+            headlines,
+            GetTopHeadlinesRequest::builder().build().unwrap()
+        );
+
+        let sources: GetSourcesRequest = serde_json::from_str("{}").unwrap();
+        assert_eq!(sources, GetSourcesRequest::builder().build());
+    }
+
+    #[test]
+    fn test_published_at_tolerates_near_misses() {
+        let expected = Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).unwrap();
+        for value in [
+            "2024-05-01T12:30:00Z",
+            "2024-05-01T14:30:00+02:00",
+            "2024-05-01T12:30:00.000Z",
+            "2024-05-01 12:30:00Z",
+            "2024-05-01T12:30:00+0000",
+            "2024-05-01T12:30:00",
+            " 2024-05-01T12:30:00Z ",
+        ] {
+            assert_eq!(parse_published_at(value), Some(expected), "{value}");
+        }
+        assert_eq!(
+            parse_published_at("2024-05-01"),
+            Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).single()
+        );
+        assert_eq!(parse_published_at("yesterday"), None);
+
+        let article: Article = serde_json::from_value(serde_json::json!({
+            "source": {"id": null, "name": "Example"},
+            "title": "Title",
+            "url": "https://example.com/a",
+            "publishedAt": "2024-05-01 12:30:00"
+        }))
+        .unwrap();
+        assert_eq!(*article.get_published_at(), expected);
+        let round_trip = serde_json::to_value(&article).unwrap();
+        assert_eq!(round_trip["publishedAt"], "2024-05-01T12:30:00Z");
+    }
+
+    #[cfg(feature = "extra-fields")]
+    #[test]
+    fn test_unknown_fields_are_kept() {
+        let json = serde_json::json!({
+            "status": "ok",
+            "totalResults": 1,
+            "nextPage": "abc",
+            "articles": [{
+                "source": {"id": null, "name": "Example", "region": "eu"},
+                "title": "Title",
+                "url": "https://example.com/a",
+                "publishedAt": "2024-01-01T00:00:00Z",
+                "sentiment": 0.5
+            }]
+        });
+        let response: GetEverythingResponse = serde_json::from_value(json).unwrap();
+        assert_eq!(response.get_extra()["nextPage"], "abc");
+        let article = &response.get_articles()[0];
+        assert_eq!(article.get_extra()["sentiment"], 0.5);
+        assert_eq!(article.get_source().get_extra()["region"], "eu");
+        assert!(!article.get_extra().contains_key("title"));
+
+        let round_trip = serde_json::to_value(article).unwrap();
+        assert_eq!(round_trip["sentiment"], 0.5);
+    }
 }
@@ -0,0 +1,161 @@
+use crate::endpoint::Endpoint;
+use crate::error::ApiClientError;
+use crate::redact::redact_url;
+use crate::transport::HttpResponse;
+use opentelemetry::propagation::Injector;
+use opentelemetry::trace::{SpanKind, Status, TraceContextExt, Tracer};
+use opentelemetry::{global, Context, KeyValue};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::Method;
+use url::Url;
+
+/// Name of the tracer the client's spans are recorded with.
+pub const TRACER_NAME: &str = "newsapi-rs";
+
+/// A client span covering one HTTP request to the API, following the OpenTelemetry HTTP
+/// semantic conventions. Spans are recorded with the global tracer provider as children of
+/// the current context, and end when dropped.
+pub(crate) struct RequestSpan {
+    cx: Context,
+}
+
+impl RequestSpan {
+    /// Starts a span for a request to `url` and injects its trace context into `headers` with
+    /// the global propagator, so the API call joins the caller's trace.
+    pub(crate) fn start(
+        endpoint: Endpoint,
+        method: &Method,
+        url: &Url,
+        headers: &mut HeaderMap,
+    ) -> Self {
+        let tracer = global::tracer(TRACER_NAME);
+        let mut attributes = vec![
+            KeyValue::new("http.request.method", method.to_string()),
+            KeyValue::new("url.full", redact_url(url).to_string()),
+            KeyValue::new("newsapi.endpoint", endpoint.to_string()),
+        ];
+        if let Some(host) = url.host_str() {
+            attributes.push(KeyValue::new("server.address", host.to_string()));
+        }
+        if let Some(port) = url.port_or_known_default() {
+            attributes.push(KeyValue::new("server.port", i64::from(port)));
+        }
+        let span = tracer
+            .span_builder(method.to_string())
+            .with_kind(SpanKind::Client)
+            .with_attributes(attributes)
+            .start_with_context(&tracer, &Context::current());
+        let cx = Context::current_with_span(span);
+
+        global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&cx, &mut HeaderInjector(headers))
+        });
+        Self { cx }
+    }
+
+    /// Records the response status, or the error that prevented one, marking error statuses
+    /// and failures as errors.
+    pub(crate) fn record(&self, response: &Result<HttpResponse, ApiClientError>) {
+        let span = self.cx.span();
+        match response {
+            Ok(response) => {
+                let status = response.get_status();
+                span.set_attribute(KeyValue::new(
+                    "http.response.status_code",
+                    i64::from(status.as_u16()),
+                ));
+                if status.is_client_error() || status.is_server_error() {
+                    span.set_attribute(KeyValue::new("error.type", status.as_u16().to_string()));
+                    span.set_status(Status::error(status.to_string()));
+                }
+            }
+            Err(e) => {
+                span.set_attribute(KeyValue::new("error.type", error_type(e)));
+                span.set_status(Status::error(e.to_string()));
+            }
+        }
+    }
+}
+
+impl Drop for RequestSpan {
+    fn drop(&mut self) {
+        self.cx.span().end();
+    }
+}
+
+/// Low-cardinality description of a failure to get a response.
+fn error_type(error: &ApiClientError) -> &'static str {
+    match error {
+        ApiClientError::Http(e) if e.is_timeout() => "timeout",
+        ApiClientError::Http(e) if e.is_connect() => "connect",
+        ApiClientError::Http(_) => "http",
+        ApiClientError::Transport(_) => "transport",
+        _ => "_OTHER",
+    }
+}
+
+struct HeaderInjector<'a>(&'a mut HeaderMap);
+
+impl Injector for HeaderInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(key.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            self.0.insert(name, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::propagation::TextMapPropagator;
+    use opentelemetry::trace::{SpanContext, SpanId, TraceFlags, TraceId, TraceState};
+
+    /// Writes a fixed header, standing in for a W3C trace context propagator.
+    #[derive(Debug)]
+    struct FixedPropagator;
+
+    impl TextMapPropagator for FixedPropagator {
+        fn inject_context(&self, cx: &Context, injector: &mut dyn Injector) {
+            let span_context = cx.span().span_context().clone();
+            injector.set("x-trace-id", span_context.trace_id().to_string());
+        }
+
+        fn extract_with_context(
+            &self,
+            cx: &Context,
+            _extractor: &dyn opentelemetry::propagation::Extractor,
+        ) -> Context {
+            cx.clone()
+        }
+
+        fn fields(&self) -> opentelemetry::propagation::text_map_propagator::FieldIter<'_> {
+            opentelemetry::propagation::text_map_propagator::FieldIter::new(&[])
+        }
+    }
+
+    #[test]
+    fn test_injects_trace_context() {
+        global::set_text_map_propagator(FixedPropagator);
+        let parent = SpanContext::new(
+            TraceId::from(0x0af7651916cd43dd8448eb211c80319c),
+            SpanId::from(0xb7ad6b7169203331),
+            TraceFlags::SAMPLED,
+            true,
+            TraceState::default(),
+        );
+        let _guard = Context::current().with_remote_span_context(parent).attach();
+        let mut headers = HeaderMap::new();
+
+        let _span = RequestSpan::start(
+            Endpoint::Everything,
+            &Method::GET,
+            &Url::parse("https://newsapi.org/v2/everything?q=rust").unwrap(),
+            &mut headers,
+        );
+
+        assert_eq!(headers["x-trace-id"], "0af7651916cd43dd8448eb211c80319c");
+    }
+}
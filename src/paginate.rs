@@ -0,0 +1,586 @@
+use crate::client::NewsApiClient;
+use crate::constant::{DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
+use crate::error::ApiClientError;
+use crate::model::{Article, GetEverythingRequest};
+use crate::transport::HttpTransport;
+
+/// How [`EverythingPaginator::sample`] picks articles from the result set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleStrategy {
+    /// Uniformly random articles across the whole result set.
+    Random,
+    /// Every k-th article, starting with the first. `EveryKth(0)` behaves like `EveryKth(1)`.
+    EveryKth(usize),
+}
+
+/// Page size and number of pages for reading a target number of articles, see
+/// [`NewsApiClient::collect_everything`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PagePlan {
+    page_size: usize,
+    pages: usize,
+}
+
+impl PagePlan {
+    /// The plan reading `target` articles in the fewest requests, with the smallest page size
+    /// that needs no more of them. A `remaining_quota` too small for that caps the plan at as
+    /// many full pages as it allows.
+    pub fn for_target(target: usize, remaining_quota: Option<u32>) -> Self {
+        let max_page_size = MAX_PAGE_SIZE as usize;
+        let needed = target.div_ceil(max_page_size);
+        let pages = remaining_quota.map_or(needed, |quota| needed.min(quota as usize));
+        let page_size = if pages < needed {
+            max_page_size
+        } else {
+            target.div_ceil(pages.max(1))
+        };
+        Self { page_size, pages }
+    }
+
+    pub fn get_page_size(&self) -> usize {
+        self.page_size
+    }
+
+    pub fn get_pages(&self) -> usize {
+        self.pages
+    }
+
+    /// Most articles the plan can read.
+    pub fn capacity(&self) -> usize {
+        self.page_size * self.pages
+    }
+
+    /// `request` reading the plan's page size from the start of the result set.
+    pub(crate) fn apply(&self, request: &GetEverythingRequest) -> GetEverythingRequest {
+        request.with_page(1).with_page_size(self.page_size as i32)
+    }
+}
+
+/// Walks the pages of an everything search, starting at the request's page.
+///
+/// Created with [`NewsApiClient::everything_pages`].
+pub struct EverythingPaginator<'a, T = reqwest::Client> {
+    client: &'a NewsApiClient<T>,
+    request: GetEverythingRequest,
+    page_size: usize,
+    next_page: i32,
+    total_results: Option<usize>,
+    done: bool,
+}
+
+impl<'a, T: HttpTransport> EverythingPaginator<'a, T> {
+    pub(crate) fn new(client: &'a NewsApiClient<T>, request: &GetEverythingRequest) -> Self {
+        Self {
+            client,
+            request: request.clone(),
+            page_size: effective_page_size(request, client.get_config().get_default_page_size()),
+            next_page: (*request.get_page()).max(1),
+            total_results: None,
+            done: false,
+        }
+    }
+
+    /// Total results reported by the API, known once the first page has been fetched.
+    pub fn get_total_results(&self) -> Option<usize> {
+        self.total_results
+    }
+
+    /// Fetches the next page, or returns `None` once all results have been read.
+    ///
+    /// The paginator stops after the first error.
+    pub async fn next_page(&mut self) -> Option<Result<Vec<Article>, ApiClientError>> {
+        if self.done {
+            return None;
+        }
+
+        let page = self.next_page;
+        match self.fetch_page(page).await {
+            Ok(articles) => {
+                self.next_page += 1;
+                self.done = is_last_page(page, self.page_size, &articles, self.total_results);
+                Some(Ok(articles))
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+
+    /// Collects up to `n` articles chosen by `strategy`, fetching only the pages that contain
+    /// a sampled article.
+    pub async fn sample(
+        mut self,
+        n: usize,
+        strategy: SampleStrategy,
+    ) -> Result<Vec<Article>, ApiClientError> {
+        let start_page = self.next_page;
+        let first_page = match self.next_page().await {
+            Some(result) => result?,
+            None => return Ok(Vec::new()),
+        };
+
+        let skipped = (start_page as usize - 1) * self.page_size;
+        let population = self.total_results.unwrap_or(0).saturating_sub(skipped);
+        let positions = sample_positions(population, n, strategy);
+        log::debug!("Sampling positions {positions:?} of {population} results");
+
+        // Positions are sorted, so each page is fetched at most once.
+        let mut page_index = 0;
+        let mut page: Vec<Option<Article>> = first_page.into_iter().map(Some).collect();
+
+        let mut sample = Vec::with_capacity(positions.len());
+        for position in positions {
+            if position / self.page_size != page_index {
+                page_index = position / self.page_size;
+                let articles = self.fetch_page(start_page + page_index as i32).await?;
+                page = articles.into_iter().map(Some).collect();
+            }
+
+            if let Some(article) = page
+                .get_mut(position % self.page_size)
+                .and_then(Option::take)
+            {
+                sample.push(article);
+            }
+        }
+
+        Ok(sample)
+    }
+
+    async fn fetch_page(&mut self, page: i32) -> Result<Vec<Article>, ApiClientError> {
+        let response = self
+            .client
+            .get_everything(&self.request.with_page(page))
+            .await?;
+        self.total_results = Some((*response.get_total_results()).max(0) as usize);
+        Ok(response.into_articles())
+    }
+}
+
+/// Blocking counterpart of [`EverythingPaginator`], yielding one page of articles per
+/// iteration. Each page is fetched with the client's retry policy and iteration stops after
+/// the first error.
+///
+/// Created with [`NewsApiClient::everything_pages_iter`].
+#[cfg(feature = "blocking")]
+pub struct BlockingEverythingPaginator {
+    client: NewsApiClient<reqwest::blocking::Client>,
+    request: GetEverythingRequest,
+    page_size: usize,
+    next_page: i32,
+    total_results: Option<usize>,
+    done: bool,
+    prefetch: bool,
+    pending: Option<std::thread::JoinHandle<PageResult>>,
+}
+
+#[cfg(feature = "blocking")]
+type PageResult = Result<(Vec<Article>, usize), ApiClientError>;
+
+#[cfg(feature = "blocking")]
+impl BlockingEverythingPaginator {
+    pub(crate) fn new(
+        client: &NewsApiClient<reqwest::blocking::Client>,
+        request: &GetEverythingRequest,
+    ) -> Self {
+        Self {
+            client: client.clone(),
+            request: request.clone(),
+            page_size: effective_page_size(request, client.get_config().get_default_page_size()),
+            next_page: (*request.get_page()).max(1),
+            total_results: None,
+            done: false,
+            prefetch: false,
+            pending: None,
+        }
+    }
+
+    /// Fetches the next page on a background thread while the current one is processed, with
+    /// at most one request in flight. A prefetched page that is never read is discarded.
+    pub fn with_prefetch(mut self) -> Self {
+        self.prefetch = true;
+        self
+    }
+
+    /// Total results reported by the API, known once the first page has been fetched.
+    pub fn get_total_results(&self) -> Option<usize> {
+        self.total_results
+    }
+
+    fn fetch_page(
+        client: NewsApiClient<reqwest::blocking::Client>,
+        request: &GetEverythingRequest,
+        page: i32,
+    ) -> PageResult {
+        let response = client.get_everything(&request.with_page(page))?;
+        let total_results = (*response.get_total_results()).max(0) as usize;
+        Ok((response.into_articles(), total_results))
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl Iterator for BlockingEverythingPaginator {
+    type Item = Result<Vec<Article>, ApiClientError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let page = self.next_page;
+        let result = match self.pending.take() {
+            Some(prefetch) => prefetch.join().expect("prefetch thread panicked"),
+            None => Self::fetch_page(self.client.clone(), &self.request, page),
+        };
+        match result {
+            Ok((articles, total_results)) => {
+                self.total_results = Some(total_results);
+                self.next_page += 1;
+                self.done = is_last_page(page, self.page_size, &articles, self.total_results);
+                if self.prefetch && !self.done {
+                    let client = self.client.clone();
+                    let request = self.request.clone();
+                    let next_page = self.next_page;
+                    self.pending = Some(std::thread::spawn(move || {
+                        Self::fetch_page(client, &request, next_page)
+                    }));
+                }
+                Some(Ok(articles))
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Whether `page` holding `articles` is the last page of the result set.
+fn is_last_page(
+    page: i32,
+    page_size: usize,
+    articles: &[Article],
+    total_results: Option<usize>,
+) -> bool {
+    articles.is_empty() || (page as usize) * page_size >= total_results.unwrap_or(0)
+}
+
+/// The page size the API applies to a request: its own, else the client's default, clamped
+/// like the client does. The API defaults to 100 when none is sent.
+pub(crate) fn effective_page_size(
+    request: &GetEverythingRequest,
+    default_page_size: Option<i32>,
+) -> usize {
+    let page_size = match *request.get_page_size() {
+        0 => default_page_size.unwrap_or(0),
+        page_size => page_size,
+    };
+    match page_size {
+        0 => DEFAULT_PAGE_SIZE,
+        page_size => page_size.clamp(1, MAX_PAGE_SIZE) as usize,
+    }
+}
+
+/// Sorted result positions to sample from a result set of `population` articles.
+fn sample_positions(population: usize, n: usize, strategy: SampleStrategy) -> Vec<usize> {
+    let mut positions = match strategy {
+        SampleStrategy::Random => {
+            rand::seq::index::sample(&mut rand::rng(), population, n.min(population)).into_vec()
+        }
+        SampleStrategy::EveryKth(k) => (0..population).step_by(k.max(1)).take(n).collect(),
+    };
+    positions.sort_unstable();
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use url::Url;
+
+    /// Serves `total` generated articles, recording the pages requested.
+    async fn create_paged_server(
+        total: usize,
+    ) -> (mockito::ServerGuard, mockito::Mock, Arc<Mutex<Vec<usize>>>) {
+        let requested_pages = Arc::new(Mutex::new(Vec::new()));
+        let pages = requested_pages.clone();
+
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v2/everything")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body_from_request(move |request| {
+                let url = Url::parse(&format!("http://localhost{}", request.path_and_query()))
+                    .unwrap();
+                let param = |name: &str, default: usize| {
+                    url.query_pairs()
+                        .find(|(key, _)| key == name)
+                        .map_or(default, |(_, value)| value.parse().unwrap())
+                };
+                let page = param("page", 1);
+                let page_size = param("pageSize", 100);
+                pages.lock().unwrap().push(page);
+
+                let articles: Vec<String> = ((page - 1) * page_size..(page * page_size).min(total))
+                    .map(|i| {
+                        format!(
+                            r#"{{"source": {{"id": null, "name": "Source"}}, "title": "Article {i}",
+                            "url": "https://example.com/{i}", "publishedAt": "2023-05-01T12:00:00Z"}}"#
+                        )
+                    })
+                    .collect();
+                format!(
+                    r#"{{"status": "ok", "totalResults": {total}, "articles": [{}]}}"#,
+                    articles.join(",")
+                )
+                .into()
+            })
+            .create_async()
+            .await;
+
+        (server, mock, requested_pages)
+    }
+
+    fn titles(articles: &[Article]) -> Vec<String> {
+        articles.iter().map(|a| a.get_title().to_string()).collect()
+    }
+
+    #[test]
+    fn test_page_plan_for_target() {
+        let plan = |target, quota| {
+            let plan = PagePlan::for_target(target, quota);
+            (plan.get_page_size(), plan.get_pages())
+        };
+        assert_eq!(plan(0, None), (0, 0));
+        assert_eq!(plan(30, None), (30, 1));
+        assert_eq!(plan(100, None), (100, 1));
+        assert_eq!(plan(150, None), (75, 2));
+        assert_eq!(plan(250, None), (84, 3));
+        assert_eq!(plan(250, Some(2)), (100, 2));
+        assert_eq!(plan(250, Some(5)), (84, 3));
+        assert!(PagePlan::for_target(250, None).capacity() >= 250);
+    }
+
+    #[tokio::test]
+    async fn test_collect_everything_follows_plan() {
+        let (server, _m, requested_pages) = create_paged_server(500).await;
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .build()
+            .unwrap();
+        let request = GetEverythingRequest::builder()
+            .search_term("test".to_string())
+            .page(4)
+            .page_size(10)
+            .build();
+
+        let articles = client.collect_everything(&request, 150).await.unwrap();
+        assert_eq!(articles.len(), 150);
+        assert_eq!(articles[0].get_title(), "Article 0");
+        assert_eq!(*requested_pages.lock().unwrap(), vec![1, 2]);
+
+        let (small, _m, requested_pages) = create_paged_server(40).await;
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(small.url())
+            .build()
+            .unwrap();
+        let articles = client.collect_everything(&request, 150).await.unwrap();
+        assert_eq!(articles.len(), 40);
+        assert_eq!(*requested_pages.lock().unwrap(), vec![1]);
+    }
+
+    #[tokio::test]
+    async fn test_next_page_reads_all_pages() {
+        let (server, _m, requested_pages) = create_paged_server(5).await;
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .build()
+            .unwrap();
+        let request = GetEverythingRequest::builder()
+            .search_term("test".to_string())
+            .page_size(2)
+            .build();
+
+        let mut paginator = client.everything_pages(&request);
+        let mut articles = Vec::new();
+        while let Some(page) = paginator.next_page().await {
+            articles.extend(page.unwrap());
+        }
+
+        assert_eq!(paginator.get_total_results(), Some(5));
+        assert_eq!(articles.len(), 5);
+        assert_eq!(*requested_pages.lock().unwrap(), vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_sample_every_kth_skips_pages() {
+        let (server, _m, requested_pages) = create_paged_server(10).await;
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .build()
+            .unwrap();
+        let request = GetEverythingRequest::builder()
+            .search_term("test".to_string())
+            .page_size(2)
+            .build();
+
+        let sample = client
+            .everything_pages(&request)
+            .sample(2, SampleStrategy::EveryKth(6))
+            .await
+            .unwrap();
+
+        assert_eq!(titles(&sample), vec!["Article 0", "Article 6"]);
+        assert_eq!(*requested_pages.lock().unwrap(), vec![1, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_pages_use_client_default_page_size() {
+        let (server, _m, requested_pages) = create_paged_server(50).await;
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .default_page_size(20)
+            .build()
+            .unwrap();
+        let request = GetEverythingRequest::builder()
+            .search_term("test".to_string())
+            .build();
+
+        let mut paginator = client.everything_pages(&request);
+        let mut articles = Vec::new();
+        while let Some(page) = paginator.next_page().await {
+            articles.extend(page.unwrap());
+        }
+        assert_eq!(articles.len(), 50);
+        assert_eq!(*requested_pages.lock().unwrap(), vec![1, 2, 3]);
+
+        requested_pages.lock().unwrap().clear();
+        let sample = client
+            .everything_pages(&request)
+            .sample(2, SampleStrategy::EveryKth(25))
+            .await
+            .unwrap();
+        assert_eq!(titles(&sample), vec!["Article 0", "Article 25"]);
+        assert_eq!(*requested_pages.lock().unwrap(), vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_sample_random() {
+        let (server, _m, _) = create_paged_server(7).await;
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .build()
+            .unwrap();
+        let request = GetEverythingRequest::builder()
+            .search_term("test".to_string())
+            .page_size(3)
+            .build();
+
+        let sample = client
+            .everything_pages(&request)
+            .sample(4, SampleStrategy::Random)
+            .await
+            .unwrap();
+        let mut sampled = titles(&sample);
+        sampled.dedup();
+        assert_eq!(sampled.len(), 4);
+
+        let everything = client
+            .everything_pages(&request)
+            .sample(20, SampleStrategy::Random)
+            .await
+            .unwrap();
+        assert_eq!(everything.len(), 7);
+    }
+
+    #[cfg(feature = "blocking")]
+    #[tokio::test]
+    async fn test_everything_pages_iter_blocking() {
+        let (server, _m, requested_pages) = create_paged_server(5).await;
+        let url = server.url();
+
+        let (pages, total_results) = tokio::task::spawn_blocking(move || {
+            let client = NewsApiClient::builder_blocking()
+                .api_key("test-api-key")
+                .base_url(url)
+                .build()
+                .unwrap();
+            let request = GetEverythingRequest::builder()
+                .search_term("test".to_string())
+                .page_size(2)
+                .build();
+
+            let mut pages = client.everything_pages_iter(&request);
+            let sizes: Vec<usize> = pages.by_ref().map(|page| page.unwrap().len()).collect();
+            (sizes, pages.get_total_results())
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(pages, vec![2, 2, 1]);
+        assert_eq!(total_results, Some(5));
+        assert_eq!(*requested_pages.lock().unwrap(), vec![1, 2, 3]);
+    }
+
+    #[cfg(feature = "blocking")]
+    #[tokio::test]
+    async fn test_everything_pages_iter_prefetch_blocking() {
+        let (server, _m, requested_pages) = create_paged_server(5).await;
+        let url = server.url();
+        let pages = requested_pages.clone();
+
+        let titles = tokio::task::spawn_blocking(move || {
+            let client = NewsApiClient::builder_blocking()
+                .api_key("test-api-key")
+                .base_url(url)
+                .build()
+                .unwrap();
+            let request = GetEverythingRequest::builder()
+                .search_term("test".to_string())
+                .page_size(2)
+                .build();
+
+            let mut paginator = client.everything_pages_iter(&request).with_prefetch();
+            let first = paginator.next().unwrap().unwrap();
+            // The second page is requested while the first is being processed.
+            while pages.lock().unwrap().len() < 2 {
+                std::thread::yield_now();
+            }
+            let mut articles = first;
+            articles.extend(paginator.flat_map(Result::unwrap));
+            titles(&articles)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(titles.len(), 5);
+        assert_eq!(titles[4], "Article 4");
+        assert_eq!(*requested_pages.lock().unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_sample_positions() {
+        assert_eq!(
+            sample_positions(10, 3, SampleStrategy::EveryKth(4)),
+            vec![0, 4, 8]
+        );
+        assert_eq!(
+            sample_positions(3, 5, SampleStrategy::EveryKth(0)),
+            vec![0, 1, 2]
+        );
+
+        let random = sample_positions(50, 10, SampleStrategy::Random);
+        assert_eq!(random.len(), 10);
+        assert!(random.windows(2).all(|w| w[0] < w[1]));
+        assert!(random.iter().all(|&p| p < 50));
+    }
+}
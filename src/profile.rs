@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+/// Curated combination of timeout, retry, rate limiting, caching and concurrency settings
+/// for a common kind of deployment, applied with
+/// [`NewsApiClientBuilder::profile`](crate::client::NewsApiClientBuilder::profile).
+///
+/// Settings made after the profile override it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Profile {
+    /// User-facing requests that should answer quickly: a 3 second timeout per request, a
+    /// few short retries bounded by a 10 second deadline, a circuit breaker, and stale cached
+    /// responses while the API is unavailable.
+    Interactive,
+    /// Long-running backfills that should finish rather than finish fast: a 30 second
+    /// timeout per request, patient exponential retries on network and rate-limit errors, at
+    /// most 60 requests a minute and two in flight.
+    BatchBackfill,
+    /// Keys with a small daily quota, such as the developer plan: a 10 second timeout per
+    /// request, a local budget of 100 requests a day, a single retry on network errors only,
+    /// and conditional requests so repeated queries reuse cached responses.
+    LowQuota,
+}
+
+impl Profile {
+    pub(crate) const INTERACTIVE_TIMEOUT: Duration = Duration::from_secs(3);
+    pub(crate) const INTERACTIVE_DEADLINE: Duration = Duration::from_secs(10);
+    pub(crate) const BACKFILL_TIMEOUT: Duration = Duration::from_secs(30);
+    pub(crate) const BACKFILL_REQUESTS_PER_MINUTE: u32 = 60;
+    pub(crate) const LOW_QUOTA_TIMEOUT: Duration = Duration::from_secs(10);
+    pub(crate) const LOW_QUOTA_DAILY_LIMIT: u32 = 100;
+}
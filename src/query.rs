@@ -0,0 +1,318 @@
+use crate::constant::MAX_QUERY_LENGTH;
+use regex::RegexBuilder;
+use std::fmt;
+
+/// Share of [`MAX_QUERY_LENGTH`] from which [`QueryWarning::Length`] is reported.
+const LENGTH_WARNING_RATIO: f64 = 0.9;
+
+/// Operators NewsAPI only recognizes in uppercase.
+const OPERATORS: [&str; 3] = ["AND", "OR", "NOT"];
+
+/// Likely mistake in an everything search query, found by [`Query::lint`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryWarning {
+    /// An odd number of `"`, leaving a phrase unterminated.
+    UnbalancedQuotes,
+    /// A `)` without a matching `(`, or a `(` that is never closed.
+    UnbalancedParentheses,
+    /// A `+` inside a word, usually a form-encoded space. NewsAPI reads `+` as "must appear"
+    /// only at the start of a word.
+    StrayPlus { position: usize },
+    /// A percent-encoded character such as `%20`. The client encodes queries itself, so
+    /// these are sent literally.
+    PercentEncoded(String),
+    /// An operator written in lowercase, which is searched for as a literal word.
+    LowercaseOperator(String),
+    /// The query is close to or over the API's length limit.
+    Length { length: usize, max: usize },
+}
+
+impl fmt::Display for QueryWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryWarning::UnbalancedQuotes => write!(f, "Unbalanced quotes"),
+            QueryWarning::UnbalancedParentheses => write!(f, "Unbalanced parentheses"),
+            QueryWarning::StrayPlus { position } => {
+                write!(f, "Stray '+' at position {position}, use a space instead")
+            }
+            QueryWarning::PercentEncoded(encoded) => {
+                write!(f, "Percent-encoded '{encoded}' is sent literally")
+            }
+            QueryWarning::LowercaseOperator(operator) => write!(
+                f,
+                "Lowercase '{operator}' is searched literally, use '{}'",
+                operator.to_uppercase()
+            ),
+            QueryWarning::Length { length, max } => {
+                write!(f, "Query is {length} characters long, the limit is {max}")
+            }
+        }
+    }
+}
+
+/// Article field a query term was found in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MatchField {
+    Title,
+    Description,
+}
+
+/// Occurrence of a query term in an article, as byte offsets into the field, see
+/// [`Article::get_term_matches`](crate::model::Article::get_term_matches).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TermMatch {
+    term: String,
+    field: MatchField,
+    start: usize,
+    end: usize,
+}
+
+impl TermMatch {
+    pub fn get_term(&self) -> &str {
+        &self.term
+    }
+
+    pub fn get_field(&self) -> MatchField {
+        self.field
+    }
+
+    pub fn get_start(&self) -> usize {
+        self.start
+    }
+
+    pub fn get_end(&self) -> usize {
+        self.end
+    }
+}
+
+/// Search query for the everything endpoint.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Query(String);
+
+impl Query {
+    pub fn new(query: impl Into<String>) -> Self {
+        Self(query.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Checks the query for likely mistakes, see [`Query::lint_raw`].
+    pub fn lint(&self) -> Vec<QueryWarning> {
+        Self::lint_raw(&self.0)
+    }
+
+    /// Checks a query string for likely mistakes before spending quota on it. Quoted
+    /// phrases are exempt from the operator and `+` checks.
+    pub fn lint_raw(query: &str) -> Vec<QueryWarning> {
+        let mut warnings = Vec::new();
+
+        if !query.matches('"').count().is_multiple_of(2) {
+            warnings.push(QueryWarning::UnbalancedQuotes);
+        }
+
+        let mut depth = 0_u32;
+        let mut unmatched_close = false;
+        let mut in_phrase = false;
+        let mut previous = None;
+        for (position, c) in query.char_indices() {
+            match c {
+                '"' => in_phrase = !in_phrase,
+                '(' if !in_phrase => depth += 1,
+                ')' if !in_phrase => match depth.checked_sub(1) {
+                    Some(d) => depth = d,
+                    None => unmatched_close = true,
+                },
+                '+' if !in_phrase
+                    && previous.is_some_and(|p: char| !p.is_whitespace() && p != '(') =>
+                {
+                    warnings.push(QueryWarning::StrayPlus { position });
+                }
+                _ => {}
+            }
+            previous = Some(c);
+        }
+        if depth != 0 || unmatched_close {
+            warnings.push(QueryWarning::UnbalancedParentheses);
+        }
+
+        for (position, _) in query.match_indices('%') {
+            let encoded = query.get(position..position + 3).unwrap_or_default();
+            if encoded.len() == 3 && encoded[1..].chars().all(|c| c.is_ascii_hexdigit()) {
+                warnings.push(QueryWarning::PercentEncoded(encoded.to_string()));
+            }
+        }
+
+        for word in unquoted(query).split_whitespace() {
+            let word = word.trim_matches(|c| c == '(' || c == ')');
+            if word != word.to_uppercase() && OPERATORS.contains(&word.to_uppercase().as_str()) {
+                warnings.push(QueryWarning::LowercaseOperator(word.to_string()));
+            }
+        }
+
+        let length = query.chars().count();
+        if length as f64 >= MAX_QUERY_LENGTH as f64 * LENGTH_WARNING_RATIO {
+            warnings.push(QueryWarning::Length {
+                length,
+                max: MAX_QUERY_LENGTH,
+            });
+        }
+
+        warnings
+    }
+}
+
+impl Query {
+    /// Terms an article can match: quoted phrases and words other than operators. Excluded
+    /// (`-word`) terms are left out and `+` and parentheses are stripped.
+    pub fn terms(&self) -> Vec<String> {
+        let mut terms: Vec<String> = Vec::new();
+        for (index, part) in self.0.split('"').enumerate() {
+            let candidates: Vec<&str> = if index % 2 == 1 {
+                vec![part.trim()]
+            } else {
+                part.split_whitespace()
+                    .map(|word| word.trim_matches(|c| c == '(' || c == ')'))
+                    .filter(|word| !OPERATORS.contains(word) && !word.starts_with('-'))
+                    .map(|word| word.trim_start_matches('+'))
+                    .collect()
+            };
+            for term in candidates {
+                if !term.is_empty() && !terms.iter().any(|t| t.eq_ignore_ascii_case(term)) {
+                    terms.push(term.to_string());
+                }
+            }
+        }
+        terms
+    }
+
+    /// Case-insensitive occurrences of the query's [`terms`](Self::terms) in an article's
+    /// title and description, ordered by field and position.
+    pub fn find_matches(&self, title: &str, description: Option<&str>) -> Vec<TermMatch> {
+        let patterns: Vec<_> = self
+            .terms()
+            .into_iter()
+            .filter_map(|term| {
+                RegexBuilder::new(&regex::escape(&term))
+                    .case_insensitive(true)
+                    .build()
+                    .ok()
+                    .map(|pattern| (term, pattern))
+            })
+            .collect();
+
+        let fields = [
+            (MatchField::Title, Some(title)),
+            (MatchField::Description, description),
+        ];
+        let mut matches = Vec::new();
+        for (field, text) in fields {
+            let Some(text) = text else { continue };
+            let start = matches.len();
+            for (term, pattern) in &patterns {
+                matches.extend(pattern.find_iter(text).map(|found| TermMatch {
+                    term: term.clone(),
+                    field,
+                    start: found.start(),
+                    end: found.end(),
+                }));
+            }
+            matches[start..].sort_by_key(|m| (m.start, m.end));
+        }
+        matches
+    }
+}
+
+/// The parts of `query` outside quoted phrases.
+fn unquoted(query: &str) -> String {
+    query.split('"').step_by(2).collect::<Vec<_>>().join(" ")
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for Query {
+    fn from(query: &str) -> Self {
+        Self::new(query)
+    }
+}
+
+impl From<String> for Query {
+    fn from(query: String) -> Self {
+        Self(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint_clean_query() {
+        assert!(
+            Query::new(r#"+bitcoin AND ("crypto currency" OR -ethereum)"#)
+                .lint()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_lint_warnings() {
+        assert_eq!(
+            Query::lint_raw(r#"(rust and "go or not"#),
+            vec![
+                QueryWarning::UnbalancedQuotes,
+                QueryWarning::UnbalancedParentheses,
+                QueryWarning::LowercaseOperator("and".to_string()),
+            ]
+        );
+        assert_eq!(
+            Query::lint_raw("rust+lang%20news) ("),
+            vec![
+                QueryWarning::StrayPlus { position: 4 },
+                QueryWarning::UnbalancedParentheses,
+                QueryWarning::PercentEncoded("%20".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_terms_and_matches() {
+        let query = Query::new(r#"+Bitcoin AND ("crypto currency" OR -ethereum) bitcoin"#);
+        assert_eq!(query.terms(), vec!["Bitcoin", "crypto currency"]);
+
+        let matches = query.find_matches(
+            "BITCOIN rallies",
+            Some("Crypto currency markets follow bitcoin"),
+        );
+        let spans: Vec<_> = matches
+            .iter()
+            .map(|m| (m.get_field(), m.get_term(), m.get_start(), m.get_end()))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                (MatchField::Title, "Bitcoin", 0, 7),
+                (MatchField::Description, "crypto currency", 0, 15),
+                (MatchField::Description, "Bitcoin", 31, 38),
+            ]
+        );
+        assert!(query.find_matches("Ethereum news", None).is_empty());
+    }
+
+    #[test]
+    fn test_lint_length() {
+        assert!(Query::lint_raw(&"a".repeat(449)).is_empty());
+        assert_eq!(
+            Query::lint_raw(&"a".repeat(501)),
+            vec![QueryWarning::Length {
+                length: 501,
+                max: MAX_QUERY_LENGTH
+            }]
+        );
+    }
+}
@@ -0,0 +1,551 @@
+use crate::error::ApiClientError;
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Default usage ratios at which quota threshold callbacks fire.
+pub const DEFAULT_QUOTA_THRESHOLDS: [f64; 2] = [0.8, 0.95];
+
+type QuotaThresholdCallback = Arc<dyn Fn(u32, u32) + Send + Sync>;
+
+/// Snapshot of the client's view of the NewsAPI quota.
+///
+/// All clones of a client share the same quota state, so a pause triggered by one task
+/// is observed by every other task using the same client.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QuotaStatus {
+    paused_until: Option<DateTime<Utc>>,
+    used: Option<u32>,
+    limit: Option<u32>,
+}
+
+impl QuotaStatus {
+    /// Time until which requests are paused after an `apiKeyExhausted`/`rateLimited`
+    /// response that advertised its reset time.
+    pub fn get_paused_until(&self) -> Option<&DateTime<Utc>> {
+        self.paused_until.as_ref()
+    }
+
+    /// Whether requests were paused when the status was taken.
+    pub fn is_paused(&self) -> bool {
+        self.paused_until.is_some()
+    }
+
+    /// Requests counted against the [`QuotaBudget`] in its current window.
+    pub fn get_used(&self) -> Option<u32> {
+        self.used
+    }
+
+    /// Limit of the configured [`QuotaBudget`].
+    pub fn get_limit(&self) -> Option<u32> {
+        self.limit
+    }
+
+    pub fn get_remaining(&self) -> Option<u32> {
+        Some(self.limit?.saturating_sub(self.used?))
+    }
+}
+
+/// Local budget of requests per rolling window, such as the 100 requests per day of the
+/// NewsAPI developer plan.
+///
+/// Once the budget is spent the client fails with [`ApiClientError::QuotaExceeded`] instead
+/// of sending requests the API would reject.
+#[derive(Debug)]
+pub struct QuotaBudget {
+    limit: u32,
+    window: chrono::Duration,
+    requests: Mutex<VecDeque<DateTime<Utc>>>,
+    store: Option<Box<dyn QuotaStore>>,
+    /// Number of request logs handed to the store, and the newest one it saved, so a slow
+    /// save never overwrites a newer log.
+    recorded: AtomicU64,
+    saved: Mutex<u64>,
+}
+
+impl QuotaBudget {
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit,
+            window: chrono::Duration::from_std(window).unwrap_or(chrono::Duration::MAX),
+            requests: Mutex::new(VecDeque::new()),
+            store: None,
+            recorded: AtomicU64::new(0),
+            saved: Mutex::new(0),
+        }
+    }
+
+    /// Budget of `limit` requests per rolling 24 hours.
+    pub fn daily(limit: u32) -> Self {
+        Self::new(limit, Duration::from_secs(24 * 60 * 60))
+    }
+
+    /// Persists the request log to `store`, so usage survives process restarts.
+    ///
+    /// Requests already recorded in the store are loaded immediately and expire against the
+    /// client's clock on first use. Failures to load or save are logged and otherwise ignored,
+    /// leaving the budget purely in memory.
+    pub fn with_store(mut self, store: impl QuotaStore + 'static) -> Self {
+        match store.load() {
+            Ok(mut loaded) => {
+                loaded.sort_unstable();
+                self.requests = Mutex::new(VecDeque::from(loaded));
+            }
+            Err(e) => log::warn!("Failed to load quota usage: {e}"),
+        }
+        self.store = Some(Box::new(store));
+        self
+    }
+
+    /// Records a request at `now` and returns the usage including it, or returns when the
+    /// next request will be allowed.
+    fn try_acquire(&self, now: DateTime<Utc>) -> Result<u32, DateTime<Utc>> {
+        let mut requests = self.requests.lock().unwrap();
+        self.expire(&mut requests, now);
+
+        if requests.len() >= self.limit as usize {
+            return Err(requests.front().map_or(now, |oldest| {
+                self.expiry(*oldest).unwrap_or(DateTime::<Utc>::MAX_UTC)
+            }));
+        }
+
+        requests.push_back(now);
+        let used = requests.len() as u32;
+        if let Some(store) = &self.store {
+            let log = Vec::from(requests.clone());
+            let generation = self.recorded.fetch_add(1, Ordering::Relaxed) + 1;
+            drop(requests);
+            self.save(store.as_ref(), &log, generation);
+        }
+        Ok(used)
+    }
+
+    /// Saves `log` unless a newer one was saved already, outside the lock on the request log
+    /// so callers only checking the budget don't wait for the store.
+    fn save(&self, store: &dyn QuotaStore, log: &[DateTime<Utc>], generation: u64) {
+        let mut saved = self.saved.lock().unwrap();
+        if *saved > generation {
+            return;
+        }
+        match store.save(log) {
+            Ok(()) => *saved = generation,
+            Err(e) => log::warn!("Failed to save quota usage: {e}"),
+        }
+    }
+
+    fn used(&self, now: DateTime<Utc>) -> u32 {
+        let mut requests = self.requests.lock().unwrap();
+        self.expire(&mut requests, now);
+        requests.len() as u32
+    }
+
+    fn expire(&self, requests: &mut VecDeque<DateTime<Utc>>, now: DateTime<Utc>) {
+        while requests
+            .front()
+            .and_then(|oldest| self.expiry(*oldest))
+            .is_some_and(|expiry| expiry <= now)
+        {
+            requests.pop_front();
+        }
+    }
+
+    /// When a request made at `request` leaves the window, or `None` if it never does.
+    fn expiry(&self, request: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        request.checked_add_signed(self.window)
+    }
+}
+
+/// Storage for the request log of a [`QuotaBudget`], see [`QuotaBudget::with_store`].
+///
+/// [`save`](Self::save) is called synchronously for every request the budget admits, on the
+/// thread sending it, which is an async runtime worker for the async client. Stores that
+/// block, like [`FileQuotaStore`], hold up that worker and other savers for the duration.
+pub trait QuotaStore: fmt::Debug + Send + Sync {
+    /// Returns the times of previously recorded requests.
+    fn load(&self) -> io::Result<Vec<DateTime<Utc>>>;
+
+    /// Replaces the stored request log with `requests`, oldest first.
+    fn save(&self, requests: &[DateTime<Utc>]) -> io::Result<()>;
+}
+
+/// Stores request times in a file, one RFC 3339 timestamp per line.
+///
+/// A missing file counts as no usage. The store does not lock the file, so processes
+/// sharing one budget file should not run concurrently.
+#[derive(Debug, Clone)]
+pub struct FileQuotaStore {
+    path: PathBuf,
+}
+
+impl FileQuotaStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl QuotaStore for FileQuotaStore {
+    fn load(&self) -> io::Result<Vec<DateTime<Utc>>> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                DateTime::parse_from_rfc3339(line.trim())
+                    .map(|date| date.with_timezone(&Utc))
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            })
+            .collect()
+    }
+
+    fn save(&self, requests: &[DateTime<Utc>]) -> io::Result<()> {
+        let contents: String = requests
+            .iter()
+            .map(|request| format!("{}\n", request.to_rfc3339()))
+            .collect();
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        fs::write(&tmp, contents)?;
+        fs::rename(tmp, &self.path)
+    }
+}
+
+/// Callback fired when budget usage crosses one of the configured ratios.
+#[derive(Clone)]
+pub(crate) struct QuotaAlarm {
+    thresholds: Vec<f64>,
+    callback: QuotaThresholdCallback,
+}
+
+impl QuotaAlarm {
+    pub(crate) fn new(thresholds: Vec<f64>, callback: QuotaThresholdCallback) -> Self {
+        Self {
+            thresholds,
+            callback,
+        }
+    }
+
+    /// Fires the callback once when usage went from `used - 1` to `used` across a threshold.
+    fn notify(&self, used: u32, limit: u32) {
+        let crossed = self.thresholds.iter().any(|threshold| {
+            let mark = threshold * f64::from(limit);
+            f64::from(used - 1) < mark && f64::from(used) >= mark
+        });
+        if crossed {
+            log::debug!("Quota usage {used}/{limit} crossed a threshold");
+            (self.callback)(used, limit);
+        }
+    }
+}
+
+impl fmt::Debug for QuotaAlarm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QuotaAlarm")
+            .field("thresholds", &self.thresholds)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct QuotaState {
+    paused_until: Mutex<Option<DateTime<Utc>>>,
+    budget: Option<QuotaBudget>,
+    alarm: Option<QuotaAlarm>,
+}
+
+impl QuotaState {
+    pub(crate) fn new(budget: Option<QuotaBudget>, alarm: Option<QuotaAlarm>) -> Self {
+        Self {
+            paused_until: Mutex::default(),
+            budget,
+            alarm,
+        }
+    }
+
+    /// Pauses all requests until `until`. An existing later pause is kept.
+    pub(crate) fn pause_until(&self, until: DateTime<Utc>) {
+        let mut paused_until = self.paused_until.lock().unwrap();
+        if paused_until.is_none_or(|current| current < until) {
+            log::debug!("Pausing requests until {until}");
+            *paused_until = Some(until);
+        }
+    }
+
+    /// Fails fast with [`ApiClientError::Paused`] while a pause is in effect at `now`.
+    pub(crate) fn check(&self, now: DateTime<Utc>) -> Result<(), ApiClientError> {
+        let mut paused_until = self.paused_until.lock().unwrap();
+        match *paused_until {
+            Some(until) if until > now => Err(ApiClientError::Paused(until)),
+            Some(_) => {
+                *paused_until = None;
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Counts a request against the budget, failing with [`ApiClientError::QuotaExceeded`]
+    /// when it is spent.
+    pub(crate) fn acquire(&self, now: DateTime<Utc>) -> Result<(), ApiClientError> {
+        let Some(budget) = &self.budget else {
+            return Ok(());
+        };
+
+        let used = budget
+            .try_acquire(now)
+            .map_err(|resets_at| ApiClientError::QuotaExceeded {
+                limit: budget.limit,
+                resets_at,
+            })?;
+        if let Some(alarm) = &self.alarm {
+            alarm.notify(used, budget.limit);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn status(&self, now: DateTime<Utc>) -> QuotaStatus {
+        QuotaStatus {
+            paused_until: self
+                .paused_until
+                .lock()
+                .unwrap()
+                .filter(|until| *until > now),
+            used: self.budget.as_ref().map(|budget| budget.used(now)),
+            limit: self.budget.as_ref().map(|budget| budget.limit),
+        }
+    }
+}
+
+/// Parses a `Retry-After` header value, given either as delay seconds or as an HTTP date.
+pub(crate) fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<i64>() {
+        return Some(
+            chrono::Duration::try_seconds(seconds.max(0))
+                .and_then(|delay| now.checked_add_signed(delay))
+                .unwrap_or(DateTime::<Utc>::MAX_UTC),
+        );
+    }
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = DateTime::<Utc>::from_str("2023-01-01T00:00:00Z").unwrap();
+
+        assert_eq!(
+            parse_retry_after("120", now).unwrap(),
+            DateTime::<Utc>::from_str("2023-01-01T00:02:00Z").unwrap()
+        );
+        assert_eq!(
+            parse_retry_after("Sun, 01 Jan 2023 01:00:00 GMT", now).unwrap(),
+            DateTime::<Utc>::from_str("2023-01-01T01:00:00Z").unwrap()
+        );
+        assert!(parse_retry_after("soon", now).is_none());
+    }
+
+    #[test]
+    fn test_parse_retry_after_oversized() {
+        let now = DateTime::<Utc>::from_str("2023-01-01T00:00:00Z").unwrap();
+
+        assert_eq!(
+            parse_retry_after("10000000000000", now),
+            Some(DateTime::<Utc>::MAX_UTC)
+        );
+        assert_eq!(
+            parse_retry_after(&i64::MAX.to_string(), now),
+            Some(DateTime::<Utc>::MAX_UTC)
+        );
+    }
+
+    #[test]
+    fn test_quota_state_pause() {
+        let state = QuotaState::default();
+        let now = Utc::now();
+        assert!(state.check(now).is_ok());
+        assert!(!state.status(now).is_paused());
+
+        let until = now + chrono::Duration::minutes(5);
+        state.pause_until(until);
+        state.pause_until(until - chrono::Duration::minutes(1));
+
+        assert!(state.status(now).is_paused());
+        assert_eq!(state.status(now).get_paused_until(), Some(&until));
+        assert!(matches!(state.check(now), Err(ApiClientError::Paused(t)) if t == until));
+        assert!(!state.status(until).is_paused());
+    }
+
+    #[test]
+    fn test_quota_budget_rolling_window() {
+        let budget = QuotaBudget::daily(2);
+        let start = DateTime::<Utc>::from_str("2023-01-01T00:00:00Z").unwrap();
+
+        assert_eq!(budget.try_acquire(start), Ok(1));
+        assert_eq!(
+            budget.try_acquire(start + chrono::Duration::hours(12)),
+            Ok(2)
+        );
+        assert_eq!(
+            budget.try_acquire(start + chrono::Duration::hours(13)),
+            Err(start + chrono::Duration::hours(24))
+        );
+        assert_eq!(budget.used(start + chrono::Duration::hours(13)), 2);
+
+        assert!(budget
+            .try_acquire(start + chrono::Duration::hours(24))
+            .is_ok());
+        assert_eq!(budget.used(start + chrono::Duration::hours(24)), 2);
+    }
+
+    #[test]
+    fn test_quota_budget_unbounded_window() {
+        let budget = QuotaBudget::new(1, Duration::MAX);
+        let start = DateTime::<Utc>::from_str("2023-01-01T00:00:00Z").unwrap();
+
+        assert_eq!(budget.try_acquire(start), Ok(1));
+        assert_eq!(
+            budget.try_acquire(start + chrono::Duration::days(365)),
+            Err(DateTime::<Utc>::MAX_UTC)
+        );
+        assert_eq!(budget.used(DateTime::<Utc>::MAX_UTC), 1);
+    }
+
+    #[test]
+    fn test_file_quota_store_survives_restart() {
+        let path =
+            std::env::temp_dir().join(format!("newsapi-rs-quota-{}.log", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let budget = QuotaBudget::daily(2).with_store(FileQuotaStore::new(&path));
+        assert!(budget.try_acquire(Utc::now()).is_ok());
+        drop(budget);
+
+        let restarted = QuotaBudget::daily(2).with_store(FileQuotaStore::new(&path));
+        assert_eq!(restarted.used(Utc::now()), 1);
+        assert_eq!(restarted.try_acquire(Utc::now()), Ok(2));
+        assert!(restarted.try_acquire(Utc::now()).is_err());
+        assert_eq!(FileQuotaStore::new(&path).load().unwrap().len(), 2);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_file_quota_store_temp_file_is_per_path() {
+        let dir = std::env::temp_dir().join(format!("newsapi-rs-quota-dir-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        // The temporary file of a store at `quota.tmp` would collide with this directory.
+        fs::create_dir_all(dir.join("quota.tmp")).unwrap();
+
+        let store = FileQuotaStore::new(dir.join("quota.a"));
+        store.save(&[Utc::now()]).unwrap();
+        assert_eq!(store.load().unwrap().len(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_quota_budget_skips_stale_saves() {
+        #[derive(Debug, Default)]
+        struct RecordingStore(Mutex<Vec<usize>>);
+
+        impl QuotaStore for RecordingStore {
+            fn load(&self) -> io::Result<Vec<DateTime<Utc>>> {
+                Ok(Vec::new())
+            }
+
+            fn save(&self, requests: &[DateTime<Utc>]) -> io::Result<()> {
+                self.0.lock().unwrap().push(requests.len());
+                Ok(())
+            }
+        }
+
+        let budget = QuotaBudget::daily(10);
+        let store = RecordingStore::default();
+        let now = Utc::now();
+        budget.save(&store, &[now, now], 2);
+        budget.save(&store, &[now], 1);
+
+        assert_eq!(*store.0.lock().unwrap(), vec![2]);
+    }
+
+    #[test]
+    fn test_quota_budget_store_expires_against_caller_time() {
+        let path =
+            std::env::temp_dir().join(format!("newsapi-rs-quota-clock-{}.log", std::process::id()));
+        let start = DateTime::<Utc>::from_str("2023-01-01T00:00:00Z").unwrap();
+        FileQuotaStore::new(&path).save(&[start]).unwrap();
+
+        let budget = QuotaBudget::daily(2).with_store(FileQuotaStore::new(&path));
+        assert_eq!(budget.used(start + chrono::Duration::hours(1)), 1);
+        assert_eq!(budget.used(start + chrono::Duration::hours(24)), 0);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_file_quota_store_missing_file() {
+        let store = FileQuotaStore::new("/nonexistent/newsapi-rs-quota.log");
+        assert!(store.load().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_quota_state_acquire() {
+        let state = QuotaState::new(Some(QuotaBudget::daily(1)), None);
+        let now = Utc::now();
+
+        assert!(state.acquire(now).is_ok());
+        assert!(matches!(
+            state.acquire(now),
+            Err(ApiClientError::QuotaExceeded { limit: 1, .. })
+        ));
+
+        let status = state.status(now);
+        assert_eq!(status.get_used(), Some(1));
+        assert_eq!(status.get_limit(), Some(1));
+        assert_eq!(status.get_remaining(), Some(0));
+        assert!(QuotaState::default().acquire(now).is_ok());
+    }
+
+    #[test]
+    fn test_quota_alarm_fires_on_crossing() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let recorded = calls.clone();
+        let alarm = QuotaAlarm::new(
+            DEFAULT_QUOTA_THRESHOLDS.to_vec(),
+            Arc::new(move |used, limit| recorded.lock().unwrap().push((used, limit))),
+        );
+        let state = QuotaState::new(Some(QuotaBudget::daily(20)), Some(alarm));
+
+        for _ in 0..20 {
+            state.acquire(Utc::now()).unwrap();
+        }
+
+        assert_eq!(*calls.lock().unwrap(), vec![(16, 20), (19, 20)]);
+    }
+
+    #[test]
+    fn test_quota_state_pause_expires() {
+        let state = QuotaState::default();
+        let now = Utc::now();
+        state.pause_until(now - chrono::Duration::seconds(1));
+
+        assert!(state.check(now).is_ok());
+        assert_eq!(state.status(now), QuotaStatus::default());
+    }
+}
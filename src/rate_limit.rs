@@ -0,0 +1,309 @@
+use crate::clock::Clock;
+#[cfg(all(feature = "governor", feature = "blocking"))]
+use governor::clock::Clock as _;
+#[cfg(feature = "governor")]
+use governor::{DefaultDirectRateLimiter, DefaultKeyedRateLimiter, Jitter};
+#[cfg(feature = "blocking")]
+use std::sync::Condvar;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+/// Rate limiter installed in front of all endpoints of a client.
+#[derive(Debug, Clone)]
+pub(crate) enum Limiter {
+    TokenBucket(Arc<RateLimiter>),
+    #[cfg(feature = "governor")]
+    Governor(Arc<DefaultDirectRateLimiter>, Option<Jitter>),
+    /// Keyed by API key, so clients using different keys can share one limiter.
+    #[cfg(feature = "governor")]
+    KeyedGovernor(Arc<DefaultKeyedRateLimiter<String>>, Option<Jitter>),
+}
+
+impl Limiter {
+    /// Whether this is the token bucket built from the client's own rate limit setting,
+    /// rather than a `governor` limiter supplied to the builder.
+    pub(crate) fn is_token_bucket(&self) -> bool {
+        matches!(self, Limiter::TokenBucket(_))
+    }
+
+    #[cfg_attr(not(feature = "governor"), allow(unused_variables))]
+    pub(crate) async fn acquire(&self, api_key: &str) {
+        match self {
+            Limiter::TokenBucket(limiter) => limiter.acquire().await,
+            #[cfg(feature = "governor")]
+            Limiter::Governor(limiter, jitter) => {
+                limiter
+                    .until_ready_with_jitter(jitter.unwrap_or_default())
+                    .await;
+            }
+            #[cfg(feature = "governor")]
+            Limiter::KeyedGovernor(limiter, jitter) => {
+                limiter
+                    .until_key_ready_with_jitter(&api_key.to_string(), jitter.unwrap_or_default())
+                    .await;
+            }
+        }
+    }
+
+    #[cfg(feature = "blocking")]
+    #[cfg_attr(not(feature = "governor"), allow(unused_variables))]
+    pub(crate) fn acquire_blocking(&self, api_key: &str) {
+        match self {
+            Limiter::TokenBucket(limiter) => limiter.acquire_blocking(),
+            #[cfg(feature = "governor")]
+            Limiter::Governor(limiter, jitter) => {
+                while let Err(not_until) = limiter.check() {
+                    let wait = not_until.wait_time_from(limiter.clock().now());
+                    std::thread::sleep(jitter.unwrap_or_default() + wait);
+                }
+            }
+            #[cfg(feature = "governor")]
+            Limiter::KeyedGovernor(limiter, jitter) => {
+                let key = api_key.to_string();
+                while let Err(not_until) = limiter.check_key(&key) {
+                    let wait = not_until.wait_time_from(limiter.clock().now());
+                    std::thread::sleep(jitter.unwrap_or_default() + wait);
+                }
+            }
+        }
+    }
+}
+
+/// Token bucket allowing `capacity` requests per `window`, refilled continuously.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    capacity: f64,
+    window: Duration,
+    bucket: Mutex<Bucket>,
+    clock: Arc<dyn Clock>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(requests_per_window: u32, window: Duration, clock: Arc<dyn Clock>) -> Self {
+        let capacity = f64::from(requests_per_window);
+        Self {
+            capacity,
+            window,
+            bucket: Mutex::new(Bucket {
+                tokens: capacity,
+                last_refill: clock.now(),
+            }),
+            clock,
+        }
+    }
+
+    /// Takes a token if one is available, otherwise returns how long until the next one is.
+    fn try_acquire(&self) -> Result<(), Duration> {
+        let mut bucket = self.bucket.lock().unwrap();
+        let now = self.clock.now();
+        let refill_per_sec = self.capacity / self.window.as_secs_f64();
+
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * refill_per_sec).min(self.capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / refill_per_sec,
+            ))
+        }
+    }
+
+    pub(crate) async fn acquire(&self) {
+        while let Err(wait) = self.try_acquire() {
+            log::debug!("Rate limit reached, waiting {wait:?}");
+            self.clock.sleep(wait).await;
+        }
+    }
+
+    #[cfg(feature = "blocking")]
+    pub(crate) fn acquire_blocking(&self) {
+        while let Err(wait) = self.try_acquire() {
+            log::debug!("Rate limit reached, waiting {wait:?}");
+            self.clock.sleep_blocking(wait);
+        }
+    }
+}
+
+/// Caps the number of requests in flight across all endpoints and clones of a client.
+#[derive(Debug)]
+pub(crate) struct ConcurrencyLimiter {
+    semaphore: Semaphore,
+    #[cfg(feature = "blocking")]
+    released: (Mutex<()>, Condvar),
+}
+
+impl ConcurrencyLimiter {
+    pub(crate) fn new(max_concurrent_requests: usize) -> Self {
+        Self {
+            semaphore: Semaphore::new(max_concurrent_requests),
+            #[cfg(feature = "blocking")]
+            released: (Mutex::new(()), Condvar::new()),
+        }
+    }
+
+    pub(crate) async fn acquire(&self) -> SemaphorePermit<'_> {
+        self.semaphore
+            .acquire()
+            .await
+            .expect("semaphore is never closed")
+    }
+
+    #[cfg(feature = "blocking")]
+    pub(crate) fn acquire_blocking(&self) -> BlockingPermit<'_> {
+        let (lock, released) = &self.released;
+        let mut guard = lock.lock().unwrap();
+        loop {
+            if let Ok(permit) = self.semaphore.try_acquire() {
+                return BlockingPermit {
+                    permit: Some(permit),
+                    limiter: self,
+                };
+            }
+            log::debug!("Concurrency limit reached, waiting for a request to finish");
+            guard = released.wait(guard).unwrap();
+        }
+    }
+}
+
+/// Permit of a blocking request, waking up a waiting thread when dropped.
+#[cfg(feature = "blocking")]
+pub(crate) struct BlockingPermit<'a> {
+    permit: Option<SemaphorePermit<'a>>,
+    limiter: &'a ConcurrencyLimiter,
+}
+
+#[cfg(feature = "blocking")]
+impl Drop for BlockingPermit<'_> {
+    fn drop(&mut self) {
+        self.permit.take();
+        let (lock, released) = &self.limiter.released;
+        let _guard = lock.lock().unwrap();
+        released.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{ManualClock, SystemClock};
+    #[cfg(feature = "governor")]
+    use governor::Quota;
+    #[cfg(feature = "governor")]
+    use std::num::NonZeroU32;
+
+    #[test]
+    fn test_try_acquire_exhausts_bucket() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60), Arc::new(SystemClock));
+
+        assert!(limiter.try_acquire().is_ok());
+        assert!(limiter.try_acquire().is_ok());
+
+        let wait = limiter.try_acquire().unwrap_err();
+        assert!(wait > Duration::from_secs(29) && wait <= Duration::from_secs(30));
+    }
+
+    #[tokio::test]
+    async fn test_acquire_waits_for_refill() {
+        let clock = ManualClock::new();
+        let limiter = RateLimiter::new(2, Duration::from_secs(60), Arc::new(clock.clone()));
+
+        for _ in 0..3 {
+            limiter.acquire().await;
+        }
+
+        assert_eq!(clock.get_elapsed(), Duration::from_secs(30));
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn test_acquire_blocking_waits_for_refill() {
+        let clock = ManualClock::new();
+        let limiter = RateLimiter::new(1, Duration::from_secs(60), Arc::new(clock.clone()));
+
+        limiter.acquire_blocking();
+        limiter.acquire_blocking();
+
+        assert_eq!(clock.get_elapsed(), Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limiter_caps_in_flight() {
+        let limiter = ConcurrencyLimiter::new(2);
+        let first = limiter.acquire().await;
+        let _second = limiter.acquire().await;
+
+        let third = tokio::time::timeout(Duration::from_millis(20), limiter.acquire()).await;
+        assert!(third.is_err());
+
+        drop(first);
+        let third = tokio::time::timeout(Duration::from_millis(20), limiter.acquire()).await;
+        assert!(third.is_ok());
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn test_concurrency_limiter_blocking_waits_for_release() {
+        let limiter = Arc::new(ConcurrencyLimiter::new(1));
+        let in_flight = Arc::new(Mutex::new((0, 0)));
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let limiter = limiter.clone();
+                let in_flight = in_flight.clone();
+                std::thread::spawn(move || {
+                    let _permit = limiter.acquire_blocking();
+                    {
+                        let mut counts = in_flight.lock().unwrap();
+                        counts.0 += 1;
+                        counts.1 = counts.1.max(counts.0);
+                    }
+                    std::thread::sleep(Duration::from_millis(5));
+                    in_flight.lock().unwrap().0 -= 1;
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(*in_flight.lock().unwrap(), (0, 1));
+    }
+
+    #[cfg(feature = "governor")]
+    #[tokio::test]
+    async fn test_governor_limiter_waits() {
+        let quota = Quota::with_period(Duration::from_millis(50)).unwrap();
+        let limiter = Limiter::Governor(Arc::new(DefaultDirectRateLimiter::direct(quota)), None);
+        let start = Instant::now();
+
+        limiter.acquire("key").await;
+        limiter.acquire("key").await;
+
+        assert!(start.elapsed() >= Duration::from_millis(45));
+    }
+
+    #[cfg(feature = "governor")]
+    #[tokio::test]
+    async fn test_keyed_governor_limiter_separates_keys() {
+        let quota = Quota::per_hour(NonZeroU32::new(1).unwrap());
+        let limiter = Arc::new(DefaultKeyedRateLimiter::keyed(quota));
+        let keyed = Limiter::KeyedGovernor(limiter.clone(), None);
+
+        keyed.acquire("first-key").await;
+        keyed.acquire("second-key").await;
+
+        assert!(limiter.check_key(&"first-key".to_string()).is_err());
+        assert!(limiter.check_key(&"third-key".to_string()).is_ok());
+    }
+}
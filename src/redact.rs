@@ -0,0 +1,264 @@
+use crate::error::{at_path, ApiClientError, ApiClientErrorResponse};
+use std::fmt;
+use url::Url;
+
+/// Query parameter NewsAPI accepts the API key in.
+pub(crate) const API_KEY_PARAM: &str = "apiKey";
+
+pub(crate) const REDACTED: &str = "REDACTED";
+
+/// Returns `url` with the value of any `apiKey` query parameter replaced, for use in logs,
+/// cache keys and response metadata.
+pub(crate) fn redact_url(url: &Url) -> Url {
+    redact_query(url, |key| key == API_KEY_PARAM)
+}
+
+/// Returns `url` with the values of the query parameters matching `redact` replaced.
+fn redact_query(url: &Url, redact: impl Fn(&str) -> bool) -> Url {
+    if !url.query_pairs().any(|(key, _)| redact(&key)) {
+        return url.clone();
+    }
+
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(key, value)| {
+            let value = if redact(&key) {
+                REDACTED.to_string()
+            } else {
+                value.into_owned()
+            };
+            (key.into_owned(), value)
+        })
+        .collect();
+
+    let mut redacted = url.clone();
+    redacted.query_pairs_mut().clear().extend_pairs(pairs);
+    redacted
+}
+
+/// Redacts the request URL that HTTP errors carry in their message.
+pub(crate) fn redact_error(error: ApiClientError) -> ApiClientError {
+    match error {
+        ApiClientError::Http(e) => match e.url().map(redact_url) {
+            Some(url) => ApiClientError::Http(e.with_url(url)),
+            None => ApiClientError::Http(e),
+        },
+        e => e,
+    }
+}
+
+/// What the client leaves out of its logs, see
+/// [`NewsApiClientBuilder::log_redaction`](crate::client::NewsApiClientBuilder::log_redaction).
+///
+/// The API key is always redacted from logged URLs and errors, whatever the settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogRedaction {
+    query_params: Vec<String>,
+    omit_bodies: bool,
+}
+
+impl LogRedaction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also redacts the value of the `name` query parameter from logged URLs, e.g. `q` to keep
+    /// search terms out of logs. The debug line echoing each request is then left out, since
+    /// it holds the same values.
+    pub fn redact_query_param(mut self, name: impl Into<String>) -> Self {
+        self.query_params.push(name.into());
+        self
+    }
+
+    /// Leaves content that came from the API out of logged errors: the API's error messages,
+    /// parse errors, which can quote the response body, and the details of transport errors.
+    /// The error kind and code are still logged.
+    pub fn omit_bodies(mut self, omit: bool) -> Self {
+        self.omit_bodies = omit;
+        self
+    }
+
+    /// `url` as it may be logged.
+    pub(crate) fn url(&self, url: &Url) -> Url {
+        redact_query(url, |key| {
+            key == API_KEY_PARAM || self.query_params.iter().any(|param| param == key)
+        })
+    }
+
+    /// Whether requests may be echoed in full.
+    pub(crate) fn logs_requests(&self) -> bool {
+        self.query_params.is_empty()
+    }
+
+    /// `error` as it may be logged.
+    pub(crate) fn error<'a>(&'a self, error: &'a ApiClientError) -> LoggedError<'a> {
+        LoggedError {
+            error,
+            redaction: self,
+        }
+    }
+}
+
+/// Formats an error for the logs according to a [`LogRedaction`].
+pub(crate) struct LoggedError<'a> {
+    error: &'a ApiClientError,
+    redaction: &'a LogRedaction,
+}
+
+impl fmt::Display for LoggedError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let redaction = self.redaction;
+        match self.error {
+            ApiClientError::Http(e) if redaction.omit_bodies || !redaction.logs_requests() => {
+                let kind = if e.is_timeout() {
+                    "timeout"
+                } else if e.is_connect() {
+                    "connection failed"
+                } else if e.is_body() || e.is_decode() {
+                    "failed to read response"
+                } else {
+                    "request failed"
+                };
+                write!(f, "HTTP error: {kind}")
+            }
+            ApiClientError::Transport(_) if redaction.omit_bodies => {
+                write!(f, "Transport error")
+            }
+            ApiClientError::InvalidRequest(_) if redaction.omit_bodies => {
+                write!(f, "Invalid request")
+            }
+            ApiClientError::ParseResponse {
+                context,
+                path,
+                source,
+            } if redaction.omit_bodies || !redaction.logs_requests() => {
+                write!(f, "Failed to parse response")?;
+                if let Some(status) = context.get_status() {
+                    write!(f, " (status {status})")?;
+                }
+                write!(f, "{}", at_path(path))?;
+                if !redaction.omit_bodies {
+                    write!(f, ": {source}")?;
+                }
+                Ok(())
+            }
+            ApiClientError::InvalidResponse(response) if redaction.omit_bodies => {
+                write_without_message(f, "Invalid response", response)
+            }
+            ApiClientError::RateLimited { response, .. } if redaction.omit_bodies => {
+                write_without_message(f, "Rate limited", response)
+            }
+            error => {
+                write!(f, "{error}")?;
+                match std::error::Error::source(error) {
+                    Some(source) => write!(f, ": {source}"),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// Formats an API error response without its message, which can quote the request.
+fn write_without_message(
+    f: &mut fmt::Formatter<'_>,
+    kind: &str,
+    response: &ApiClientErrorResponse,
+) -> fmt::Result {
+    write!(
+        f,
+        "{kind}: status={}, code={}",
+        response.status, response.code
+    )?;
+    match &response.request_id {
+        Some(request_id) => write!(f, ", request_id={request_id}"),
+        None => Ok(()),
+    }
+}
+
+/// Formats a secret without revealing it.
+pub(crate) struct Redacted;
+
+impl fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ResponseContext;
+
+    #[test]
+    fn test_redact_url() {
+        let url = Url::parse("https://newsapi.org/v2/everything?q=rust&apiKey=secret-key&page=2")
+            .unwrap();
+
+        let redacted = redact_url(&url).to_string();
+        assert!(!redacted.contains("secret-key"));
+        assert_eq!(
+            redacted,
+            "https://newsapi.org/v2/everything?q=rust&apiKey=REDACTED&page=2"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redact_error() {
+        let error = reqwest::get("http://127.0.0.1:1/v2/everything?apiKey=secret-key")
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("secret-key"));
+
+        let redacted = redact_error(ApiClientError::Http(error));
+        assert!(!redacted.to_string().contains("secret-key"));
+        assert!(!format!("{redacted:?}").contains("secret-key"));
+    }
+
+    #[test]
+    fn test_log_redaction() {
+        let redaction = LogRedaction::new()
+            .redact_query_param("q")
+            .omit_bodies(true);
+        let url = Url::parse("https://newsapi.org/v2/everything?q=secret+topic&apiKey=secret-key")
+            .unwrap();
+        let error = ApiClientError::InvalidRequest(
+            "Failed to parse response: invalid type: string \"article text\"".to_string(),
+        );
+
+        assert_eq!(
+            redaction.url(&url).as_str(),
+            "https://newsapi.org/v2/everything?q=REDACTED&apiKey=REDACTED"
+        );
+        assert!(!redaction.logs_requests());
+        assert_eq!(redaction.error(&error).to_string(), "Invalid request");
+        assert_eq!(
+            LogRedaction::new().error(&error).to_string(),
+            error.to_string()
+        );
+
+        let parse_error = ApiClientError::ParseResponse {
+            context: ResponseContext::new(&url, Some(200), "{\"status\": \"ok\"}"),
+            path: "articles[3].title".to_string(),
+            source: serde_json::from_str::<u32>("\"article text\"").unwrap_err(),
+        };
+        assert_eq!(
+            redaction.error(&parse_error).to_string(),
+            "Failed to parse response (status 200) at articles[3].title"
+        );
+        assert!(!parse_error.to_string().contains("secret-key"));
+        assert_eq!(
+            LogRedaction::new().error(&parse_error).to_string(),
+            format!(
+                "{parse_error}: {}",
+                std::error::Error::source(&parse_error).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_redact_url_without_key() {
+        let url = Url::parse("https://newsapi.org/v2/everything?q=a%20b").unwrap();
+        assert_eq!(redact_url(&url), url);
+    }
+}
@@ -0,0 +1,86 @@
+use crate::model::Country;
+
+/// Group of countries whose top headlines are fetched together, see
+/// [`NewsApiClient::get_top_headlines_for_region`](crate::client::NewsApiClient::get_top_headlines_for_region).
+///
+/// The built-in regions only include countries supported by the top headlines endpoint.
+#[derive(Debug, Clone)]
+pub enum Region {
+    /// Norway and Sweden
+    Nordics,
+    /// Germany, Austria and Switzerland
+    Dach,
+    /// Belgium and the Netherlands
+    Benelux,
+    /// Lithuania and Latvia
+    Baltics,
+    /// Argentina, Brazil, Colombia, Cuba, Mexico and Venezuela
+    Latam,
+    /// User-defined region
+    Custom {
+        name: String,
+        countries: Vec<Country>,
+    },
+}
+
+impl Region {
+    pub fn custom(name: impl Into<String>, countries: Vec<Country>) -> Self {
+        Region::Custom {
+            name: name.into(),
+            countries,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            Region::Nordics => "Nordics",
+            Region::Dach => "DACH",
+            Region::Benelux => "Benelux",
+            Region::Baltics => "Baltics",
+            Region::Latam => "LATAM",
+            Region::Custom { name, .. } => name,
+        }
+    }
+
+    /// Countries the region expands to.
+    pub fn get_countries(&self) -> Vec<Country> {
+        match self {
+            Region::Nordics => vec![Country::NO, Country::SE],
+            Region::Dach => vec![Country::DE, Country::AT, Country::CH],
+            Region::Benelux => vec![Country::BE, Country::NL],
+            Region::Baltics => vec![Country::LT, Country::LV],
+            Region::Latam => vec![
+                Country::AR,
+                Country::BR,
+                Country::CO,
+                Country::CU,
+                Country::MX,
+                Country::VE,
+            ],
+            Region::Custom { countries, .. } => countries.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(region: &Region) -> Vec<String> {
+        region
+            .get_countries()
+            .iter()
+            .map(|country| country.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_region_countries() {
+        assert_eq!(codes(&Region::Dach), vec!["de", "at", "ch"]);
+        assert_eq!(Region::Latam.get_name(), "LATAM");
+
+        let custom = Region::custom("Iberia", vec![Country::PT]);
+        assert_eq!(custom.get_name(), "Iberia");
+        assert_eq!(codes(&custom), vec!["pt"]);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::endpoint::Endpoint;
+use crate::error::ApiClientError;
+use metrics::{counter, histogram};
+use std::time::Duration;
+
+/// Requests sent to the API, retries included, labeled by `endpoint`.
+pub const REQUESTS_TOTAL: &str = "newsapi_requests_total";
+/// Failed requests, labeled by `endpoint` and `code`: the API's error code, such as
+/// `rateLimited`, or the kind of failure when the API did not answer, such as `http`.
+pub const ERRORS_TOTAL: &str = "newsapi_errors_total";
+/// Retries of failed calls, labeled by `endpoint`.
+pub const RETRIES_TOTAL: &str = "newsapi_retries_total";
+/// Request latency in seconds, labeled by `endpoint`.
+pub const REQUEST_DURATION_SECONDS: &str = "newsapi_request_duration_seconds";
+
+/// Records a request sent to the API with the installed `metrics` recorder.
+pub(crate) fn record_request(
+    endpoint: Endpoint,
+    latency: Duration,
+    error: Option<&ApiClientError>,
+) {
+    let endpoint = endpoint.to_string();
+    counter!(REQUESTS_TOTAL, "endpoint" => endpoint.clone()).increment(1);
+    histogram!(REQUEST_DURATION_SECONDS, "endpoint" => endpoint.clone()).record(latency);
+    if let Some(error) = error {
+        counter!(ERRORS_TOTAL, "endpoint" => endpoint, "code" => error_code(error)).increment(1);
+    }
+}
+
+/// Records a retry of a failed call with the installed `metrics` recorder.
+pub(crate) fn record_retry(endpoint: Endpoint) {
+    counter!(RETRIES_TOTAL, "endpoint" => endpoint.to_string()).increment(1);
+}
+
+fn error_code(error: &ApiClientError) -> String {
+    match error {
+        ApiClientError::InvalidResponse(response) => response.code.to_string(),
+        ApiClientError::RateLimited { response, .. } => response.code.to_string(),
+        ApiClientError::Http(_) => "http".to_string(),
+        ApiClientError::Transport(_) => "transport".to_string(),
+        ApiClientError::InvalidRequest(_) => "invalidRequest".to_string(),
+        ApiClientError::ParseResponse { .. } => "parseResponse".to_string(),
+        ApiClientError::InvalidHeaderValue(_) => "invalidHeaderValue".to_string(),
+        ApiClientError::Paused(_) => "paused".to_string(),
+        ApiClientError::QuotaExceeded { .. } => "quotaExceeded".to_string(),
+        ApiClientError::CircuitOpen(_) => "circuitOpen".to_string(),
+        ApiClientError::Offline(_) => "offline".to_string(),
+        ApiClientError::Cancelled => "cancelled".to_string(),
+        #[cfg(feature = "translate")]
+        ApiClientError::Translation(_) => "translation".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{ApiClientErrorCode, ApiClientErrorResponse};
+    use crate::model::ResponseStatus;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+    #[test]
+    fn test_records_requests_and_errors() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let error = ApiClientError::InvalidResponse(ApiClientErrorResponse {
+            status: ResponseStatus::Error,
+            code: ApiClientErrorCode::RateLimited,
+            message: "Too many requests".to_string(),
+            request_id: None,
+            context: None,
+        });
+
+        metrics::with_local_recorder(&recorder, || {
+            record_request(Endpoint::Everything, Duration::from_millis(100), None);
+            record_request(
+                Endpoint::Everything,
+                Duration::from_millis(50),
+                Some(&error),
+            );
+            record_retry(Endpoint::Everything);
+        });
+
+        let metrics: Vec<_> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .map(|(key, _, _, value)| {
+                let labels: Vec<String> = key
+                    .key()
+                    .labels()
+                    .map(|label| format!("{}={}", label.key(), label.value()))
+                    .collect();
+                (key.key().name().to_string(), labels.join(","), value)
+            })
+            .collect();
+        let find = |name: &str| {
+            metrics
+                .iter()
+                .find(|(metric, _, _)| metric == name)
+                .unwrap_or_else(|| panic!("{name} not recorded"))
+        };
+
+        assert_eq!(find(REQUESTS_TOTAL).2, DebugValue::Counter(2));
+        assert_eq!(find(RETRIES_TOTAL).2, DebugValue::Counter(1));
+        let errors = find(ERRORS_TOTAL);
+        assert_eq!(errors.1, "endpoint=everything,code=rateLimited");
+        assert_eq!(errors.2, DebugValue::Counter(1));
+        assert!(matches!(
+            &find(REQUEST_DURATION_SECONDS).2,
+            DebugValue::Histogram(values) if values.len() == 2
+        ));
+    }
+}
@@ -1,5 +1,81 @@
+use crate::clock::{Clock, SystemClock};
+use crate::error::{ApiClientError, ApiClientErrorCode};
 use std::future::Future;
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// A failed attempt that was retried, see
+/// [`ResponseMeta::get_retries`](crate::model::ResponseMeta::get_retries).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryAttempt {
+    error: String,
+    delay: Duration,
+}
+
+impl RetryAttempt {
+    pub(crate) fn new(error: &ApiClientError, delay: Duration) -> Self {
+        Self {
+            error: error.to_string(),
+            delay,
+        }
+    }
+
+    /// Message of the error that triggered the retry.
+    pub fn get_error(&self) -> &str {
+        &self.error
+    }
+
+    /// Delay before the retry was sent.
+    pub fn get_delay(&self) -> Duration {
+        self.delay
+    }
+}
+
+/// Callback invoked before each retry with the retry number, counted from one, the error that
+/// triggered it and the delay before it, see
+/// [`NewsApiClientBuilder::on_retry`](crate::client::NewsApiClientBuilder::on_retry).
+pub(crate) type RetryHook = Arc<dyn Fn(usize, &ApiClientError, Duration) + Send + Sync>;
+
+/// Which errors the client retries, see
+/// [`NewsApiClientBuilder::retry_on`](crate::client::NewsApiClientBuilder::retry_on).
+///
+/// Errors the client raises itself without sending a request, such as
+/// [`ApiClientError::Paused`] or [`ApiClientError::CircuitOpen`], are never retried, since
+/// waiting out the backoff would only return the same error.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RetryOn {
+    /// Only transport-level failures, never API error responses.
+    NetworkOnly,
+    /// Transport-level failures and `rateLimited` responses.
+    NetworkAndRateLimit,
+    /// Every network and API error.
+    #[default]
+    All,
+}
+
+impl RetryOn {
+    pub fn should_retry(&self, error: &ApiClientError) -> bool {
+        match (self, error) {
+            (
+                _,
+                ApiClientError::Paused(_)
+                | ApiClientError::QuotaExceeded { .. }
+                | ApiClientError::CircuitOpen(_)
+                | ApiClientError::Offline(_),
+            ) => false,
+            (RetryOn::All, _)
+            | (_, ApiClientError::Http(_))
+            | (_, ApiClientError::Transport(_)) => true,
+            (RetryOn::NetworkAndRateLimit, ApiClientError::RateLimited { response, .. }) => {
+                response.code != ApiClientErrorCode::ApiKeyExhausted
+            }
+            (RetryOn::NetworkAndRateLimit, ApiClientError::InvalidResponse(response)) => {
+                response.code == ApiClientErrorCode::RateLimited
+            }
+            _ => false,
+        }
+    }
+}
 
 #[derive(Debug, Clone, Copy, Default)]
 pub enum RetryStrategy {
@@ -7,81 +83,256 @@ pub enum RetryStrategy {
     None,
     Constant(Duration),
     Linear(Duration),
-    Exponential(Duration),
+    /// Doubles the delay on every retry, starting at `base` and never exceeding `max`.
+    Exponential {
+        base: Duration,
+        max: Duration,
+    },
+    /// Exponential backoff with full jitter: a random delay between zero and the exponential
+    /// delay, capped at `max`, so clients retrying in lockstep spread out.
+    ExponentialJitter {
+        base: Duration,
+        max: Duration,
+    },
+    /// Decorrelated jitter: a random delay between `base` and three times the previous delay,
+    /// capped at `max`.
+    DecorrelatedJitter {
+        base: Duration,
+        max: Duration,
+    },
+}
+
+impl RetryStrategy {
+    /// Delay before retry `attempt`, counted from zero, given the delay before the previous one.
+    fn delay(&self, attempt: usize, previous: Duration) -> Duration {
+        match *self {
+            RetryStrategy::None => Duration::from_secs(0),
+            RetryStrategy::Constant(d) => d,
+            RetryStrategy::Linear(d) => {
+                Duration::from_millis((d.as_millis() as u64) * (attempt + 1) as u64)
+            }
+            RetryStrategy::Exponential { base, max } => exponential(base, attempt).min(max),
+            RetryStrategy::ExponentialJitter { base, max } => exponential(base, attempt)
+                .min(max)
+                .mul_f64(rand::random::<f64>()),
+            RetryStrategy::DecorrelatedJitter { base, max } => {
+                let upper = previous.saturating_mul(3).max(base);
+                (base + (upper - base).mul_f64(rand::random::<f64>())).min(max)
+            }
+        }
+    }
+}
+
+fn exponential(base: Duration, attempt: usize) -> Duration {
+    Duration::from_millis(
+        (base.as_millis() as u64).saturating_mul(2_u64.saturating_pow(attempt as u32)),
+    )
 }
 
 pub async fn retry<F, T, E, Fut>(
     strategy: RetryStrategy,
     max_retries: usize,
+    operation: F,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    retry_if(strategy, max_retries, |_| true, operation).await
+}
+
+/// Like [`retry`], but only retries errors for which `should_retry` returns `true`.
+pub async fn retry_if<F, T, E, Fut, P>(
+    strategy: RetryStrategy,
+    max_retries: usize,
+    should_retry: P,
+    operation: F,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    P: Fn(&E) -> bool,
+{
+    retry_within(
+        strategy,
+        max_retries,
+        None,
+        should_retry,
+        |_, _, _| {},
+        &SystemClock,
+        operation,
+    )
+    .await
+}
+
+/// Like [`retry_if`], but never sleeps past `deadline`; the error is returned instead.
+/// `on_retry` is called before each retry, which waits on `clock`.
+pub(crate) async fn retry_within<F, T, E, Fut, P, O>(
+    delays: impl Into<Delays>,
+    max_retries: usize,
+    deadline: Option<Instant>,
+    should_retry: P,
+    on_retry: O,
+    clock: &dyn Clock,
     mut operation: F,
 ) -> Result<T, E>
 where
     F: FnMut() -> Fut,
     Fut: Future<Output = Result<T, E>>,
+    P: Fn(&E) -> bool,
+    O: Fn(usize, &E, Duration),
 {
-    match strategy {
-        RetryStrategy::None => operation().await,
-        _ => {
-            let mut attempt = 0;
-
-            loop {
-                match operation().await {
-                    Ok(result) => return Ok(result),
-                    Err(_e) if attempt < max_retries => {
-                        let delay = match strategy {
-                            RetryStrategy::None => Duration::from_secs(0),
-                            RetryStrategy::Constant(d) => d,
-                            RetryStrategy::Linear(d) => {
-                                Duration::from_millis((d.as_millis() as u64) * (attempt + 1) as u64)
-                            }
-                            RetryStrategy::Exponential(d) => Duration::from_millis(
-                                (d.as_millis() as u64) * (2_u64.pow(attempt as u32)),
-                            ),
-                        };
-                        tokio::time::sleep(delay).await;
-                        attempt += 1;
-                    }
-                    Err(e) => return Err(e),
-                }
+    let mut delays = delays.into();
+    let mut attempt = 0;
+
+    loop {
+        match operation().await {
+            Ok(result) => return Ok(result),
+            Err(e) if attempt < max_retries && should_retry(&e) => {
+                let Some(delay) = delays.next(attempt, deadline, clock) else {
+                    return Err(e);
+                };
+                on_retry(attempt + 1, &e, delay);
+                clock.sleep(delay).await;
+                attempt += 1;
             }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Source of the delays between retries: a [`RetryStrategy`], or a policy from the `backoff`
+/// crate.
+#[derive(Debug)]
+pub(crate) enum Delays {
+    Strategy {
+        strategy: RetryStrategy,
+        previous: Duration,
+    },
+    #[cfg(feature = "backoff")]
+    Backoff(backoff::ExponentialBackoff),
+}
+
+impl Delays {
+    /// Delay before retry `attempt`, counted from zero, or `None` to stop retrying, including
+    /// when the delay would end past `deadline`.
+    fn next(
+        &mut self,
+        attempt: usize,
+        deadline: Option<Instant>,
+        clock: &dyn Clock,
+    ) -> Option<Duration> {
+        let delay = match self {
+            Delays::Strategy {
+                strategy: RetryStrategy::None,
+                ..
+            } => None,
+            Delays::Strategy { strategy, previous } => {
+                *previous = strategy.delay(attempt, *previous);
+                Some(*previous)
+            }
+            #[cfg(feature = "backoff")]
+            Delays::Backoff(policy) => backoff::backoff::Backoff::next_backoff(policy),
+        }?;
+        fits_before(delay, deadline, clock).then_some(delay)
+    }
+}
+
+impl From<RetryStrategy> for Delays {
+    fn from(strategy: RetryStrategy) -> Self {
+        Delays::Strategy {
+            strategy,
+            previous: Duration::ZERO,
         }
     }
 }
 
+#[cfg(feature = "backoff")]
+impl From<backoff::ExponentialBackoff> for Delays {
+    /// Restarts the policy's elapsed time, so each call gets its full `max_elapsed_time`.
+    fn from(mut policy: backoff::ExponentialBackoff) -> Self {
+        backoff::backoff::Backoff::reset(&mut policy);
+        Delays::Backoff(policy)
+    }
+}
+
+/// Whether sleeping for `delay` still leaves time before `deadline`.
+fn fits_before(delay: Duration, deadline: Option<Instant>, clock: &dyn Clock) -> bool {
+    deadline.is_none_or(|deadline| {
+        clock
+            .now()
+            .checked_add(delay)
+            .is_some_and(|end| end < deadline)
+    })
+}
+
 #[cfg(feature = "blocking")]
 pub fn retry_blocking<F, T, E>(
     strategy: RetryStrategy,
     max_retries: usize,
+    operation: F,
+) -> Result<T, E>
+where
+    F: FnMut() -> Result<T, E>,
+{
+    retry_blocking_if(strategy, max_retries, |_| true, operation)
+}
+
+/// Like [`retry_blocking`], but only retries errors for which `should_retry` returns `true`.
+#[cfg(feature = "blocking")]
+pub fn retry_blocking_if<F, T, E, P>(
+    strategy: RetryStrategy,
+    max_retries: usize,
+    should_retry: P,
+    operation: F,
+) -> Result<T, E>
+where
+    F: FnMut() -> Result<T, E>,
+    P: Fn(&E) -> bool,
+{
+    retry_blocking_within(
+        strategy,
+        max_retries,
+        None,
+        should_retry,
+        |_, _, _| {},
+        &SystemClock,
+        operation,
+    )
+}
+
+/// Like [`retry_blocking_if`], but never sleeps past `deadline`; the error is returned
+/// instead. `on_retry` is called before each retry, which waits on `clock`.
+#[cfg(feature = "blocking")]
+pub(crate) fn retry_blocking_within<F, T, E, P, O>(
+    delays: impl Into<Delays>,
+    max_retries: usize,
+    deadline: Option<Instant>,
+    should_retry: P,
+    on_retry: O,
+    clock: &dyn Clock,
     mut operation: F,
 ) -> Result<T, E>
 where
     F: FnMut() -> Result<T, E>,
+    P: Fn(&E) -> bool,
+    O: Fn(usize, &E, Duration),
 {
-    match strategy {
-        RetryStrategy::None => operation(),
-        _ => {
-            let mut attempt = 0;
-
-            loop {
-                match operation() {
-                    Ok(result) => return Ok(result),
-                    Err(_e) if attempt < max_retries => {
-                        let delay = match strategy {
-                            RetryStrategy::None => Duration::from_secs(0),
-                            RetryStrategy::Constant(d) => d,
-                            RetryStrategy::Linear(d) => {
-                                Duration::from_millis((d.as_millis() as u64) * (attempt + 1) as u64)
-                            }
-                            RetryStrategy::Exponential(d) => Duration::from_millis(
-                                (d.as_millis() as u64) * (2_u64.pow(attempt as u32)),
-                            ),
-                        };
-                        std::thread::sleep(delay);
-                        attempt += 1;
-                    }
-                    Err(e) => return Err(e),
-                }
+    let mut delays = delays.into();
+    let mut attempt = 0;
+
+    loop {
+        match operation() {
+            Ok(result) => return Ok(result),
+            Err(e) if attempt < max_retries && should_retry(&e) => {
+                let Some(delay) = delays.next(attempt, deadline, clock) else {
+                    return Err(e);
+                };
+                on_retry(attempt + 1, &e, delay);
+                clock.sleep_blocking(delay);
+                attempt += 1;
             }
+            Err(e) => return Err(e),
         }
     }
 }
@@ -89,6 +340,8 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::ManualClock;
+    use crate::model::ResponseStatus;
 
     #[tokio::test]
     async fn test_retry_none() {
@@ -141,6 +394,245 @@ mod tests {
         assert_eq!(counter.get(), 3); // Initial attempt + 2 retries
     }
 
+    #[tokio::test]
+    async fn test_retry_within_calls_on_retry() {
+        let clock = ManualClock::new();
+        let retries = std::cell::RefCell::new(Vec::new());
+        let result = retry_within(
+            RetryStrategy::Linear(Duration::from_secs(60)),
+            2,
+            None,
+            |_| true,
+            |attempt, e: &&str, delay| retries.borrow_mut().push((attempt, *e, delay)),
+            &clock,
+            || async { Err::<i32, _>("always fails") },
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            *retries.borrow(),
+            vec![
+                (1, "always fails", Duration::from_secs(60)),
+                (2, "always fails", Duration::from_secs(120)),
+            ]
+        );
+        assert_eq!(clock.get_elapsed(), Duration::from_secs(180));
+    }
+
+    #[cfg(feature = "backoff")]
+    #[tokio::test]
+    async fn test_retry_within_backoff_policy() {
+        let policy = backoff::ExponentialBackoff {
+            initial_interval: Duration::from_millis(1),
+            randomization_factor: 0.0,
+            max_elapsed_time: Some(Duration::from_millis(50)),
+            ..Default::default()
+        };
+        let delays = std::cell::RefCell::new(Vec::new());
+        let result = retry_within(
+            policy,
+            3,
+            None,
+            |_| true,
+            |_, _: &&str, delay| delays.borrow_mut().push(delay),
+            &SystemClock,
+            || async { Err::<i32, _>("always fails") },
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            *delays.borrow(),
+            vec![
+                Duration::from_millis(1),
+                Duration::from_micros(1500),
+                Duration::from_micros(2250),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_exponential_delay_is_capped() {
+        let strategy = RetryStrategy::Exponential {
+            base: Duration::from_millis(100),
+            max: Duration::from_secs(1),
+        };
+        let delays: Vec<_> = (0..6)
+            .map(|attempt| strategy.delay(attempt, Duration::ZERO).as_millis())
+            .collect();
+
+        assert_eq!(delays, vec![100, 200, 400, 800, 1000, 1000]);
+        assert_eq!(
+            strategy.delay(usize::MAX, Duration::ZERO),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn test_jitter_delays_stay_in_bounds() {
+        let base = Duration::from_millis(100);
+        let jitter = RetryStrategy::ExponentialJitter {
+            base,
+            max: Duration::from_secs(1),
+        };
+        for attempt in 0..5 {
+            let delay = jitter.delay(attempt, Duration::ZERO);
+            assert!(delay <= exponential(base, attempt));
+        }
+        assert!(jitter.delay(usize::MAX, Duration::ZERO) <= Duration::from_secs(1));
+
+        let decorrelated = RetryStrategy::DecorrelatedJitter {
+            base,
+            max: Duration::from_secs(10),
+        };
+        let mut previous = Duration::ZERO;
+        for attempt in 0..5 {
+            let delay = decorrelated.delay(attempt, previous);
+            assert!(delay >= base && delay <= (previous * 3).max(base));
+            previous = delay;
+        }
+    }
+
+    #[test]
+    fn test_decorrelated_jitter_is_capped() {
+        let base = Duration::from_secs(1);
+        let max = Duration::from_secs(5);
+        let decorrelated = RetryStrategy::DecorrelatedJitter { base, max };
+
+        let mut previous = Duration::ZERO;
+        for attempt in 0..20 {
+            previous = decorrelated.delay(attempt, previous);
+            assert!(previous >= base && previous <= max);
+        }
+        assert!(decorrelated.delay(20, Duration::MAX) <= max);
+    }
+
+    #[tokio::test]
+    async fn test_retry_exponential_jitter() {
+        let counter = std::cell::Cell::new(0);
+        let result = retry(
+            RetryStrategy::ExponentialJitter {
+                base: Duration::from_millis(1),
+                max: Duration::from_millis(10),
+            },
+            3,
+            || async {
+                counter.set(counter.get() + 1);
+                Err::<i32, _>("always fails")
+            },
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(counter.get(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_retry_if_stops_on_unretryable_error() {
+        let counter = std::cell::Cell::new(0);
+        let result = retry_if(
+            RetryStrategy::Constant(Duration::from_millis(1)),
+            3,
+            |e: &&str| *e == "transient",
+            || async {
+                counter.set(counter.get() + 1);
+                Err::<i32, _>(if counter.get() < 2 {
+                    "transient"
+                } else {
+                    "fatal"
+                })
+            },
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err(), "fatal");
+        assert_eq!(counter.get(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_within_stops_at_deadline() {
+        let clock = ManualClock::new();
+        let counter = std::cell::Cell::new(0);
+        let result = retry_within(
+            RetryStrategy::Constant(Duration::from_millis(20)),
+            100,
+            Some(clock.now() + Duration::from_millis(50)),
+            |_| true,
+            |_, _, _| {},
+            &clock,
+            || async {
+                counter.set(counter.get() + 1);
+                Err::<i32, _>("always fails")
+            },
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(clock.get_elapsed(), Duration::from_millis(40));
+        assert_eq!(counter.get(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_within_saturated_delay_stops_at_deadline() {
+        let counter = std::cell::Cell::new(0);
+        let result = retry_within(
+            RetryStrategy::Constant(Duration::MAX),
+            3,
+            Some(Instant::now() + Duration::from_secs(60)),
+            |_| true,
+            |_, _, _| {},
+            &SystemClock,
+            || async {
+                counter.set(counter.get() + 1);
+                Err::<i32, _>("always fails")
+            },
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(counter.get(), 1);
+    }
+
+    #[test]
+    fn test_retry_on() {
+        let response = |code| {
+            ApiClientError::InvalidResponse(crate::error::ApiClientErrorResponse {
+                status: ResponseStatus::Error,
+                code,
+                message: String::new(),
+                request_id: None,
+                context: None,
+            })
+        };
+        let rate_limited = response(ApiClientErrorCode::RateLimited);
+        let unexpected = response(ApiClientErrorCode::UnexpectedError);
+
+        assert!(!RetryOn::NetworkOnly.should_retry(&rate_limited));
+        assert!(RetryOn::NetworkAndRateLimit.should_retry(&rate_limited));
+        assert!(!RetryOn::NetworkAndRateLimit.should_retry(&unexpected));
+        assert!(RetryOn::All.should_retry(&unexpected));
+    }
+
+    #[test]
+    fn test_retry_on_never_retries_local_errors() {
+        let now = chrono::Utc::now();
+        let local = [
+            ApiClientError::Paused(now),
+            ApiClientError::QuotaExceeded {
+                limit: 100,
+                resets_at: now,
+            },
+            ApiClientError::CircuitOpen(now),
+            ApiClientError::Offline("sources?".to_string()),
+        ];
+
+        for error in &local {
+            assert!(!RetryOn::All.should_retry(error), "{error}");
+            assert!(!RetryOn::NetworkAndRateLimit.should_retry(error), "{error}");
+        }
+    }
+
     #[cfg(feature = "blocking")]
     #[test]
     fn test_retry_blocking_function() {
@@ -0,0 +1,90 @@
+use crate::redact::Redacted;
+use std::fmt;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use zeroize::Zeroize;
+
+/// A secret such as an API key. It is wiped from memory when dropped, and its `Debug` and
+/// `Display` output is redacted. It implements no serialization, so it cannot leak through
+/// serialized state either.
+///
+/// Converts from `String` and `&str`, so it is accepted wherever a key was given as a string.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SecretString(String);
+
+impl SecretString {
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self(secret.into())
+    }
+
+    /// The secret itself. Avoid copying it into values that outlive the request.
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Stable identifier of the secret within this process, for keying state by it without
+    /// holding a copy.
+    pub(crate) fn fingerprint(&self) -> String {
+        let mut hasher = DefaultHasher::new();
+        self.0.hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        Self(secret)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(secret: &str) -> Self {
+        Self(secret.to_string())
+    }
+}
+
+impl From<&String> for SecretString {
+    fn from(secret: &String) -> Self {
+        Self(secret.clone())
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&Redacted, f)
+    }
+}
+
+impl fmt::Display for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&Redacted, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_is_redacted() {
+        let secret = SecretString::from("secret-api-key");
+
+        assert_eq!(secret.expose_secret(), "secret-api-key");
+        assert_eq!(format!("{secret:?}"), "REDACTED");
+        assert_eq!(secret.to_string(), "REDACTED");
+        assert_eq!(
+            secret.fingerprint(),
+            SecretString::new("secret-api-key".to_string()).fingerprint()
+        );
+        assert!(!secret.fingerprint().contains("secret"));
+    }
+}
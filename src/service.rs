@@ -0,0 +1,107 @@
+use crate::client::NewsApiClient;
+use crate::error::ApiClientError;
+use crate::model::{
+    GetEverythingRequest, GetEverythingResponse, GetSourcesRequest, GetSourcesResponse,
+    GetTopHeadlinesRequest, TopHeadlinesResponse,
+};
+use crate::transport::HttpTransport;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tower_service::Service;
+
+/// Future returned by the client's [`Service`] implementations.
+pub type ServiceFuture<R> = Pin<Box<dyn Future<Output = Result<R, ApiClientError>> + Send>>;
+
+/// Implements [`Service`] for a request type by calling the client method for it on a clone
+/// of the client, which shares its caches, limits and quota.
+macro_rules! impl_service {
+    ($request:ty, $response:ty, $method:ident) => {
+        impl<T: HttpTransport + Clone + 'static> Service<$request> for NewsApiClient<T> {
+            type Response = $response;
+            type Error = ApiClientError;
+            type Future = ServiceFuture<$response>;
+
+            /// Always ready: rate limits, concurrency limits and the quota are applied when
+            /// the request is sent.
+            fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+                Poll::Ready(Ok(()))
+            }
+
+            fn call(&mut self, request: $request) -> Self::Future {
+                let client = self.clone();
+                Box::pin(async move { client.$method(&request).await })
+            }
+        }
+    };
+}
+
+impl_service!(GetEverythingRequest, GetEverythingResponse, get_everything);
+impl_service!(
+    GetTopHeadlinesRequest,
+    TopHeadlinesResponse,
+    get_top_headlines
+);
+impl_service!(GetSourcesRequest, GetSourcesResponse, get_sources);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tower::{ServiceBuilder, ServiceExt};
+
+    #[tokio::test]
+    async fn test_service_with_middleware() {
+        let mut server = mockito::Server::new_async().await;
+        let m = server
+            .mock("GET", "/v2/top-headlines/sources")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"status":"ok","sources":[]}"#)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .build()
+            .unwrap();
+        let service = ServiceBuilder::new()
+            .timeout(Duration::from_secs(5))
+            .service(client);
+
+        let response = service
+            .oneshot(GetSourcesRequest::builder().build())
+            .await
+            .unwrap();
+
+        assert!(response.get_sources().is_empty());
+        m.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_service_error() {
+        let mut server = mockito::Server::new_async().await;
+        let _m = server
+            .mock("GET", "/v2/everything")
+            .match_query(mockito::Matcher::Any)
+            .with_status(401)
+            .with_body(r#"{"status":"error","code":"apiKeyInvalid","message":"Invalid key"}"#)
+            .create_async()
+            .await;
+
+        let client = NewsApiClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.url())
+            .retry(crate::retry::RetryStrategy::None, 0)
+            .build()
+            .unwrap();
+        let request = GetEverythingRequest::builder()
+            .search_term("rust".to_string())
+            .build();
+
+        let result = client.oneshot(request).await;
+
+        assert!(matches!(result, Err(ApiClientError::InvalidResponse(_))));
+    }
+}
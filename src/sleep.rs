@@ -0,0 +1,59 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Waits out the delays between retries, see
+/// [`NewsApiClientBuilder::sleeper`](crate::client::NewsApiClientBuilder::sleeper).
+///
+/// Replacing the default [`TokioSleeper`] with an [`InstantSleeper`] lets tests exercise a
+/// retry configuration without waiting. Async tests can also pause tokio's clock with
+/// `tokio::time::pause`, which the default sleeper respects.
+pub trait Sleeper: fmt::Debug + Send + Sync {
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()> + Send>>;
+
+    fn sleep_blocking(&self, duration: Duration);
+}
+
+/// Sleeps with `tokio::time::sleep`, or `std::thread::sleep` for the blocking client.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioSleeper;
+
+impl Sleeper for TokioSleeper {
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+
+    fn sleep_blocking(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+/// Returns immediately, recording the requested delays. Clones share the recording.
+#[derive(Debug, Clone, Default)]
+pub struct InstantSleeper {
+    sleeps: Arc<Mutex<Vec<Duration>>>,
+}
+
+impl InstantSleeper {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The delays slept so far, oldest first.
+    pub fn get_sleeps(&self) -> Vec<Duration> {
+        self.sleeps.lock().unwrap().clone()
+    }
+}
+
+impl Sleeper for InstantSleeper {
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        self.sleep_blocking(duration);
+        Box::pin(std::future::ready(()))
+    }
+
+    fn sleep_blocking(&self, duration: Duration) {
+        self.sleeps.lock().unwrap().push(duration);
+    }
+}
@@ -0,0 +1,131 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Length in characters of summaries the client synthesizes by default, see
+/// [`NewsApiClientBuilder::summary_length`](crate::client::NewsApiClientBuilder::summary_length).
+pub const DEFAULT_SUMMARY_LENGTH: usize = 280;
+
+/// The marker NewsAPI appends to truncated content, e.g. `… [+2817 chars]`.
+static TRUNCATION_MARKER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"…?\s*\[\+\d+ chars\]\s*$").unwrap());
+
+/// Synthesizes a summary of at most `max_chars` characters from an article's description and
+/// the truncated content the API returns, without fetching the article itself.
+///
+/// Content that repeats the description is used instead of it, otherwise the content
+/// continues the description. The summary ends at a sentence boundary where one fits, and is
+/// cut at a word with an ellipsis otherwise.
+pub(crate) fn synthesize(
+    description: Option<&str>,
+    content: Option<&str>,
+    max_chars: usize,
+) -> Option<String> {
+    let description = description.map(str::trim).filter(|text| !text.is_empty());
+    let (content, truncated) = match content {
+        Some(content) => {
+            let stripped = TRUNCATION_MARKER.replace(content, "");
+            let truncated = stripped.len() != content.len();
+            (Some(stripped.trim().to_string()), truncated)
+        }
+        None => (None, false),
+    };
+    let content = content.filter(|text| !text.is_empty());
+
+    let (text, truncated) = match (description, content) {
+        (Some(description), Some(content)) if content.contains(description) => (content, truncated),
+        (Some(description), Some(content)) => (format!("{description} {content}"), truncated),
+        (Some(description), None) => (description.to_string(), false),
+        (None, Some(content)) => (content, truncated),
+        (None, None) => return None,
+    };
+
+    // The last sentence of truncated content is incomplete.
+    let text = match (truncated, last_sentence_end(&text)) {
+        (true, Some(end)) => text[..end].to_string(),
+        _ => text,
+    };
+    Some(shorten(&text, max_chars))
+}
+
+/// `text` shortened to at most `max_chars` characters, preferring a sentence boundary.
+fn shorten(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let prefix_end = text
+        .char_indices()
+        .nth(max_chars)
+        .map_or(text.len(), |(index, _)| index);
+    let prefix = &text[..prefix_end];
+    if let Some(end) = last_sentence_end(prefix) {
+        return prefix[..end].to_string();
+    }
+
+    // Leave room for the ellipsis.
+    let prefix_end = prefix.char_indices().last().map_or(0, |(index, _)| index);
+    let prefix = &prefix[..prefix_end];
+    let cut = prefix.rfind(char::is_whitespace).unwrap_or(prefix.len());
+    let mut shortened = prefix[..cut]
+        .trim_end_matches(|c: char| c.is_whitespace() || c.is_ascii_punctuation())
+        .to_string();
+    shortened.push('…');
+    shortened
+}
+
+/// Byte offset just past the last complete sentence of `text`.
+fn last_sentence_end(text: &str) -> Option<usize> {
+    let mut chars = text.char_indices().peekable();
+    let mut end = None;
+    while let Some((index, c)) = chars.next() {
+        if matches!(c, '.' | '!' | '?') && chars.peek().is_none_or(|(_, next)| next.is_whitespace())
+        {
+            end = Some(index + c.len_utf8());
+        }
+    }
+    end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_continues_description_with_content() {
+        let summary = synthesize(
+            Some("Markets rallied on Monday."),
+            Some("Analysts expect more gains. Traders were cautious about… [+2817 chars]"),
+            280,
+        );
+        assert_eq!(
+            summary.as_deref(),
+            Some("Markets rallied on Monday. Analysts expect more gains.")
+        );
+
+        let repeated = synthesize(
+            Some("Markets rallied on Monday."),
+            Some("Markets rallied on Monday. Analysts expect more gains."),
+            280,
+        );
+        assert_eq!(
+            repeated.as_deref(),
+            Some("Markets rallied on Monday. Analysts expect more gains.")
+        );
+        assert_eq!(synthesize(Some("  "), None, 280), None);
+    }
+
+    #[test]
+    fn test_shortens_at_sentence_or_word() {
+        let text = "First sentence here. Second sentence is longer than the limit allows.";
+        assert_eq!(shorten(text, 40), "First sentence here.");
+        assert_eq!(
+            shorten("One very long sentence without end", 16),
+            "One very long…"
+        );
+        assert!(
+            shorten("Ünïcödé wörds everywhere in this text", 10)
+                .chars()
+                .count()
+                <= 10
+        );
+    }
+}
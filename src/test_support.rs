@@ -0,0 +1,302 @@
+use crate::model::{
+    Article, GetEverythingResponse, GetSourcesResponse, Source, TopHeadlinesResponse,
+};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// Placeholder for volatile values in a response's `redacted_snapshot`.
+pub const REDACTED: &str = "[redacted]";
+
+/// Sources used by the fakes and the mock server: id, name, category, language and country.
+pub(crate) const SOURCES: [SourceRow; 8] = [
+    ("mock-times", "Mock Times", "general", "en", "us"),
+    ("tech-daily", "Tech Daily", "technology", "en", "us"),
+    ("market-wire", "Market Wire", "business", "en", "gb"),
+    ("sport-ticker", "Sport Ticker", "sports", "en", "au"),
+    ("science-weekly", "Science Weekly", "science", "en", "ca"),
+    ("health-now", "Health Now", "health", "en", "us"),
+    ("le-journal", "Le Journal", "general", "fr", "fr"),
+    ("die-zeitung", "Die Zeitung", "general", "de", "de"),
+];
+
+const ADJECTIVES: [&str; 8] = [
+    "Surprising",
+    "Major",
+    "Quiet",
+    "Record",
+    "Unexpected",
+    "Growing",
+    "New",
+    "Bold",
+];
+
+const NOUNS: [&str; 8] = [
+    "Breakthrough",
+    "Shift",
+    "Debate",
+    "Launch",
+    "Report",
+    "Deal",
+    "Setback",
+    "Rally",
+];
+
+pub(crate) type SourceRow = (
+    &'static str,
+    &'static str,
+    &'static str,
+    &'static str,
+    &'static str,
+);
+
+/// Generates reproducible articles for tests and snapshot fixtures.
+///
+/// The same seed and settings always produce the same articles. Articles are ordered newest
+/// first with publication times spread over [`published_between`](Self::published_between),
+/// which defaults to the 30 days before 2024-01-01.
+#[derive(Debug, Clone)]
+pub struct ArticleFaker {
+    seed: u64,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    term: Option<String>,
+    sources: Vec<SourceRow>,
+}
+
+impl ArticleFaker {
+    pub fn new(seed: u64) -> Self {
+        let to = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        Self {
+            seed,
+            from: to - Duration::days(30),
+            to,
+            term: None,
+            sources: SOURCES.to_vec(),
+        }
+    }
+
+    /// Range the publication times are distributed over. The bounds may be given in either
+    /// order.
+    pub fn published_between(mut self, from: DateTime<Utc>, to: DateTime<Utc>) -> Self {
+        self.from = from.min(to);
+        self.to = from.max(to);
+        self
+    }
+
+    /// Mentions `term` in every title, like the results of a search for it.
+    pub fn about(mut self, term: impl Into<String>) -> Self {
+        self.term = Some(term.into());
+        self
+    }
+
+    /// Restricts the articles to sources with the given ids, falling back to all sources if
+    /// none match.
+    pub fn from_sources<I, S>(mut self, ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let ids: Vec<S> = ids.into_iter().collect();
+        let sources: Vec<_> = SOURCES
+            .into_iter()
+            .filter(|(id, ..)| ids.iter().any(|i| i.as_ref() == *id))
+            .collect();
+        self.sources = if sources.is_empty() {
+            SOURCES.to_vec()
+        } else {
+            sources
+        };
+        self
+    }
+
+    /// The first `count` articles.
+    pub fn articles(&self, count: usize) -> Vec<Article> {
+        (0..count).map(|index| self.article(index, count)).collect()
+    }
+
+    /// The article at `index` of `count` articles, without generating the others.
+    pub fn article(&self, index: usize, count: usize) -> Article {
+        serde_json::from_value(self.article_json(index, count, &self.sources))
+            .expect("generated articles are valid")
+    }
+
+    /// Every source the fakes draw from.
+    pub fn sources() -> Vec<Source> {
+        SOURCES
+            .into_iter()
+            .map(|source| {
+                serde_json::from_value(source_json(source, true))
+                    .expect("generated sources are valid")
+            })
+            .collect()
+    }
+
+    /// Each article depends only on the seed, the settings and its position, so any page of
+    /// a sequence can be generated on its own.
+    pub(crate) fn article_json(&self, index: usize, count: usize, sources: &[SourceRow]) -> Value {
+        let seed = self.seed;
+        let mut rng = StdRng::seed_from_u64(seed.wrapping_add(index as u64));
+        let source = *sources.choose(&mut rng).expect("sources are not empty");
+        let subject = self
+            .term
+            .as_deref()
+            .map_or_else(|| capitalize(source.2), capitalize);
+        let title = format!(
+            "{} {} in {subject}",
+            ADJECTIVES
+                .choose(&mut rng)
+                .expect("adjectives are not empty"),
+            NOUNS.choose(&mut rng).expect("nouns are not empty"),
+        );
+
+        // Each article gets an equal slot of the range, newest first.
+        let slot = (self.to - self.from).num_seconds().max(0) / count.max(1) as i64;
+        let offset = slot * index as i64 + rng.random_range(0..slot.max(1));
+        let published_at = self.to - Duration::seconds(offset);
+
+        json!({
+            "source": source_json(source, false),
+            "author": format!("Author {}", rng.random_range(1..=50)),
+            "title": title,
+            "description": format!("{title}, as reported by {}.", source.1),
+            "url": format!("https://{}.example.com/articles/{seed:x}-{index}", source.0),
+            "urlToImage": format!("https://{}.example.com/images/{seed:x}-{index}.jpg", source.0),
+            "publishedAt": published_at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            "content": format!("{title}. Generated article {index} for testing."),
+        })
+    }
+}
+
+impl GetEverythingResponse {
+    /// Pretty-printed JSON of the response for snapshot tests, with the total result count and
+    /// publication times replaced by [`REDACTED`] so that fixtures stay stable across runs.
+    pub fn redacted_snapshot(&self) -> String {
+        redacted_snapshot(self)
+    }
+}
+
+impl TopHeadlinesResponse {
+    /// See [`GetEverythingResponse::redacted_snapshot`].
+    pub fn redacted_snapshot(&self) -> String {
+        redacted_snapshot(self)
+    }
+}
+
+impl GetSourcesResponse {
+    /// Pretty-printed JSON of the response for snapshot tests. Sources hold no volatile values.
+    pub fn redacted_snapshot(&self) -> String {
+        redacted_snapshot(self)
+    }
+}
+
+fn redacted_snapshot(response: &impl Serialize) -> String {
+    let mut value = serde_json::to_value(response).expect("responses serialize to JSON");
+    if let Some(total_results) = value.get_mut("totalResults") {
+        *total_results = REDACTED.into();
+    }
+    if let Some(Value::Array(articles)) = value.get_mut("articles") {
+        for published_at in articles
+            .iter_mut()
+            .filter_map(|article| article.get_mut("publishedAt"))
+        {
+            *published_at = REDACTED.into();
+        }
+    }
+    serde_json::to_string_pretty(&value).expect("JSON values serialize")
+}
+
+pub(crate) fn source_json(source: SourceRow, full: bool) -> Value {
+    let (id, name, category, language, country) = source;
+    if !full {
+        return json!({"id": id, "name": name});
+    }
+    json!({
+        "id": id,
+        "name": name,
+        "description": format!("{name} generated by the NewsAPI mock server."),
+        "url": format!("https://{id}.example.com"),
+        "category": category,
+        "language": language,
+        "country": country,
+    })
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_articles_are_reproducible_and_spread_over_range() {
+        let to = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let from = to - Duration::days(10);
+        let faker = ArticleFaker::new(42)
+            .published_between(to, from)
+            .about("rust")
+            .from_sources(["tech-daily"]);
+
+        let articles = faker.articles(10);
+        let dates: Vec<DateTime<Utc>> = articles
+            .iter()
+            .map(|article| *article.get_published_at())
+            .collect();
+
+        assert!(dates.windows(2).all(|pair| pair[0] > pair[1]));
+        assert!(dates[0] > to - Duration::days(1) && dates[0] <= to);
+        assert!(dates[9] >= from && dates[9] < from + Duration::days(1));
+        assert!(articles.iter().all(|article| {
+            article.get_title().ends_with("in Rust")
+                && article.get_source().get_id().map(String::as_str) == Some("tech-daily")
+        }));
+
+        let again = ArticleFaker::new(42)
+            .published_between(from, to)
+            .about("rust")
+            .from_sources(["tech-daily"])
+            .article(9, 10);
+        assert_eq!(again.get_url(), articles[9].get_url());
+        assert_eq!(again.get_published_at(), articles[9].get_published_at());
+        assert_ne!(
+            ArticleFaker::new(43).articles(10)[0].get_url(),
+            ArticleFaker::new(42).articles(10)[0].get_url()
+        );
+    }
+
+    #[test]
+    fn test_redacted_snapshot_is_stable() {
+        let response = |total_results: usize, to| {
+            let articles: Vec<Value> = ArticleFaker::new(7)
+                .published_between(to - Duration::days(1), to)
+                .articles(2)
+                .iter()
+                .map(|article| serde_json::to_value(article).unwrap())
+                .collect();
+            serde_json::from_value::<GetEverythingResponse>(json!({
+                "status": "ok",
+                "totalResults": total_results,
+                "articles": articles,
+            }))
+            .unwrap()
+        };
+        let day = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+
+        let snapshot = response(120, day).redacted_snapshot();
+        assert_eq!(
+            snapshot,
+            response(95, day + Duration::days(3)).redacted_snapshot()
+        );
+        assert!(snapshot.contains(r#""totalResults": "[redacted]""#));
+        assert!(snapshot.contains(r#""publishedAt": "[redacted]""#));
+        assert!(!snapshot.contains("2024-"));
+    }
+}
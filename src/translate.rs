@@ -0,0 +1,127 @@
+use crate::error::ApiClientError;
+use crate::model::{Article, Language};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+/// Translates article text, e.g. through a translation service, so that results from
+/// several languages can be monitored in one.
+///
+/// No implementation is bundled; see the `http_translator` example for one calling a
+/// user-supplied HTTP endpoint.
+pub trait Translator: fmt::Debug + Send + Sync {
+    /// Translates `texts` into `target`, returning one translation per text, in order.
+    fn translate<'a>(
+        &'a self,
+        texts: &'a [String],
+        target: &'a Language,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<String>, ApiClientError>> + Send + 'a>>;
+}
+
+/// Translates the titles and descriptions of `articles` into `target` in a single batch.
+///
+/// The articles are left unchanged if the translator fails or returns the wrong number of
+/// translations.
+pub async fn translate_articles(
+    translator: &dyn Translator,
+    articles: &mut [Article],
+    target: &Language,
+) -> Result<(), ApiClientError> {
+    let texts: Vec<String> = articles
+        .iter()
+        .flat_map(|article| {
+            std::iter::once(article.get_title().clone()).chain(article.get_description().clone())
+        })
+        .collect();
+    if texts.is_empty() {
+        return Ok(());
+    }
+
+    let translated = translator.translate(&texts, target).await?;
+    if translated.len() != texts.len() {
+        return Err(ApiClientError::Translation(format!(
+            "expected {} translations, got {}",
+            texts.len(),
+            translated.len()
+        )));
+    }
+
+    let mut translated = translated.into_iter();
+    for article in articles {
+        let has_description = article.get_description().is_some();
+        article.set_title(translated.next().expect("one translation per text"));
+        if has_description {
+            article.set_description(translated.next());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn article(title: &str, description: Option<&str>) -> Article {
+        serde_json::from_value(json!({
+            "source": {"id": null, "name": "Le Journal"},
+            "author": null,
+            "title": title,
+            "description": description,
+            "url": "https://example.com",
+            "urlToImage": null,
+            "publishedAt": "2024-01-01T00:00:00Z",
+            "content": null,
+        }))
+        .unwrap()
+    }
+
+    #[derive(Debug)]
+    struct Shouting {
+        drop_last: bool,
+    }
+
+    impl Translator for Shouting {
+        fn translate<'a>(
+            &'a self,
+            texts: &'a [String],
+            target: &'a Language,
+        ) -> Pin<Box<dyn Future<Output = Result<Vec<String>, ApiClientError>> + Send + 'a>>
+        {
+            Box::pin(async move {
+                let mut translated: Vec<String> = texts
+                    .iter()
+                    .map(|text| format!("[{target}] {}", text.to_uppercase()))
+                    .collect();
+                if self.drop_last {
+                    translated.pop();
+                }
+                Ok(translated)
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_translate_articles() {
+        let mut articles = vec![
+            article("bonjour", Some("le monde")),
+            article("au revoir", None),
+        ];
+
+        translate_articles(&Shouting { drop_last: false }, &mut articles, &Language::EN)
+            .await
+            .unwrap();
+        assert_eq!(articles[0].get_title(), "[en] BONJOUR");
+        assert_eq!(
+            articles[0].get_description().as_deref(),
+            Some("[en] LE MONDE")
+        );
+        assert_eq!(articles[1].get_title(), "[en] AU REVOIR");
+        assert_eq!(*articles[1].get_description(), None);
+
+        let result =
+            translate_articles(&Shouting { drop_last: true }, &mut articles, &Language::DE).await;
+        assert!(matches!(result, Err(ApiClientError::Translation(_))));
+        assert_eq!(articles[1].get_title(), "[en] AU REVOIR");
+    }
+}
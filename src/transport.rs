@@ -0,0 +1,216 @@
+use crate::error::ApiClientError;
+use crate::redact::redact_url;
+use reqwest::header::{HeaderMap, CONTENT_LENGTH};
+use reqwest::{Method, StatusCode};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use url::Url;
+use zeroize::Zeroizing;
+
+/// A request for an [`HttpTransport`] to send.
+///
+/// The URL carries the API key when the client authenticates with a query parameter, so it is
+/// zeroized on drop and redacted from debug output.
+#[derive(Clone)]
+pub struct HttpRequest {
+    method: Method,
+    url: Zeroizing<String>,
+    headers: HeaderMap,
+    timeout: Option<Duration>,
+}
+
+impl HttpRequest {
+    pub fn new(method: Method, url: impl Into<String>) -> Self {
+        Self {
+            method,
+            url: Zeroizing::new(url.into()),
+            headers: HeaderMap::new(),
+            timeout: None,
+        }
+    }
+
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Time allowed for the whole request, from connecting until the body has been read.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn get_method(&self) -> &Method {
+        &self.method
+    }
+
+    pub fn get_url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn get_headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    pub fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+}
+
+impl fmt::Debug for HttpRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let url = Url::parse(&self.url)
+            .map(|url| redact_url(&url).to_string())
+            .unwrap_or_else(|_| "<invalid>".to_string());
+        f.debug_struct("HttpRequest")
+            .field("method", &self.method)
+            .field("url", &url)
+            .field("headers", &self.headers)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+/// The response an [`HttpTransport`] received, with its body read in full.
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn new(status: StatusCode, headers: HeaderMap, body: Vec<u8>) -> Self {
+        Self {
+            status,
+            headers,
+            body,
+        }
+    }
+
+    pub fn get_status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn get_headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    pub fn get_body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Bytes received on the wire according to `Content-Length`, which is smaller than the
+    /// body when the response was compressed.
+    pub(crate) fn content_length(&self) -> Option<u64> {
+        self.headers
+            .get(CONTENT_LENGTH)?
+            .to_str()
+            .ok()?
+            .parse()
+            .ok()
+    }
+
+    /// The body as text, replacing invalid UTF-8.
+    pub(crate) fn into_text(self) -> String {
+        match String::from_utf8(self.body) {
+            Ok(text) => text,
+            Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+        }
+    }
+}
+
+/// Sends the async client's HTTP requests, so that a backend other than reqwest can be
+/// plugged in with [`NewsApiClientBuilder::build_with_transport`].
+///
+/// Implemented for [`reqwest::Client`], the default. Failures other than reqwest's are
+/// reported as [`ApiClientError::Transport`], which the client retries and counts against
+/// the circuit breaker like any network error.
+///
+/// [`NewsApiClientBuilder::build_with_transport`]:
+///     crate::client::NewsApiClientBuilder::build_with_transport
+pub trait HttpTransport: fmt::Debug + Send + Sync {
+    /// Sends `request` and reads the response body, whatever its status.
+    fn send(
+        &self,
+        request: HttpRequest,
+    ) -> Pin<Box<dyn Future<Output = Result<HttpResponse, ApiClientError>> + Send + '_>>;
+}
+
+impl HttpTransport for reqwest::Client {
+    fn send(
+        &self,
+        request: HttpRequest,
+    ) -> Pin<Box<dyn Future<Output = Result<HttpResponse, ApiClientError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut builder = self
+                .request(request.method.clone(), request.get_url())
+                .headers(request.headers.clone());
+            if let Some(timeout) = request.timeout {
+                builder = builder.timeout(timeout);
+            }
+            let response = builder.send().await?;
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = response.bytes().await?;
+            Ok(HttpResponse::new(status, headers, body.into()))
+        })
+    }
+}
+
+/// Sends the blocking client's HTTP requests.
+///
+/// Implemented for [`reqwest::blocking::Client`], which the blocking client methods are
+/// available on.
+#[cfg(feature = "blocking")]
+pub trait BlockingHttpTransport: fmt::Debug + Send + Sync {
+    /// Sends `request` and reads the response body, whatever its status.
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse, ApiClientError>;
+}
+
+#[cfg(feature = "blocking")]
+impl BlockingHttpTransport for reqwest::blocking::Client {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse, ApiClientError> {
+        let mut builder = self
+            .request(request.method.clone(), request.get_url())
+            .headers(request.headers.clone());
+        if let Some(timeout) = request.timeout {
+            builder = builder.timeout(timeout);
+        }
+        let response = builder.send()?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.bytes()?;
+        Ok(HttpResponse::new(status, headers, body.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_debug_redacts_api_key() {
+        let request = HttpRequest::new(
+            Method::GET,
+            "https://newsapi.org/v2/everything?q=rust&apiKey=test-api-key",
+        );
+
+        let debug = format!("{request:?}");
+
+        assert!(!debug.contains("test-api-key"));
+        assert!(debug.contains("q=rust"));
+    }
+
+    #[test]
+    fn test_response_content_length_and_text() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_LENGTH, "12".parse().unwrap());
+        let response = HttpResponse::new(StatusCode::OK, headers, b"{\"ok\":true}".to_vec());
+
+        assert_eq!(response.content_length(), Some(12));
+        assert_eq!(response.into_text(), "{\"ok\":true}");
+    }
+}